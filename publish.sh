@@ -20,4 +20,7 @@ cargo publish &&
 cd ../ &&
 cd tank-sqlite/ &&
 cargo publish &&
+cd ../ &&
+cd tank-cli/ &&
+cargo publish &&
 cd ../
@@ -0,0 +1,88 @@
+use crate::DIALECTS;
+use clap::Parser;
+use std::{fs, io::Write, path::PathBuf};
+use tank_core::{Driver, Error, Result, SchemaExport, SqlWriter};
+
+/// Entities of an application, rendered by `export`.
+///
+/// *Example:*
+/// ```ignore
+/// struct Entities;
+///
+/// impl EntitySet for Entities {
+///     fn register<W: SqlWriter>(export: SchemaExport<W>) -> SchemaExport<W> {
+///         export.entity::<Unit>().entity::<Soldier>()
+///     }
+/// }
+/// ```
+pub trait EntitySet {
+    /// Adds the entities to `export`.
+    fn register<W: SqlWriter>(export: SchemaExport<W>) -> SchemaExport<W>;
+}
+
+/// Arguments of `export`, parsed by a binary of the application declaring the entities.
+///
+/// *Example:*
+/// ```ignore
+/// fn main() -> tank::Result<()> {
+///     tank_cli::export::<Entities>(ExportCli::parse(), &mut std::io::stdout())
+/// }
+/// ```
+#[derive(Debug, Parser)]
+#[command(name = "tank-export", version)]
+pub struct ExportCli {
+    /// Dialect of the DDL.
+    #[arg(short, long)]
+    pub dialect: String,
+    /// Guard the CREATE statements with IF NOT EXISTS.
+    #[arg(long)]
+    pub if_not_exists: bool,
+    /// File receiving the DDL, the output stream by default.
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+/// Renders the DDL creating the entities of `S` in the dialect requested, without a connection.
+pub fn export<S: EntitySet>(cli: ExportCli, out: &mut impl Write) -> Result<()> {
+    let ddl = match cli.dialect.as_str() {
+        #[cfg(feature = "duckdb")]
+        tank_duckdb::DuckDBDriver::NAME => export_sql::<S, _>(
+            tank_duckdb::DuckDBDriver::new().sql_writer(),
+            cli.if_not_exists,
+        ),
+        #[cfg(feature = "mysql")]
+        tank_mysql::MySQLDriver::NAME => export_sql::<S, _>(
+            tank_mysql::MySQLDriver::new().sql_writer(),
+            cli.if_not_exists,
+        ),
+        #[cfg(feature = "postgres")]
+        tank_postgres::PostgresDriver::NAME => export_sql::<S, _>(
+            tank_postgres::PostgresDriver::new().sql_writer(),
+            cli.if_not_exists,
+        ),
+        #[cfg(feature = "sqlite")]
+        tank_sqlite::SQLiteDriver::NAME => export_sql::<S, _>(
+            tank_sqlite::SQLiteDriver::new().sql_writer(),
+            cli.if_not_exists,
+        ),
+        dialect => {
+            return Err(Error::msg(format!(
+                "Unsupported dialect `{}`, expected one of: {}",
+                dialect,
+                DIALECTS.join(", "),
+            )));
+        }
+    };
+    match cli.output {
+        Some(path) => fs::write(&path, ddl + "\n")
+            .map_err(|e| Error::new(e).context(format!("Failed to write `{}`", path.display())))?,
+        None => writeln!(out, "{}", ddl)?,
+    }
+    Ok(())
+}
+
+fn export_sql<S: EntitySet, W: SqlWriter>(writer: W, if_not_exists: bool) -> String {
+    S::register(SchemaExport::new(writer))
+        .if_not_exists(if_not_exists)
+        .to_sql()
+}
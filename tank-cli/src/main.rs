@@ -0,0 +1,16 @@
+use clap::Parser;
+use std::{io, process::ExitCode};
+use tank_cli::{Cli, run};
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("off")).init();
+    let cli = Cli::parse();
+    match run(cli, &mut io::stdout().lock()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Error: {:#}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use clap::ValueEnum;
+use serde_json::{Map, Number, Value as JsonValue};
+use std::{fmt::Write as _, io::Write};
+use tank_core::{Interval, Result, RowLabeled, Value};
+
+/// Output format of the query results.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Aligned columns, for humans.
+    #[default]
+    Table,
+    /// Comma separated values with a header line.
+    Csv,
+    /// Array of objects, one for each row.
+    Json,
+}
+
+/// Writes the rows in the given format.
+pub fn write_rows(out: &mut impl Write, format: Format, rows: &[RowLabeled]) -> Result<()> {
+    let labels = rows
+        .first()
+        .map(|v| v.labels.iter().map(String::as_str).collect::<Vec<_>>())
+        .unwrap_or_default();
+    match format {
+        Format::Table => {
+            let rows = rows
+                .iter()
+                .map(|row| row.values.iter().map(value_to_text).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            write_table(out, &labels, &rows)?;
+        }
+        Format::Csv => {
+            if !labels.is_empty() {
+                write_csv_line(out, labels.iter().copied())?;
+            }
+            for row in rows {
+                let values = row.values.iter().map(value_to_text).collect::<Vec<_>>();
+                write_csv_line(out, values.iter().map(String::as_str))?;
+            }
+        }
+        Format::Json => {
+            let rows = rows
+                .iter()
+                .map(|row| {
+                    JsonValue::Object(
+                        row.labels
+                            .iter()
+                            .zip(row.values.iter())
+                            .map(|(k, v)| (k.clone(), value_to_json(v)))
+                            .collect::<Map<_, _>>(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            serde_json::to_writer_pretty(&mut *out, &rows)?;
+            writeln!(out)?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes the rows as aligned columns, followed by the number of rows.
+pub fn write_table(out: &mut impl Write, labels: &[&str], rows: &[Vec<String>]) -> Result<()> {
+    let mut widths = labels.iter().map(|v| v.chars().count()).collect::<Vec<_>>();
+    for row in rows {
+        for (width, value) in widths.iter_mut().zip(row) {
+            *width = (*width).max(value.chars().count());
+        }
+    }
+    let mut line = String::new();
+    let mut write_line = |out: &mut dyn Write, values: &mut dyn Iterator<Item = &str>| {
+        line.clear();
+        for (i, (value, width)) in values.zip(&widths).enumerate() {
+            if i > 0 {
+                line.push_str(" | ");
+            }
+            let _ = write!(line, "{:width$}", value, width = width);
+        }
+        writeln!(out, "{}", line.trim_end())
+    };
+    if !labels.is_empty() {
+        write_line(out, &mut labels.iter().copied())?;
+        let separator = widths.iter().map(|v| "-".repeat(*v)).collect::<Vec<_>>();
+        writeln!(out, "{}", separator.join("-+-"))?;
+    }
+    for row in rows {
+        write_line(out, &mut row.iter().map(String::as_str))?;
+    }
+    writeln!(
+        out,
+        "({} {})",
+        rows.len(),
+        if rows.len() == 1 { "row" } else { "rows" }
+    )?;
+    Ok(())
+}
+
+fn write_csv_line<'a>(out: &mut impl Write, values: impl Iterator<Item = &'a str>) -> Result<()> {
+    let mut line = String::new();
+    for (i, value) in values.enumerate() {
+        if i > 0 {
+            line.push(',');
+        }
+        if value.contains([',', '"', '\n', '\r']) {
+            let _ = write!(line, "\"{}\"", value.replace('"', "\"\""));
+        } else {
+            line.push_str(value);
+        }
+    }
+    writeln!(out, "{}", line)?;
+    Ok(())
+}
+
+/// Plain text of a value, empty for NULL.
+pub fn value_to_text(value: &Value) -> String {
+    match value_to_json(value) {
+        JsonValue::Null => String::new(),
+        JsonValue::String(v) => v,
+        v => v.to_string(),
+    }
+}
+
+/// JSON representation of a value: numbers and booleans are kept, dates, times, decimals and
+/// UUIDs become strings, blobs become hex strings.
+pub fn value_to_json(value: &Value) -> JsonValue {
+    match value.clone() {
+        Value::Boolean(Some(v)) => JsonValue::Bool(v),
+        Value::Int8(Some(v)) => v.into(),
+        Value::Int16(Some(v)) => v.into(),
+        Value::Int32(Some(v)) => v.into(),
+        Value::Int64(Some(v)) => v.into(),
+        Value::Int128(Some(v)) => i64::try_from(v)
+            .map(Into::into)
+            .unwrap_or_else(|_| v.to_string().into()),
+        Value::UInt8(Some(v)) => v.into(),
+        Value::UInt16(Some(v)) => v.into(),
+        Value::UInt32(Some(v)) => v.into(),
+        Value::UInt64(Some(v)) => v.into(),
+        Value::UInt128(Some(v)) => u64::try_from(v)
+            .map(Into::into)
+            .unwrap_or_else(|_| v.to_string().into()),
+        Value::Float32(Some(v)) => Number::from_f64(v as f64)
+            .map(JsonValue::Number)
+            .unwrap_or_else(|| v.to_string().into()),
+        Value::Float64(Some(v)) => Number::from_f64(v)
+            .map(JsonValue::Number)
+            .unwrap_or_else(|| v.to_string().into()),
+        Value::Decimal(Some(v), ..) => v.to_string().into(),
+        Value::Char(Some(v)) => v.to_string().into(),
+        Value::Varchar(Some(v)) | Value::Unknown(Some(v)) => v.into(),
+        Value::Blob(Some(v)) => v
+            .iter()
+            .fold(String::with_capacity(v.len() * 2), |mut out, b| {
+                let _ = write!(out, "{:02x}", b);
+                out
+            })
+            .into(),
+        Value::Date(Some(v)) => v.to_string().into(),
+        Value::Time(Some(v)) => v.to_string().into(),
+        Value::Timestamp(Some(v)) => v.to_string().into(),
+        Value::TimestampWithTimezone(Some(v)) => v.to_string().into(),
+        Value::Interval(Some(v)) => interval_to_iso8601(&v).into(),
+        Value::Uuid(Some(v)) => v.to_string().into(),
+        Value::Array(Some(v), ..) => v.iter().map(value_to_json).collect(),
+        Value::List(Some(v), ..) => v.iter().map(value_to_json).collect(),
+        Value::Map(Some(v), ..) => v
+            .iter()
+            .map(|(k, v)| (value_to_text(k), value_to_json(v)))
+            .collect::<Map<_, _>>()
+            .into(),
+        Value::Json(Some(v)) => v,
+        Value::Struct(Some(v), ..) => v
+            .iter()
+            .map(|(k, v)| (k.clone(), value_to_json(v)))
+            .collect::<Map<_, _>>()
+            .into(),
+        _ => JsonValue::Null,
+    }
+}
+
+fn interval_to_iso8601(value: &Interval) -> String {
+    let mut out = String::from("P");
+    let (years, months) = (value.months / 12, value.months % 12);
+    if years != 0 {
+        let _ = write!(out, "{}Y", years);
+    }
+    if months != 0 {
+        let _ = write!(out, "{}M", months);
+    }
+    if value.days != 0 {
+        let _ = write!(out, "{}D", value.days);
+    }
+    if value.nanos != 0 {
+        let seconds = (value.nanos / Interval::NANOS_IN_SEC).abs();
+        let nanos = (value.nanos % Interval::NANOS_IN_SEC).abs();
+        let sign = if value.nanos < 0 { "-" } else { "" };
+        let _ = write!(out, "T{}{}", sign, seconds);
+        if nanos != 0 {
+            let fraction = format!("{:09}", nanos);
+            let _ = write!(out, ".{}", fraction.trim_end_matches('0'));
+        }
+        out.push('S');
+    }
+    if out.len() == 1 {
+        out.push_str("T0S");
+    }
+    out
+}
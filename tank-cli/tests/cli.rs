@@ -0,0 +1,165 @@
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use clap::Parser;
+    use indoc::indoc;
+    use std::{env, fs, path::Path, process};
+    use tank_cli::{Cli, run};
+
+    async fn tank(args: &[&str]) -> String {
+        let mut out = Vec::new();
+        run(Cli::parse_from(["tank"].iter().chain(args)), &mut out)
+            .await
+            .expect("The command failed");
+        String::from_utf8(out).expect("The output is not valid utf8")
+    }
+
+    #[tokio::test]
+    async fn commands() {
+        let dir = env::temp_dir().join(format!("tank_cli_{}", process::id()));
+        let migrations = dir.join("migrations");
+        fs::create_dir_all(&migrations).expect("Failed to create the migrations directory");
+        fs::write(
+            migrations.join("0001_create_units.sql"),
+            "CREATE TABLE units (id INTEGER PRIMARY KEY, name VARCHAR(32) NOT NULL UNIQUE);",
+        )
+        .expect("Failed to write");
+        fs::write(
+            migrations.join("0001_create_units.down.sql"),
+            "DROP TABLE units;",
+        )
+        .expect("Failed to write");
+        fs::write(
+            migrations.join("0002_create_soldiers.sql"),
+            indoc! {"
+                CREATE TABLE soldiers (
+                    id INTEGER PRIMARY KEY,
+                    unit INTEGER NOT NULL REFERENCES units(id),
+                    callsign TEXT DEFAULT 'none'
+                );
+            "},
+        )
+        .expect("Failed to write");
+        let url = format!(
+            "sqlite://{}?mode=rwc",
+            Path::new(&dir).join("army.sqlite").display()
+        );
+        let migrate = |command| {
+            let dir = migrations.display().to_string();
+            let url = url.clone();
+            async move { tank(&["-u", &url, "migrate", "-d", &dir, command]).await }
+        };
+
+        // Migrations
+        let output = migrate("up").await;
+        assert!(output.starts_with("Applied 2 migrations"), "{}", output);
+        let output = migrate("status").await;
+        assert!(output.contains("create_soldiers | applied"), "{}", output);
+
+        // Queries
+        assert_eq!(
+            tank(&[
+                "-u",
+                &url,
+                "query",
+                "INSERT INTO units (id, name) VALUES (1, 'Alpha'), (2, 'Bravo, Charlie')",
+            ])
+            .await,
+            "2 rows affected\n"
+        );
+        let select = "SELECT id, name FROM units ORDER BY id";
+        assert_eq!(
+            tank(&["-u", &url, "query", select]).await,
+            indoc! {"
+                id | name
+                ---+---------------
+                1  | Alpha
+                2  | Bravo, Charlie
+                (2 rows)
+            "}
+        );
+        assert_eq!(
+            tank(&["-u", &url, "query", select, "-f", "csv"]).await,
+            indoc! {r#"
+                id,name
+                1,Alpha
+                2,"Bravo, Charlie"
+            "#}
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&tank(&["-u", &url, "query", select, "-f", "json"]).await)
+                .expect("The output is not valid json");
+        assert_eq!(
+            json,
+            serde_json::json!([
+                { "id": 1, "name": "Alpha" },
+                { "id": 2, "name": "Bravo, Charlie" },
+            ])
+        );
+
+        // Inspection
+        let output = tank(&["-u", &url, "inspect", "soldiers"]).await;
+        assert!(output.contains("callsign | TEXT"), "{}", output);
+        assert!(output.contains("Primary key: (id)"), "{}", output);
+        assert!(output.contains("(unit) references units(id)"), "{}", output);
+
+        // Schema dump, the referenced tables come first
+        let output = tank(&["-u", &url, "schema", "dump"]).await;
+        let units = output
+            .find("CREATE TABLE \"units\"")
+            .expect("Missing units");
+        let soldiers = output
+            .find("CREATE TABLE \"soldiers\"")
+            .expect("Missing soldiers");
+        assert!(units < soldiers, "{}", output);
+        assert!(
+            output.contains("\"name\" VARCHAR(32) NOT NULL"),
+            "{}",
+            output
+        );
+        assert!(output.contains("DEFAULT 'none'"), "{}", output);
+        let output = tank(&["-u", &url, "schema", "dump", "--dialect", "sqlite"]).await;
+        assert!(output.contains("DEFAULT 'none'"), "{}", output);
+        #[cfg(feature = "postgres")]
+        {
+            // Defaults are not translated across dialects
+            let output = tank(&["-u", &url, "schema", "dump", "--dialect", "postgres"]).await;
+            assert!(output.contains("CREATE TABLE"), "{}", output);
+            assert!(!output.contains("DEFAULT"), "{}", output);
+        }
+
+        // Entities
+        let output = tank(&["-u", &url, "schema", "entities"]).await;
+        assert!(output.starts_with("use tank::Entity;\n"), "{}", output);
+        assert!(output.contains("pub struct Soldiers {"), "{}", output);
+        assert!(
+            output.contains("#[tank(references = units(id))]\n    pub unit: i64,"),
+            "{}",
+            output
+        );
+        assert!(
+            output.contains("pub callsign: Option<String>,"),
+            "{}",
+            output
+        );
+
+        // Errors
+        assert!(
+            run(
+                Cli::parse_from(["tank", "-u", &url, "inspect", "missing"]),
+                &mut Vec::new()
+            )
+            .await
+            .is_err()
+        );
+        assert!(
+            run(
+                Cli::parse_from(["tank", "-u", "unknown://db", "inspect", "units"]),
+                &mut Vec::new()
+            )
+            .await
+            .is_err()
+        );
+
+        fs::remove_dir_all(&dir).expect("Failed to remove the test directory");
+    }
+}
@@ -0,0 +1,76 @@
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use clap::Parser;
+    use indoc::indoc;
+    use std::{env, fs, process};
+    use tank::{Entity, SchemaExport, SqlWriter};
+    use tank_cli::{EntitySet, ExportCli, export};
+
+    #[derive(Entity)]
+    #[tank(name = "units")]
+    struct Unit {
+        #[tank(primary_key)]
+        id: i32,
+        name: String,
+    }
+
+    #[derive(Entity)]
+    #[tank(name = "soldiers")]
+    struct Soldier {
+        #[tank(primary_key)]
+        id: i64,
+        #[tank(references = Unit::id)]
+        unit: i32,
+    }
+
+    struct Entities;
+
+    impl EntitySet for Entities {
+        fn register<W: SqlWriter>(export: SchemaExport<W>) -> SchemaExport<W> {
+            export.entity::<Soldier>().entity::<Unit>()
+        }
+    }
+
+    #[test]
+    fn export_entities() {
+        let mut out = Vec::new();
+        export::<Entities>(
+            ExportCli::parse_from(["tank-export", "--dialect", "sqlite", "--if-not-exists"]),
+            &mut out,
+        )
+        .expect("The export failed");
+        assert_eq!(
+            String::from_utf8(out).expect("The output is not valid utf8"),
+            indoc! {r#"
+                CREATE TABLE IF NOT EXISTS "units" (
+                "id" INTEGER PRIMARY KEY,
+                "name" TEXT NOT NULL);
+                CREATE TABLE IF NOT EXISTS "soldiers" (
+                "id" INTEGER PRIMARY KEY,
+                "unit" INTEGER NOT NULL,
+                FOREIGN KEY ("unit") REFERENCES "units"("id"));
+            "#}
+        );
+
+        // Output file
+        let path = env::temp_dir().join(format!("tank_export_{}.sql", process::id()));
+        let output = path.display().to_string();
+        export::<Entities>(
+            ExportCli::parse_from(["tank-export", "-d", "sqlite", "-o", &output]),
+            &mut Vec::new(),
+        )
+        .expect("The export failed");
+        let ddl = fs::read_to_string(&path).expect("Failed to read the output file");
+        assert!(ddl.starts_with("CREATE TABLE \"units\" ("), "{}", ddl);
+        fs::remove_file(&path).expect("Failed to remove the output file");
+
+        // Errors
+        assert!(
+            export::<Entities>(
+                ExportCli::parse_from(["tank-export", "-d", "oracle"]),
+                &mut Vec::new()
+            )
+            .is_err()
+        );
+    }
+}
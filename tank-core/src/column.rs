@@ -1,7 +1,9 @@
-use crate::{Expression, OpPrecedence, TableRef, Value, writer::Context};
+use crate::{
+    Expression, OpPrecedence, TableRef, Validation, Value, resolve_schema, writer::Context,
+};
 use proc_macro2::TokenStream;
 use quote::{ToTokens, TokenStreamExt, quote};
-use std::collections::BTreeMap;
+use std::{borrow::Cow, collections::BTreeMap};
 
 /// Helper trait for types that expose an underlying column definition and reference.
 pub trait ColumnTrait {
@@ -23,6 +25,10 @@ pub struct ColumnRef {
 }
 
 impl ColumnRef {
+    /// Schema used when rendering, honoring the `with_schema` override.
+    pub fn effective_schema(&self) -> Cow<'static, str> {
+        resolve_schema(self.schema)
+    }
     pub fn table(&self) -> TableRef {
         TableRef {
             name: self.table,
@@ -56,7 +62,7 @@ impl ToTokens for PrimaryKeyType {
 }
 
 /// Referential action for foreign key updates or deletes.
-#[derive(Default, Debug, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Action {
     /// No special action.
     #[default]
@@ -108,6 +114,14 @@ pub struct ColumnDef {
     pub on_update: Option<Action>,
     /// Passive columns are skipped when generating `INSERT` value lists (DEFAULT used).
     pub passive: bool,
+    /// Value assigned by the database on insert (identity, auto increment, sequence).
+    pub auto_increment: bool,
+    /// Generation expression, the column is computed by the database and never written.
+    pub generated: Option<Box<dyn Expression>>,
+    /// Generated column is stored on write rather than computed on read.
+    pub stored: bool,
+    /// Rules checked before writing, also rendered as DDL where possible.
+    pub validate: Vec<Validation>,
     /// Optional human-readable comment.
     pub comment: &'static str,
 }
@@ -122,6 +136,20 @@ impl ColumnDef {
     pub fn schema(&self) -> &'static str {
         &self.column_ref.schema
     }
+    /// Maximum length allowed by the `Validation::Length` rules, if any.
+    pub fn max_length(&self) -> Option<usize> {
+        self.validate
+            .iter()
+            .filter_map(|v| match v {
+                Validation::Length { max, .. } => *max,
+                _ => None,
+            })
+            .min()
+    }
+    /// Whether the column value is provided by the database through a generation expression.
+    pub fn is_generated(&self) -> bool {
+        self.generated.is_some()
+    }
 }
 
 impl<'a> From<&'a ColumnDef> for &'a ColumnRef {
@@ -1,7 +1,12 @@
-use crate::{Driver, Executor, Result, Transaction};
+use crate::{
+    Driver, Entity, Error, Executor, Result, RetryPolicy, RowsAffected, Transaction,
+    TransactionOptions,
+    future::{BoxFuture, FutureExt},
+};
 use std::{
     borrow::Cow,
     future::{self, Future},
+    panic::{self, AssertUnwindSafe},
 };
 
 /// A live database handle capable of executing queries and spawning transactions.
@@ -18,6 +23,8 @@ use std::{
 /// - `begin` starts a transaction returning an object implementing
 ///   [`Transaction`]. Commit / rollback MUST be awaited to guarantee resource
 ///   release.
+/// - `transaction` runs a closure in a transaction, committing or rolling back
+///   according to its result.
 pub trait Connection: Executor {
     /// Create a connection (or pool) with at least one underlying session
     /// established to the given URL.
@@ -28,6 +35,154 @@ pub trait Connection: Executor {
     /// Begin a transaction scope tied to the current connection.
     fn begin(&mut self) -> impl Future<Output = Result<impl Transaction<'_>>>;
 
+    /// Begin a transaction scope with the given isolation level and access mode.
+    ///
+    /// Drivers not overriding it accept only the default options.
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> impl Future<Output = Result<impl Transaction<'_>>> {
+        async move {
+            if options != TransactionOptions::default() {
+                let error = Error::msg(format!(
+                    "The driver `{}` does not support the transaction options {:?}",
+                    <Self::Driver as Driver>::NAME,
+                    options
+                ));
+                log::error!("{:#}", error);
+                return Err(error);
+            }
+            self.begin().await
+        }
+    }
+
+    /// Begin a transaction like [`Connection::begin_with`], returning the driver transaction type
+    /// (`Driver::Transaction`) that [`Connection::transaction_with`] hands to its closure.
+    ///
+    /// Drivers not overriding it do not support `transaction` and `transaction_with`.
+    fn begin_driver_transaction(
+        &mut self,
+        _options: TransactionOptions,
+    ) -> impl Future<Output = Result<<Self::Driver as Driver>::Transaction<'_>>> {
+        let error = Error::msg(format!(
+            "The driver `{}` does not support `Connection::transaction`",
+            <Self::Driver as Driver>::NAME
+        ));
+        log::error!("{:#}", error);
+        future::ready(Err(error))
+    }
+
+    /// Run `f` inside a transaction, committing if it returns `Ok` and rolling back if it
+    /// returns `Err` or panics.
+    ///
+    /// Serialization failures and deadlocks are retried according to `RetryPolicy::default()`,
+    /// see [`Connection::transaction_with`].
+    ///
+    /// *Example:*
+    /// ```ignore
+    /// let total = connection
+    ///     .transaction(|tx| {
+    ///         Box::pin(async move {
+    ///             order.save(tx).await?;
+    ///             Order::count(tx, &true).await
+    ///         })
+    ///     })
+    ///     .await?;
+    /// ```
+    fn transaction<R, F>(&mut self, f: F) -> impl Future<Output = Result<R>>
+    where
+        F: for<'t> FnMut(
+            &'t mut <Self::Driver as Driver>::Transaction<'_>,
+        ) -> BoxFuture<'t, Result<R>>,
+    {
+        self.transaction_with(TransactionOptions::default(), RetryPolicy::default(), f)
+    }
+
+    /// Run `f` inside a transaction begun with `options`, like [`Connection::transaction`].
+    ///
+    /// When `f` or the commit fail with an error the driver reports as retryable (see
+    /// `Driver::is_retryable`), the transaction is rolled back and `f` runs again in a new one
+    /// after the backoff of `retry` (waited through `Driver::sleep`). `f` can therefore run more
+    /// than once, side effects outside of the database should be avoided.
+    fn transaction_with<R, F>(
+        &mut self,
+        options: TransactionOptions,
+        retry: RetryPolicy,
+        mut f: F,
+    ) -> impl Future<Output = Result<R>>
+    where
+        F: for<'t> FnMut(
+            &'t mut <Self::Driver as Driver>::Transaction<'_>,
+        ) -> BoxFuture<'t, Result<R>>,
+    {
+        async move {
+            let mut attempt = 1;
+            loop {
+                let result = async {
+                    let mut transaction = self.begin_driver_transaction(options).await?;
+                    match AssertUnwindSafe(f(&mut transaction)).catch_unwind().await {
+                        Ok(Ok(result)) => transaction.commit().await.map(|_| result),
+                        Ok(Err(error)) => {
+                            if let Err(rollback) = transaction.rollback().await {
+                                log::error!("{:#}", rollback);
+                            }
+                            Err(error)
+                        }
+                        Err(payload) => {
+                            if let Err(rollback) = transaction.rollback().await {
+                                log::error!("{:#}", rollback);
+                            }
+                            panic::resume_unwind(payload)
+                        }
+                    }
+                }
+                .await;
+                match result {
+                    Err(error)
+                        if attempt < retry.max_attempts && self.driver().is_retryable(&error) =>
+                    {
+                        log::warn!(
+                            "Retrying the transaction (attempt {} of {}) after: {:#}",
+                            attempt + 1,
+                            retry.max_attempts,
+                            error
+                        );
+                        <Self::Driver as Driver>::sleep(retry.backoff(attempt)).await;
+                        attempt += 1;
+                    }
+                    result => return result,
+                }
+            }
+        }
+    }
+
+    /// Append entities inside a single transaction, either all the chunks written by
+    /// `Executor::append` are inserted or none of them.
+    fn append_atomic<'a, E, It>(
+        &mut self,
+        entities: It,
+    ) -> impl Future<Output = Result<RowsAffected>>
+    where
+        E: Entity + 'a,
+        It: IntoIterator<Item = &'a E> + Send,
+    {
+        async move {
+            let mut transaction = self.begin().await?;
+            match transaction.append(entities).await {
+                Ok(result) => {
+                    transaction.commit().await?;
+                    Ok(result)
+                }
+                Err(error) => {
+                    if let Err(rollback) = transaction.rollback().await {
+                        log::error!("{:#}", rollback);
+                    }
+                    Err(error)
+                }
+            }
+        }
+    }
+
     fn disconnect(self) -> impl Future<Output = Result<()>> {
         future::ready(Ok(()))
     }
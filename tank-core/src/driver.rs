@@ -1,5 +1,5 @@
-use crate::{Connection, Prepared, Result, Transaction, writer::SqlWriter};
-use std::{borrow::Cow, fmt::Debug, future::Future};
+use crate::{Connection, Error, Prepared, Result, Transaction, writer::SqlWriter};
+use std::{borrow::Cow, fmt::Debug, future::Future, time::Duration};
 
 /// A backend implementation providing connection + SQL dialect services.
 ///
@@ -15,6 +15,16 @@ use std::{borrow::Cow, fmt::Debug, future::Future};
 /// * `connect` delegates to the associated `Connection::connect` – drivers may
 ///   wrap pooling or additional validation around it.
 /// * `NAME` is a human readable identifier (e.g. "postgres", "sqlite").
+/// * `MAX_INSERT_ROWS` and `MAX_INSERT_LENGTH` bound the chunks `Executor::append`
+///   splits the entities into, drivers lower them to fit the engine limits.
+/// * `TRANSACTIONAL_DDL` tells the `Migrator` whether a failed migration can be rolled back.
+/// * `ALTER_COLUMN` tells `Entity::migrate_table` whether the type, nullability, default and
+///   constraints can be changed in place, otherwise the table is rebuilt.
+/// * `SAVEPOINTS` tells `Transaction::savepoint` whether nested transactions are supported.
+/// * `is_retryable` tells `Connection::transaction` which errors are worth running the
+///   transaction again.
+/// * `sleep` is the timer of the `Connection::transaction` retries and of the `Pool` acquire
+///   timeout, each driver waits on the timer of its own runtime.
 pub trait Driver: Debug {
     /// Concrete connection type.
     type Connection: Connection;
@@ -23,11 +33,26 @@ pub trait Driver: Debug {
     /// Prepared statement wrapper binding values.
     type Prepared: Prepared;
     /// Concrete transaction type, parameterized by connection borrow lifetime.
-    type Transaction<'c>: Transaction<'c>;
+    type Transaction<'c>: Transaction<'c, Driver = Self>;
 
     /// Human-readable backend name.
     const NAME: &'static str;
 
+    /// Maximum number of rows written by a single INSERT when appending entities.
+    const MAX_INSERT_ROWS: usize = 1000;
+
+    /// Maximum length in bytes of a single INSERT when appending entities.
+    const MAX_INSERT_LENGTH: usize = 1 << 24;
+
+    /// Schema changes (CREATE, ALTER, DROP) can be rolled back as part of a transaction.
+    const TRANSACTIONAL_DDL: bool = true;
+
+    /// ALTER TABLE can change the columns and constraints in place, not just add and drop columns.
+    const ALTER_COLUMN: bool = true;
+
+    /// Transactions can be nested through SAVEPOINT.
+    const SAVEPOINTS: bool = true;
+
     /// Establish a connection given a URL.
     fn connect(&self, url: Cow<'static, str>) -> impl Future<Output = Result<impl Connection>> {
         Self::Connection::connect(url)
@@ -35,4 +60,14 @@ pub trait Driver: Debug {
 
     /// Obtain a SQL writer object (cheap to construct).
     fn sql_writer(&self) -> Self::SqlWriter;
+
+    /// The `error` is a serialization failure or a deadlock, running the transaction again can
+    /// succeed.
+    fn is_retryable(&self, _error: &Error) -> bool {
+        false
+    }
+
+    /// Wait for `duration`, between the attempts of `Connection::transaction_with` and to time
+    /// out `Pool::acquire`, using the timer of the runtime the backend client runs on.
+    fn sleep(duration: Duration) -> impl Future<Output = ()> + Send;
 }
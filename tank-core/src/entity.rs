@@ -1,8 +1,10 @@
 use crate::{
-    ColumnDef, Context, DataSet, Driver, Error, Executor, Expression, Query, Result, Row,
-    RowLabeled, RowsAffected, TableRef, Value, future::Either, stream::Stream, writer::SqlWriter,
+    AsValue, ColumnDef, Context, DataSet, Driver, EntityHooks, Error, Executor, Expression,
+    OnConflict, Query, QueryResult, Result, Row, RowLabeled, RowsAffected, SchemaChange,
+    SchemaDiff, TableRef, ValidationError, Value, future::Either, stream::Stream,
+    writer::SqlWriter,
 };
-use futures::{FutureExt, StreamExt, TryFutureExt};
+use futures::{FutureExt, StreamExt, TryFutureExt, TryStreamExt};
 use log::Level;
 use std::{
     future::{self, Future},
@@ -15,7 +17,9 @@ use std::{
 /// - Static table/column metadata
 /// - Conversion to/from database returned row
 /// - Helper CRUD operations using an `Executor`
-pub trait Entity {
+/// - Lifecycle hooks through the `EntityHooks` supertrait (implement it alongside a hand-written
+///   `Entity`, the derive does it for you)
+pub trait Entity: EntityHooks {
     /// Primary key type. Tuple of the types of the fields forming the primary key.
     type PrimaryKey<'a>;
 
@@ -49,6 +53,76 @@ pub trait Entity {
     where
         Self: Sized;
 
+    /// Checks the field values against the `validate` rules of the columns.
+    ///
+    /// Error is a `ValidationError` listing every violation.
+    fn validate(&self) -> Result<()> {
+        if Self::columns().iter().all(|c| c.validate.is_empty()) {
+            return Ok(());
+        }
+        let row = self.row_full();
+        match ValidationError::check(
+            Self::table().full_name(),
+            Self::columns().iter().zip(row.iter()),
+        ) {
+            Some(error) => {
+                let error = Error::new(error);
+                log::error!("{:#}", error);
+                Err(error)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Column whose value is assigned by the database on insert, if any.
+    fn auto_increment_def() -> Option<&'static ColumnDef> {
+        Self::columns().iter().find(|c| c.auto_increment)
+    }
+
+    /// Assigns the value generated by the database to the auto increment field.
+    ///
+    /// Error if the entity has no auto increment column or the value cannot be converted.
+    fn set_auto_increment(&mut self, _value: Value) -> Result<()> {
+        let error = Error::msg(format!(
+            "Entity `{}` does not have a auto increment column",
+            Self::table().full_name()
+        ));
+        log::error!("{:#}", error);
+        Err(error)
+    }
+
+    /// Whether the entity is backed by a view (`#[tank(view = ..)]`) instead of a table.
+    fn is_view() -> bool {
+        false
+    }
+
+    /// Emits the SELECT defining the view backing the entity, nothing for tables.
+    fn write_view_query<W: SqlWriter>(_writer: &W, _out: &mut String) {}
+
+    /// The entity as the receiver of `EntityHooks::after_insert` in `insert_one`, `None` unless
+    /// it implements the hooks (`#[tank(hooks)]`).
+    ///
+    /// `insert_one` accepts entities that are not `Sync`, it holds only this reference until the
+    /// insert completed.
+    fn as_hooks(&self) -> Option<&(dyn EntityHooks + Sync)> {
+        None
+    }
+
+    /// Checks that the entity can be written.
+    ///
+    /// Error if the entity is backed by a view, those are read-only.
+    fn writable() -> Result<()> {
+        if !Self::is_view() {
+            return Ok(());
+        }
+        let error = Error::msg(format!(
+            "Entity `{}` is backed by a view, it cannot be written",
+            Self::table().full_name()
+        ));
+        log::error!("{:#}", error);
+        Err(error)
+    }
+
     /// Creates the underlying table (and optionally schema) if requested.
     ///
     /// Parameters:
@@ -71,6 +145,166 @@ pub trait Entity {
         drop_schema: bool,
     ) -> impl Future<Output = Result<()>> + Send;
 
+    /// Compares the entity with the live table, see `SchemaDiff::compute`.
+    ///
+    /// Returns `Ok(None)` if the table does not exist.
+    fn diff_table(
+        executor: &mut impl Executor,
+    ) -> impl Future<Output = Result<Option<SchemaDiff>>> + Send
+    where
+        Self: Sized,
+    {
+        async move {
+            let Some(live) = executor.describe_table(&Self::table().into()).await? else {
+                return Ok(None);
+            };
+            Ok(Some(SchemaDiff::compute::<Self>(
+                &executor.driver().sql_writer(),
+                &live,
+            )))
+        }
+    }
+
+    /// Brings the table in line with the entity: creates it (and its schema) when missing,
+    /// otherwise applies the changes found by `diff_table`.
+    ///
+    /// Where the driver cannot alter columns in place (see `Driver::ALTER_COLUMN`) the table is
+    /// rebuilt through `SqlWriter::write_alter_table_rebuild` instead, this requires `destructive`
+    /// when any change is destructive. Views are dropped and created again, they hold no data.
+    /// Where DDL is transactional (see `Driver::TRANSACTIONAL_DDL`) the statements run in a single
+    /// transaction, a failing one leaves the table untouched. Called on an executor already inside
+    /// a transaction (see `Executor::in_transaction`) they run in a savepoint instead, or directly
+    /// when the driver has no savepoints.
+    ///
+    /// The rebuild runs with the foreign keys turned off when the connection enforces them, they
+    /// are checked before committing and turned on again afterwards. They cannot be turned off
+    /// inside a transaction, such a rebuild fails.
+    ///
+    /// Parameters:
+    /// - `destructive`: also apply the changes that can lose data, see `SchemaChange::is_destructive`.
+    ///
+    /// Returns the changes found, the skipped destructive ones included.
+    fn migrate_table(
+        executor: &mut impl Executor,
+        destructive: bool,
+    ) -> impl Future<Output = Result<SchemaDiff>> + Send
+    where
+        Self: Sized,
+    {
+        fn alter_column<E: Executor>(_: &E) -> bool {
+            <E::Driver as Driver>::ALTER_COLUMN
+        }
+        fn transactional_ddl<E: Executor>(_: &E) -> bool {
+            <E::Driver as Driver>::TRANSACTIONAL_DDL
+        }
+        async move {
+            if Self::is_view() {
+                Self::drop_table(executor, true, false).await?;
+                Self::create_table(executor, false, true).await?;
+                return Ok(SchemaDiff {
+                    table: Self::table(),
+                    changes: Vec::new(),
+                });
+            }
+            let Some(live) = executor.describe_table(&Self::table().into()).await? else {
+                Self::create_table(executor, false, true).await?;
+                return Ok(SchemaDiff {
+                    table: Self::table(),
+                    changes: Vec::new(),
+                });
+            };
+            let rebuild = !alter_column(executor);
+            let (diff, query, rebuild) = {
+                let writer = executor.driver().sql_writer();
+                let diff = SchemaDiff::compute::<Self>(&writer, &live);
+                let mut query = String::with_capacity(diff.changes.len() * 64);
+                let rebuild =
+                    rebuild && diff.changes.iter().any(SchemaChange::requires_alter_column);
+                if rebuild {
+                    if !destructive && diff.changes.iter().any(SchemaChange::is_destructive) {
+                        return Err(Error::msg(format!(
+                            "The table {} must be rebuilt to apply the changes, some of them are destructive",
+                            Self::table().full_name(),
+                        )));
+                    }
+                    writer.write_alter_table_rebuild::<Self>(&mut query, &live);
+                } else {
+                    writer.write_schema_diff(&mut query, &diff, destructive);
+                }
+                (diff, query, rebuild)
+            };
+            if query.is_empty() {
+                return Ok(diff);
+            }
+            if !transactional_ddl(executor) {
+                executor.execute(query).await?;
+                return Ok(diff);
+            }
+            if !rebuild {
+                execute_transaction(executor, query, String::new()).await?;
+                return Ok(diff);
+            }
+            let (enforced, disable, enable, check) = {
+                let writer = executor.driver().sql_writer();
+                let mut enforced = String::with_capacity(32);
+                writer.write_foreign_keys_enforced(&mut enforced);
+                let mut disable = String::with_capacity(32);
+                writer.write_set_foreign_keys_enforced(&mut disable, false);
+                let mut enable = String::with_capacity(32);
+                writer.write_set_foreign_keys_enforced(&mut enable, true);
+                let mut check = String::with_capacity(32);
+                writer.write_foreign_key_check(&mut check);
+                (enforced, disable, enable, check)
+            };
+            // Turning the foreign keys off has no effect inside a transaction. The rows are read to
+            // the end, SQLite cannot drop a table while a statement is still pending.
+            let rows = if enforced.is_empty() {
+                Vec::new()
+            } else {
+                executor.fetch(enforced).try_collect::<Vec<_>>().await?
+            };
+            let enforced = match rows
+                .into_iter()
+                .next()
+                .and_then(|v| v.values.into_iter().next())
+            {
+                Some(value) => bool::try_from_value(value)?,
+                None => false,
+            };
+            if enforced && executor.in_transaction() {
+                let error = Error::msg(format!(
+                    "The table {} must be rebuilt with the foreign keys disabled, they cannot be disabled inside a transaction",
+                    Self::table().full_name(),
+                ));
+                log::error!("{:#}", error);
+                return Err(error);
+            }
+            if enforced {
+                executor.execute(disable).await?;
+            }
+            let result = execute_transaction(
+                executor,
+                query,
+                if enforced { check } else { String::new() },
+            )
+            .await
+            .map_err(|e| {
+                e.context(format!(
+                    "While rebuilding the table {}",
+                    Self::table().full_name()
+                ))
+            });
+            if enforced {
+                match executor.execute(enable).await {
+                    Err(e) if result.is_ok() => return Err(e),
+                    Err(e) => log::error!("{:#}", e),
+                    Ok(..) => {}
+                }
+            }
+            result.map(|_| diff)
+        }
+    }
+
     /// Inserts a single entity row.
     ///
     /// Returns rows affected (expected: 1 on success).
@@ -90,6 +324,125 @@ pub trait Entity {
         Self: 'a,
         It: IntoIterator<Item = &'a Self> + Send;
 
+    /// Inserts this entity and writes the id assigned by the database back into its auto
+    /// increment field (usually a `Passive`).
+    ///
+    /// The id is read from the `RETURNING` clause or from `RowsAffected::last_affected_id`,
+    /// depending on the driver. A auto increment field already set is inserted as is.
+    ///
+    /// Errors:
+    /// - The driver did not report the generated id.
+    /// - Execution failures from underlying driver.
+    fn insert(
+        &mut self,
+        executor: &mut impl Executor,
+    ) -> impl Future<Output = Result<RowsAffected>> + Send
+    where
+        Self: Sized + Send,
+    {
+        if let Err(error) = Self::writable()
+            .and_then(|_| self.validate())
+            .and_then(|_| self.before_insert())
+        {
+            return Either::Left(future::ready(Err(error)));
+        }
+        let column = Self::auto_increment_def().filter(|column| {
+            !self
+                .row_filtered()
+                .iter()
+                .any(|(name, _)| *name == column.name())
+        });
+        let mut query = String::with_capacity(128);
+        let writer = executor.driver().sql_writer();
+        match column {
+            Some(column) => writer.write_insert_returning(&mut query, &*self, column),
+            None => writer.write_insert(&mut query, [&*self], false),
+        }
+        let stream = executor.run(query);
+        Either::Right(async move {
+            let mut stream = pin!(stream);
+            let mut result = RowsAffected::default();
+            let mut returned = None;
+            let mut returned_rows = 0;
+            while let Some(item) = stream.try_next().await? {
+                match item {
+                    QueryResult::Row(row) => {
+                        if returned.is_none() {
+                            returned = row.values.into_iter().next();
+                        }
+                        returned_rows += 1;
+                    }
+                    QueryResult::Affected(v) => result.extend([v]),
+                }
+            }
+            // Some drivers report just the returned rows for a INSERT ... RETURNING
+            result.rows_affected = result.rows_affected.max(returned_rows);
+            if let Some(column) = column {
+                let Some(id) = returned.or(result.last_affected_id.map(|v| Value::Int64(Some(v))))
+                else {
+                    let error = Error::msg(format!(
+                        "The driver did not report the value generated for `{}`",
+                        column.name()
+                    ));
+                    log::error!("{:#}", error);
+                    return Err(error);
+                };
+                self.set_auto_increment(id)?;
+            }
+            self.after_insert(&result)?;
+            Ok(result)
+        })
+    }
+
+    /// Inserts the entities, handling the rows that already exist as described by `on_conflict`.
+    ///
+    /// *Example:*
+    /// ```ignore
+    /// Operator::upsert_many(executor, &operators, &OnConflict::unique::<Operator>(0).do_nothing())
+    /// ```
+    ///
+    /// Large batches are split like in `Executor::append`.
+    ///
+    /// Returns the number of rows reported by the driver (inserted plus updated ones, the exact
+    /// count depends on the database).
+    fn upsert_many<'a, Exec, It>(
+        executor: &mut Exec,
+        entities: It,
+        on_conflict: &OnConflict,
+    ) -> impl Future<Output = Result<RowsAffected>> + Send
+    where
+        Self: Sized + Sync + 'a,
+        Exec: Executor,
+        It: IntoIterator<Item = &'a Self> + Send,
+    {
+        let entities = entities.into_iter().collect::<Vec<_>>();
+        if let Some(error) = Self::writable().err().or_else(|| {
+            entities
+                .iter()
+                .find_map(|entity| entity.validate().and_then(|_| entity.before_insert()).err())
+        }) {
+            return Either::Left(future::ready(Err(error)));
+        }
+        let mut queries = Vec::new();
+        executor.driver().sql_writer().write_insert_chunks(
+            &mut queries,
+            &entities,
+            Some(on_conflict),
+            <<Exec as Executor>::Driver as Driver>::MAX_INSERT_ROWS,
+            <<Exec as Executor>::Driver as Driver>::MAX_INSERT_LENGTH,
+        );
+        Either::Right(async move {
+            let mut result = RowsAffected::default();
+            for query in queries {
+                result.extend([executor.execute(query).await?]);
+            }
+            for entity in entities {
+                entity.after_insert(&result)?;
+            }
+            Ok(result)
+        })
+    }
+
     /// Prepare (but do not yet run) a SQL select query.
     ///
     /// Returns the prepared statement.
@@ -137,6 +490,46 @@ pub trait Entity {
     where
         Self: Sized;
 
+    /// Counts the entities matching a condition.
+    ///
+    /// The integer returned by the database is converted to `u64` whatever its width.
+    fn count(
+        executor: &mut impl Executor,
+        condition: &impl Expression,
+    ) -> impl Future<Output = Result<u64>> + Send
+    where
+        Self: Sized,
+    {
+        let mut query = String::with_capacity(128);
+        executor
+            .driver()
+            .sql_writer()
+            .write_count::<Self>(&mut query, condition);
+        let stream = executor.fetch(query);
+        async move {
+            let count = i128::try_from_value(first_value(stream).await?)?;
+            u64::try_from(count)
+                .map_err(|_| Error::msg(format!("Count {} is out of range for u64", count)))
+        }
+    }
+
+    /// Checks whether any entity matches a condition.
+    fn exists(
+        executor: &mut impl Executor,
+        condition: &impl Expression,
+    ) -> impl Future<Output = Result<bool>> + Send
+    where
+        Self: Sized,
+    {
+        let mut query = String::with_capacity(128);
+        executor
+            .driver()
+            .sql_writer()
+            .write_exists::<Self>(&mut query, condition);
+        let stream = executor.fetch(query);
+        async move { bool::try_from_value(first_value(stream).await?) }
+    }
+
     /// Deletes exactly one entity by primary key.
     ///
     /// Returns rows affected (0 if not found).
@@ -173,6 +566,12 @@ pub trait Entity {
             log::error!("{:#}", error);
             return Either::Left(future::ready(Err(error)));
         }
+        if let Err(error) = Self::writable()
+            .and_then(|_| self.validate())
+            .and_then(|_| self.before_save())
+        {
+            return Either::Left(future::ready(Err(error)));
+        }
         let mut query = String::with_capacity(512);
         executor
             .driver()
@@ -197,6 +596,9 @@ pub trait Entity {
             log::error!("{:#}", error);
             return Either::Left(future::ready(Err(error)));
         }
+        if let Err(error) = Self::writable().and_then(|_| self.before_delete()) {
+            return Either::Left(future::ready(Err(error)));
+        }
         Either::Right(Self::delete_one(executor, self.primary_key()).map(|v| {
             v.and_then(|v| {
                 if v.rows_affected == 1 {
@@ -238,3 +640,79 @@ impl<E: Entity> DataSet for E {
         Self::table().write_query(writer, context, out);
     }
 }
+
+/// Runs `query` in a transaction, rolled back when it fails or when `check` (if not empty)
+/// returns any row.
+///
+/// Inside a transaction already, it runs in a savepoint or, when the driver has none, directly in
+/// the enclosing transaction.
+async fn execute_transaction<E: Executor>(
+    executor: &mut E,
+    query: String,
+    check: String,
+) -> Result<()> {
+    let nested = executor.in_transaction();
+    if nested && !<E::Driver as Driver>::SAVEPOINTS {
+        return execute_checked(executor, query, check).await;
+    }
+    // Inside a transaction the changes are wrapped in a savepoint, a BEGIN would fail
+    let (begin, commit, rollback, release) = {
+        let writer = executor.driver().sql_writer();
+        let mut begin = String::with_capacity(32);
+        let mut commit = String::with_capacity(32);
+        let mut rollback = String::with_capacity(32);
+        let mut release = String::new();
+        if nested {
+            let name = "tank_migrate_table";
+            writer.write_transaction_savepoint(&mut begin, name);
+            writer.write_transaction_release_savepoint(&mut commit, name);
+            writer.write_transaction_rollback_to_savepoint(&mut rollback, name);
+            writer.write_transaction_release_savepoint(&mut release, name);
+        } else {
+            writer.write_transaction_begin(&mut begin);
+            writer.write_transaction_commit(&mut commit);
+            writer.write_transaction_rollback(&mut rollback);
+        }
+        (begin, commit, rollback, release)
+    };
+    executor.execute(begin).await?;
+    match execute_checked(executor, query, check).await {
+        Ok(..) => executor.execute(commit).await.map(|_| ()),
+        Err(e) => {
+            if let Err(rollback) = executor.execute(rollback).await {
+                log::error!("{:#}", rollback);
+            } else if !release.is_empty()
+                && let Err(release) = executor.execute(release).await
+            {
+                log::error!("{:#}", release);
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Runs `query`, failing when `check` (if not empty) returns any row.
+async fn execute_checked(executor: &mut impl Executor, query: String, check: String) -> Result<()> {
+    executor.execute(query).await?;
+    if check.is_empty() {
+        return Ok(());
+    }
+    let rows = executor.fetch(check).try_collect::<Vec<_>>().await?;
+    if !rows.is_empty() {
+        return Err(Error::msg(
+            "The foreign key check found rows referencing missing ones",
+        ));
+    }
+    Ok(())
+}
+
+/// First value of the first row, for single value queries like `SELECT COUNT(*)`.
+async fn first_value(stream: impl Stream<Item = Result<RowLabeled>>) -> Result<Value> {
+    let Some(row) = pin!(stream).try_next().await? else {
+        return Err(Error::msg("The query did not return any row"));
+    };
+    row.values
+        .into_iter()
+        .next()
+        .ok_or_else(|| Error::msg("The query returned a row without values"))
+}
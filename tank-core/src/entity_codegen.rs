@@ -0,0 +1,277 @@
+use crate::{Action, ColumnSchema, TableSchema, Value};
+use std::{collections::HashSet, fmt::Write};
+
+/// Rust type of the entity field holding `value`, the inverse of `decode_type`.
+///
+/// Returns `None` for the values no field type decodes to (JSON, structs, unknown types).
+pub fn value_to_rust_type(value: &Value) -> Option<String> {
+    Some(match value {
+        Value::Boolean(..) => "bool".into(),
+        Value::Int8(..) => "i8".into(),
+        Value::Int16(..) => "i16".into(),
+        Value::Int32(..) => "i32".into(),
+        Value::Int64(..) => "i64".into(),
+        Value::Int128(..) => "i128".into(),
+        Value::UInt8(..) => "u8".into(),
+        Value::UInt16(..) => "u16".into(),
+        Value::UInt32(..) => "u32".into(),
+        Value::UInt64(..) => "u64".into(),
+        Value::UInt128(..) => "u128".into(),
+        Value::Float32(..) => "f32".into(),
+        Value::Float64(..) => "f64".into(),
+        Value::Decimal(.., 0, _) => "rust_decimal::Decimal".into(),
+        Value::Decimal(.., width, scale) => format!("tank::FixedDecimal<{}, {}>", width, scale),
+        Value::Char(..) => "char".into(),
+        Value::Varchar(..) => "String".into(),
+        Value::Blob(..) => "Box<[u8]>".into(),
+        Value::Date(..) => "time::Date".into(),
+        Value::Time(..) => "time::Time".into(),
+        Value::Timestamp(..) => "time::PrimitiveDateTime".into(),
+        Value::TimestampWithTimezone(..) => "time::OffsetDateTime".into(),
+        Value::Interval(..) => "tank::Interval".into(),
+        Value::Uuid(..) => "uuid::Uuid".into(),
+        Value::Array(.., inner, len) => format!("[{}; {}]", value_to_rust_type(inner)?, len),
+        Value::List(.., inner) => format!("Vec<{}>", value_to_rust_type(inner)?),
+        Value::Map(.., key, value) => format!(
+            "std::collections::HashMap<{}, {}>",
+            value_to_rust_type(key)?,
+            value_to_rust_type(value)?
+        ),
+        _ => return None,
+    })
+}
+
+/// Writes the Rust source declaring an `Entity` for each table, `driver` is the `Driver::NAME` of
+/// the database the tables were described from.
+pub fn write_entities(out: &mut String, tables: &[TableSchema], driver: &str) {
+    out.push_str("use tank::Entity;\n");
+    // Tables with the same name in different schemas
+    let names = deduplicate(tables.iter().map(|v| type_name(&v.table.name)), "");
+    for (table, name) in tables.iter().zip(&names) {
+        out.push('\n');
+        write_struct(out, table, name, driver);
+    }
+}
+
+/// Writes the Rust source of a struct deriving `Entity` mapped on `table`.
+///
+/// Columns having a type without a field counterpart become `String` with their `column_type`
+/// overridden for `driver`, foreign keys on multiple columns are not declared.
+pub fn write_entity(out: &mut String, table: &TableSchema, driver: &str) {
+    write_struct(out, table, &type_name(&table.table.name), driver);
+}
+
+fn write_struct(out: &mut String, table: &TableSchema, name: &str, driver: &str) {
+    // Columns differing only by case or punctuation (`signalStrength`, `signal_strength`)
+    let fields = deduplicate(table.columns.iter().map(|v| field_name(&v.name)), "_");
+    let field_of = |column: &str| {
+        table
+            .columns
+            .iter()
+            .position(|v| v.name == column)
+            .map(|i| fields[i].clone())
+            .unwrap_or_else(|| column.to_string())
+    };
+    out.push_str("#[derive(Debug, Clone, Entity)]\n#[tank(");
+    if !table.table.schema.is_empty() {
+        let _ = write!(out, "schema = {:?}, ", table.table.schema);
+    }
+    let _ = write!(out, "name = {:?}", table.table.name);
+    let write_columns = |out: &mut String, attribute: &str, columns: &[String]| {
+        let _ = write!(out, ", {} = (", attribute);
+        for (i, column) in columns.iter().enumerate() {
+            if i > 0 {
+                out.push_str(", ");
+            }
+            let _ = write!(out, "Self::{}", field_of(column));
+        }
+        out.push(')');
+    };
+    if table.primary_key.len() > 1 {
+        write_columns(out, "primary_key", &table.primary_key);
+    }
+    for unique in table.unique.iter().filter(|v| v.columns.len() > 1) {
+        write_columns(out, "unique", &unique.columns);
+    }
+    let _ = writeln!(out, ")]\npub struct {} {{", name);
+    for (column, field) in table.columns.iter().zip(&fields) {
+        write_entity_field(out, table, column, field, driver);
+    }
+    out.push_str("}\n");
+}
+
+fn write_entity_field(
+    out: &mut String,
+    table: &TableSchema,
+    column: &ColumnSchema,
+    field: &str,
+    driver: &str,
+) {
+    if let Some(comment) = &column.comment {
+        for line in comment.lines() {
+            let _ = writeln!(out, "    /// {}", line.trim());
+        }
+    }
+    let primary_key = table.primary_key.contains(&column.name);
+    let mut attributes = Vec::new();
+    if primary_key && table.primary_key.len() == 1 {
+        attributes.push("primary_key".to_string());
+    }
+    if *field != column.name {
+        attributes.push(format!("name = {:?}", column.name));
+    }
+    if !primary_key
+        && table
+            .unique
+            .iter()
+            .any(|v| v.columns.len() == 1 && v.columns[0] == column.name)
+    {
+        attributes.push("unique".into());
+    }
+    if let Some(foreign_key) = table
+        .foreign_keys
+        .iter()
+        .find(|v| v.columns.len() == 1 && v.columns[0] == column.name)
+    {
+        let mut references = String::new();
+        if !foreign_key.references.schema.is_empty() {
+            let _ = write!(references, "{}.", foreign_key.references.schema);
+        }
+        let _ = write!(
+            references,
+            "{}({})",
+            foreign_key.references.name,
+            foreign_key.references_columns.join(", ")
+        );
+        attributes.push(format!("references = {}", references));
+        for (name, action) in [
+            ("on_delete", &foreign_key.on_delete),
+            ("on_update", &foreign_key.on_update),
+        ] {
+            let action = match action {
+                None | Some(Action::NoAction) => continue,
+                Some(Action::Restrict) => "restrict",
+                Some(Action::Cascade) => "cascade",
+                Some(Action::SetNull) => "set_null",
+                Some(Action::SetDefault) => "set_default",
+            };
+            attributes.push(format!("{} = {}", name, action));
+        }
+    }
+    let rust_type = value_to_rust_type(&storage_value(&column.value, driver));
+    let has_arguments =
+        column.column_type.contains('(') && !matches!(column.value, Value::Decimal(..));
+    if rust_type.is_none() || has_arguments {
+        attributes.push(format!(
+            "column_type = ({} = {:?})",
+            driver, column.column_type
+        ));
+    }
+    if !attributes.is_empty() {
+        let _ = writeln!(out, "    #[tank({})]", attributes.join(", "));
+    }
+    let rust_type = rust_type.unwrap_or_else(|| "String".into());
+    if column.nullable && !primary_key {
+        let _ = writeln!(out, "    pub {}: Option<{}>,", field, rust_type);
+    } else {
+        let _ = writeln!(out, "    pub {}: {},", field, rust_type);
+    }
+}
+
+/// Value of a column as stored by `driver`: SQLite keeps every `INTEGER` in 64 bits and every
+/// `REAL` as a double, whatever the declared type.
+fn storage_value(value: &Value, driver: &str) -> Value {
+    match value {
+        Value::Int32(..) if driver == "sqlite" => Value::Int64(None),
+        Value::Float32(..) if driver == "sqlite" => Value::Float64(None),
+        _ => value.clone(),
+    }
+}
+
+/// Appends `separator` and a counter to the repeated `names`, the first occurrence is kept.
+fn deduplicate(names: impl IntoIterator<Item = String>, separator: &str) -> Vec<String> {
+    let names = names.into_iter().collect::<Vec<_>>();
+    let mut taken = names.iter().cloned().collect::<HashSet<_>>();
+    let mut seen = HashSet::new();
+    names
+        .into_iter()
+        .map(|name| {
+            if seen.insert(name.clone()) {
+                return name;
+            }
+            let name = (2..)
+                .map(|i| format!("{}{}{}", name, separator, i))
+                .find(|v| !taken.contains(v))
+                .expect("There is always a free suffix");
+            taken.insert(name.clone());
+            name
+        })
+        .collect()
+}
+
+/// Name of the struct mapped on a table: `radio_logs` => `RadioLogs`.
+fn type_name(table: &str) -> String {
+    let mut result = String::with_capacity(table.len());
+    let mut upper = true;
+    for c in table.chars() {
+        if c.is_ascii_alphanumeric() {
+            if upper {
+                result.push(c.to_ascii_uppercase());
+            } else {
+                result.push(c);
+            }
+            upper = false;
+        } else {
+            upper = true;
+        }
+    }
+    if !result.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        result.insert(0, 'T');
+    }
+    result
+}
+
+/// Name of the field mapped on a column: `signalStrength` => `signal_strength`, keywords get a
+/// trailing underscore.
+fn field_name(column: &str) -> String {
+    let mut result = String::with_capacity(column.len() + 4);
+    let mut previous_lower = false;
+    for c in column.chars() {
+        if c.is_ascii_uppercase() {
+            if previous_lower {
+                result.push('_');
+            }
+            result.push(c.to_ascii_lowercase());
+            previous_lower = false;
+        } else if c.is_ascii_alphanumeric() {
+            result.push(c);
+            previous_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        } else {
+            if !result.ends_with('_') {
+                result.push('_');
+            }
+            previous_lower = false;
+        }
+    }
+    let result = result.trim_matches('_');
+    let mut result = if result.is_empty() {
+        "column".to_string()
+    } else {
+        result.to_string()
+    };
+    if result.starts_with(|c: char| c.is_ascii_digit()) {
+        result.insert(0, '_');
+    }
+    if KEYWORDS.contains(&result.as_str()) {
+        result.push('_');
+    }
+    result
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+    "pub", "ref", "return", "self", "static", "struct", "super", "trait", "true", "type", "unsafe",
+    "use", "where", "while", "abstract", "become", "box", "do", "final", "macro", "override",
+    "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
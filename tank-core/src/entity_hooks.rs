@@ -0,0 +1,66 @@
+use crate::{Result, RowsAffected};
+
+/// Lifecycle callbacks invoked by the `Entity` operations.
+///
+/// Every method defaults to a no-op. The derive implements this trait with the defaults unless the
+/// struct is annotated with `#[tank(hooks)]`, in which case the implementation is up to you.
+///
+/// A `before_*` hook returning an error aborts the operation before any query is sent.
+///
+/// It is a supertrait of `Entity`: a hand-written `Entity` implementation must implement it as
+/// well, an empty `impl EntityHooks for MyEntity {}` keeps the defaults.
+///
+/// *Example:*
+/// ```ignore
+/// #[derive(Entity)]
+/// #[tank(hooks)]
+/// struct Operator {
+///     #[tank(primary_key)]
+///     id: Uuid,
+///     callsign: String,
+/// }
+///
+/// impl EntityHooks for Operator {
+///     fn before_insert(&self) -> Result<()> {
+///         if self.callsign.trim().is_empty() {
+///             return Err(Error::msg("The callsign cannot be empty"));
+///         }
+///         Ok(())
+///     }
+///     fn after_load(&mut self) -> Result<()> {
+///         self.callsign = self.callsign.to_uppercase();
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait EntityHooks {
+    /// Called by `insert_one`, `insert_many` and `insert` for each entity, before the query is built.
+    fn before_insert(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called by `insert_one`, `insert_many` and `insert` on each inserted entity once the insert
+    /// completed, with the outcome of the whole operation (`insert_many` passes the result of the
+    /// batch to every entity).
+    ///
+    /// The insert futures hold the entities until then, a struct annotated with `#[tank(hooks)]`
+    /// must be `Sync`.
+    fn after_insert(&self, _result: &RowsAffected) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called by `save` and `Tracked::update` before the query is built.
+    fn before_save(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called by `delete` before the query is built.
+    fn before_delete(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called on every entity decoded from a row, the error is reported in place of the entity.
+    fn after_load(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,273 @@
+use crate::{Driver, Entity, Error, Executor, Result, SqlWriter, TableName, TableSchema};
+use std::{collections::BTreeSet, future::Future};
+
+/// Table of a entity and the tables it references through foreign keys.
+pub(crate) struct EntityNode {
+    pub(crate) table: TableName,
+    pub(crate) view: bool,
+    pub(crate) references: Vec<TableName>,
+}
+
+impl EntityNode {
+    pub(crate) fn new<E: Entity>() -> Self {
+        Self {
+            table: E::table().into(),
+            view: E::is_view(),
+            references: E::columns()
+                .iter()
+                .filter_map(|v| v.references.as_ref())
+                .map(|v| TableName::new(v.effective_schema(), v.table))
+                .collect(),
+        }
+    }
+}
+
+impl From<&TableSchema> for EntityNode {
+    fn from(value: &TableSchema) -> Self {
+        Self {
+            table: value.table.clone(),
+            view: false,
+            references: value
+                .foreign_keys
+                .iter()
+                .map(|v| v.references.clone())
+                .collect(),
+        }
+    }
+}
+
+/// Orders the tables found in the catalog so that each one comes after the tables it references,
+/// the ones caught in a cycle keep their original order.
+pub fn tables_creation_order(tables: &[TableSchema]) -> Vec<&TableSchema> {
+    let items = tables
+        .iter()
+        .map(|v| (EntityNode::from(v), v))
+        .collect::<Vec<_>>();
+    creation_order(&items, |v| &v.0)
+        .0
+        .into_iter()
+        .map(|v| v.1)
+        .collect()
+}
+
+/// Orders `items` so that the tables referenced by a foreign key come first and the views follow
+/// every table (the query of a view can read any of them).
+///
+/// The items caught in a cycle are emitted in their original order, the second value lists the
+/// tables forming the cycles (empty when there are none).
+pub(crate) fn creation_order<T>(
+    items: &[T],
+    node: impl Fn(&T) -> &EntityNode,
+) -> (Vec<&T>, Vec<&TableName>) {
+    let mut pending = items.iter().collect::<Vec<_>>();
+    let mut result = Vec::with_capacity(pending.len());
+    let mut created = BTreeSet::new();
+    let mut cycle = Vec::new();
+    while !pending.is_empty() {
+        let tables = pending.iter().any(|v| !node(v).view);
+        let ready = pending.iter().position(|item| {
+            let item = node(item);
+            (!item.view || !tables)
+                && item.references.iter().all(|v| {
+                    *v == item.table
+                        || created.contains(v)
+                        || !pending.iter().any(|t| node(t).table == *v)
+                })
+        });
+        let ready = ready.or_else(|| {
+            if cycle.is_empty() {
+                cycle = cyclic_tables(&pending, &node);
+            }
+            pending.iter().position(|v| !node(v).view)
+        });
+        let item = pending.remove(ready.unwrap_or_default());
+        created.insert(&node(item).table);
+        result.push(item);
+    }
+    (result, cycle)
+}
+
+/// Tables of `pending` taking part in a cycle, once the ones nobody else references are removed.
+fn cyclic_tables<'a, T>(
+    pending: &[&'a T],
+    node: &impl Fn(&T) -> &EntityNode,
+) -> Vec<&'a TableName> {
+    let mut cycle = pending
+        .iter()
+        .filter(|v| !node(v).view)
+        .map(|v| &node(v).table)
+        .collect::<Vec<_>>();
+    loop {
+        let referenced = cycle
+            .iter()
+            .flat_map(|table| {
+                pending
+                    .iter()
+                    .map(|v| node(v))
+                    .find(|v| v.table == **table)
+                    .into_iter()
+                    .flat_map(|v| v.references.iter().filter(|r| **r != v.table))
+            })
+            .collect::<BTreeSet<_>>();
+        let len = cycle.len();
+        cycle.retain(|v| referenced.contains(v));
+        if cycle.len() == len {
+            return cycle;
+        }
+    }
+}
+
+/// The entities of an application, created and dropped together in foreign key order.
+///
+/// Each schema is created once, before its first table, views are created after every table and
+/// dropped before them. A cycle of foreign keys is reported as an error, nothing is executed.
+///
+/// *Example:*
+/// ```ignore
+/// let registry = EntityRegistry::new()
+///     .entity::<Soldier>()
+///     .entity::<Unit>();
+/// registry.create_all(&mut connection, true).await?;
+/// // ...
+/// registry.drop_all(&mut connection, true).await?;
+/// ```
+pub struct EntityRegistry<D: Driver> {
+    entities: Vec<RegisteredEntity<D>>,
+}
+
+struct RegisteredEntity<D: Driver> {
+    node: EntityNode,
+    write_create_schema: fn(&D::SqlWriter, &mut String, bool),
+    write_create: fn(&D::SqlWriter, &mut String, bool),
+    write_drop: fn(&D::SqlWriter, &mut String, bool),
+}
+
+impl<D: Driver> EntityRegistry<D> {
+    /// Registry without entities.
+    pub fn new() -> Self {
+        Self {
+            entities: Vec::new(),
+        }
+    }
+
+    /// Adds the entity `E`, adding the same table twice has no effect.
+    pub fn entity<E: Entity>(mut self) -> Self {
+        let node = EntityNode::new::<E>();
+        if self.entities.iter().any(|v| v.node.table == node.table) {
+            return self;
+        }
+        self.entities.push(RegisteredEntity {
+            node,
+            write_create_schema: D::SqlWriter::write_create_schema::<E>,
+            write_create: if E::is_view() {
+                D::SqlWriter::write_create_view::<E>
+            } else {
+                D::SqlWriter::write_create_table::<E>
+            },
+            write_drop: if E::is_view() {
+                D::SqlWriter::write_drop_view::<E>
+            } else {
+                D::SqlWriter::write_drop_table::<E>
+            },
+        });
+        self
+    }
+
+    /// Tables of the entities registered, in creation order.
+    ///
+    /// Error if the foreign keys form a cycle.
+    pub fn tables(&self) -> Result<Vec<&TableName>> {
+        self.ordered()
+            .map(|v| v.into_iter().map(|v| &v.node.table).collect())
+    }
+
+    /// Creates the schemas, tables and views of the entities registered, the referenced tables
+    /// first.
+    ///
+    /// Parameters:
+    /// - `if_not_exists`: guards against existing tables (if drivers support it).
+    pub fn create_all<Exec>(
+        &self,
+        executor: &mut Exec,
+        if_not_exists: bool,
+    ) -> impl Future<Output = Result<()>> + Send
+    where
+        Exec: Executor<Driver = D>,
+    {
+        let queries = self.ordered().map(|entities| {
+            let writer = executor.driver().sql_writer();
+            let mut schemas = BTreeSet::new();
+            entities
+                .into_iter()
+                .map(|entity| {
+                    let mut query = String::with_capacity(512);
+                    let schema = &entity.node.table.schema;
+                    if !schema.is_empty() && schemas.insert(schema) {
+                        (entity.write_create_schema)(&writer, &mut query, true);
+                    }
+                    (entity.write_create)(&writer, &mut query, if_not_exists);
+                    query
+                })
+                .collect::<Vec<_>>()
+        });
+        execute_all(executor, queries)
+    }
+
+    /// Drops the tables and views of the entities registered, the referencing tables first.
+    /// Schemas are left in place.
+    ///
+    /// Parameters:
+    /// - `if_exists`: guards against missing tables (if drivers support it).
+    pub fn drop_all<Exec>(
+        &self,
+        executor: &mut Exec,
+        if_exists: bool,
+    ) -> impl Future<Output = Result<()>> + Send
+    where
+        Exec: Executor<Driver = D>,
+    {
+        let queries = self.ordered().map(|entities| {
+            let writer = executor.driver().sql_writer();
+            entities
+                .into_iter()
+                .rev()
+                .map(|entity| {
+                    let mut query = String::with_capacity(128);
+                    (entity.write_drop)(&writer, &mut query, if_exists);
+                    query
+                })
+                .collect::<Vec<_>>()
+        });
+        execute_all(executor, queries)
+    }
+
+    fn ordered(&self) -> Result<Vec<&RegisteredEntity<D>>> {
+        let (result, cycle) = creation_order(&self.entities, |v| &v.node);
+        if !cycle.is_empty() {
+            let error = Error::msg(format!(
+                "The foreign keys of {} form a cycle, the tables cannot be ordered",
+                cycle
+                    .iter()
+                    .map(|v| format!("`{}`", v))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+            log::error!("{:#}", error);
+            return Err(error);
+        }
+        Ok(result)
+    }
+}
+
+impl<D: Driver> Default for EntityRegistry<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+async fn execute_all(executor: &mut impl Executor, queries: Result<Vec<String>>) -> Result<()> {
+    for query in queries? {
+        executor.execute(query).await?;
+    }
+    Ok(())
+}
@@ -1,9 +1,14 @@
 use crate::{
-    AsQuery, Driver, Entity, Query, QueryResult, Result, RowLabeled, RowsAffected,
+    AsQuery, AsValue, Driver, Entity, Query, QueryResult, Result, RowLabeled, RowsAffected,
+    TableName, TableSchema, WithSchema,
     stream::{Stream, StreamExt, TryStreamExt},
     writer::SqlWriter,
 };
-use std::future::Future;
+use futures::future::Either;
+use std::{
+    borrow::Cow,
+    future::{self, Future},
+};
 
 /// Async query executor bound to a concrete `Driver`.
 ///
@@ -20,6 +25,15 @@ pub trait Executor: Send + Sized {
     /// Access the driver instance.
     fn driver(&self) -> &Self::Driver;
 
+    /// The executor runs inside a transaction: a `Transaction`, a `Savepoint` or a connection
+    /// whose transaction was neither committed nor rolled back. It cannot begin another one with
+    /// `BEGIN`, and `Pool` closes such a connection instead of handing it out again.
+    ///
+    /// Connections of drivers whose transactions roll back when dropped keep the default `false`.
+    fn in_transaction(&self) -> bool {
+        false
+    }
+
     /// Prepare a query (e.g. statement caching / parameter binding) returning a `Query`.
     ///
     /// Await/Consume:
@@ -96,7 +110,11 @@ pub trait Executor: Send + Sized {
     ///
     /// Semantics:
     /// - Uses driver append/ingest feature when supported.
-    /// - Falls back to plain INSERT statements via `sql_writer().write_insert(..., false)` otherwise.
+    /// - Falls back to plain INSERT statements via `sql_writer().write_insert_chunks(..)` otherwise,
+    ///   each one bounded by `Driver::MAX_INSERT_ROWS` and `Driver::MAX_INSERT_LENGTH`.
+    /// - Entities are validated first, nothing is sent if any of them fails.
+    /// - Chunks are executed one after the other, a failing chunk does not undo the previous
+    ///   ones. Use `Connection::append_atomic` to insert all or nothing.
     ///
     /// Returns:
     /// - Total number of inserted rows.
@@ -108,10 +126,103 @@ pub trait Executor: Send + Sized {
         E: Entity + 'a,
         It: IntoIterator<Item = &'a E> + Send,
     {
-        let mut query = String::new();
+        let entities = entities.into_iter().collect::<Vec<_>>();
+        if let Some(error) = E::writable()
+            .err()
+            .or_else(|| entities.iter().find_map(|entity| entity.validate().err()))
+        {
+            return Either::Left(future::ready(Err(error)));
+        }
+        let mut queries = Vec::new();
+        self.driver().sql_writer().write_insert_chunks(
+            &mut queries,
+            &entities,
+            None,
+            <Self::Driver as Driver>::MAX_INSERT_ROWS,
+            <Self::Driver as Driver>::MAX_INSERT_LENGTH,
+        );
+        Either::Right(async move {
+            let mut result = RowsAffected::default();
+            for query in queries {
+                result.extend([self.execute(query).await?]);
+            }
+            Ok(result)
+        })
+    }
+
+    /// Executor rendering the tables declared without a schema in `schema`, the ones declared
+    /// with a schema keep it.
+    ///
+    /// The operation futures can be awaited directly, see [`WithSchema`]. The driver is cloned
+    /// into it.
+    ///
+    /// *Example:*
+    /// ```ignore
+    /// Order::insert_one(&mut executor.with_schema("tenant_42"), &order).await?;
+    /// ```
+    fn with_schema(&mut self, schema: impl Into<Cow<'static, str>>) -> WithSchema<'_, Self>
+    where
+        Self::Driver: Clone + Send,
+    {
+        WithSchema::new(self, schema)
+    }
+
+    /// List the tables in the database, the ones in `schema` or every table outside the system
+    /// schemas.
+    fn tables(
+        &mut self,
+        schema: Option<&str>,
+    ) -> impl Future<Output = Result<Vec<TableName>>> + Send {
+        let mut query = String::with_capacity(256);
         self.driver()
             .sql_writer()
-            .write_insert(&mut query, entities, false);
-        self.execute(query)
+            .write_select_tables(&mut query, schema);
+        self.fetch(query)
+            .and_then(|row| async move {
+                let mut values = row.values.into_iter();
+                Ok(TableName::new(
+                    Option::<String>::try_from_value(values.next().unwrap_or_default())?
+                        .unwrap_or_default(),
+                    String::try_from_value(values.next().unwrap_or_default())?,
+                ))
+            })
+            .try_collect()
+    }
+
+    /// Read the live structure of `table` from the database catalog: columns, primary key, unique
+    /// constraints, foreign keys and indexes.
+    ///
+    /// An empty schema stands for the default one of the connection.
+    ///
+    /// Returns `None` when the table does not exist.
+    fn describe_table(
+        &mut self,
+        table: &TableName,
+    ) -> impl Future<Output = Result<Option<TableSchema>>> + Send {
+        let writer = self.driver().sql_writer();
+        let mut columns = String::with_capacity(256);
+        writer.write_select_table_columns(&mut columns, table);
+        let mut constraints = String::with_capacity(1024);
+        writer.write_select_table_constraints(&mut constraints, table);
+        let mut indexes = String::with_capacity(512);
+        writer.write_select_table_indexes(&mut indexes, table);
+        let table = table.clone();
+        async move {
+            let columns = self.fetch(columns).try_collect::<Vec<_>>().await?;
+            if columns.is_empty() {
+                return Ok(None);
+            }
+            let constraints = if constraints.is_empty() {
+                Vec::new()
+            } else {
+                self.fetch(constraints).try_collect().await?
+            };
+            let indexes = if indexes.is_empty() {
+                Vec::new()
+            } else {
+                self.fetch(indexes).try_collect().await?
+            };
+            TableSchema::decode(table, columns, constraints, indexes)
+        }
     }
 }
@@ -27,6 +27,8 @@ pub enum BinaryOpType {
     NotRegexp,
     Glob,
     NotGlob,
+    In,
+    NotIn,
     Equal,
     NotEqual,
     Less,
@@ -93,6 +95,8 @@ impl Display for BinaryOpType {
             BinaryOpType::NotRegexp => "NotRegexp",
             BinaryOpType::Glob => "Glob",
             BinaryOpType::NotGlob => "NotGlob",
+            BinaryOpType::In => "In",
+            BinaryOpType::NotIn => "NotIn",
             BinaryOpType::Equal => "Equal",
             BinaryOpType::NotEqual => "NotEqual",
             BinaryOpType::Less => "Less",
@@ -0,0 +1,309 @@
+use crate::{Action, AsValue, Error, Result, RowLabeled, TableRef, Value};
+use std::fmt::{self, Display, Formatter};
+
+/// Table found in the database catalog.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TableName {
+    /// Schema name, empty for the default one.
+    pub schema: String,
+    /// Table name.
+    pub name: String,
+}
+
+impl TableName {
+    pub fn new(schema: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            schema: schema.into(),
+            name: name.into(),
+        }
+    }
+}
+
+impl From<&TableRef> for TableName {
+    /// Table targeted by `TableRef`, honoring the `with_schema` override.
+    fn from(value: &TableRef) -> Self {
+        Self::new(value.effective_schema(), value.name)
+    }
+}
+
+impl Display for TableName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if !self.schema.is_empty() {
+            write!(f, "{}.", self.schema)?;
+        }
+        f.write_str(&self.name)
+    }
+}
+
+/// Structure of a table as currently found in the database, the owned counterpart of what an
+/// `Entity` declares through `TableRef` and `ColumnDef`.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct TableSchema {
+    /// Table identity.
+    pub table: TableName,
+    /// Columns in declaration order.
+    pub columns: Vec<ColumnSchema>,
+    /// Columns forming the primary key, in key order.
+    pub primary_key: Vec<String>,
+    /// Unique constraints, also the single column ones.
+    pub unique: Vec<UniqueSchema>,
+    /// Foreign key constraints.
+    pub foreign_keys: Vec<ForeignKeySchema>,
+    /// Indexes, except the ones backing the primary key and unique constraints.
+    pub indexes: Vec<IndexSchema>,
+}
+
+/// Column found in the database catalog.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ColumnSchema {
+    /// Column name.
+    pub name: String,
+    /// Type as reported by the database (e.g. `character varying(64)`).
+    pub column_type: String,
+    /// `Value` closest to `column_type`, `Value::Unknown` when it is not recognized.
+    pub value: Value,
+    /// Nullability flag.
+    pub nullable: bool,
+    /// Default expression as reported by the database.
+    pub default: Option<String>,
+    /// Column comment, `None` when missing or not supported by the database.
+    pub comment: Option<String>,
+}
+
+/// Unique constraint found in the database catalog.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct UniqueSchema {
+    /// Constraint name.
+    pub name: String,
+    /// Constrained columns.
+    pub columns: Vec<String>,
+}
+
+/// Foreign key found in the database catalog.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct ForeignKeySchema {
+    /// Constraint name (generated by the driver when the database does not name it).
+    pub name: String,
+    /// Referencing columns.
+    pub columns: Vec<String>,
+    /// Referenced table.
+    pub references: TableName,
+    /// Referenced columns, same order as `columns`.
+    pub references_columns: Vec<String>,
+    /// Action for deletes.
+    pub on_delete: Option<Action>,
+    /// Action for updates.
+    pub on_update: Option<Action>,
+}
+
+/// Index found in the database catalog.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct IndexSchema {
+    /// Index name.
+    pub name: String,
+    /// Indexed columns, in index order.
+    pub columns: Vec<String>,
+    /// Unique index.
+    pub unique: bool,
+}
+
+impl TableSchema {
+    /// Column having the given name.
+    pub fn column(&self, name: &str) -> Option<&ColumnSchema> {
+        self.columns.iter().find(|c| c.name == name)
+    }
+
+    /// Builds the table from the rows of the catalog queries written by the `SqlWriter`:
+    /// - `columns`: name, type, nullable, default, comment
+    /// - `constraints`: name, type, column, position, referenced schema, referenced table,
+    ///   referenced column, update rule, delete rule
+    /// - `indexes`: name, unique, column
+    ///
+    /// Returns `None` when there are no columns (the table does not exist).
+    #[doc(hidden)]
+    pub fn decode(
+        table: TableName,
+        columns: Vec<RowLabeled>,
+        constraints: Vec<RowLabeled>,
+        indexes: Vec<RowLabeled>,
+    ) -> Result<Option<Self>> {
+        if columns.is_empty() {
+            return Ok(None);
+        }
+        let mut result = TableSchema {
+            table,
+            ..Default::default()
+        };
+        for row in columns {
+            let mut values = row.values.into_iter();
+            let name = decode_text(values.next())?.unwrap_or_default();
+            let column_type = decode_text(values.next())?.unwrap_or_default();
+            result.columns.push(ColumnSchema {
+                value: sql_type_to_value(&column_type),
+                name,
+                column_type,
+                nullable: bool::try_from_value(values.next().unwrap_or_default())?,
+                default: decode_text(values.next())?,
+                comment: decode_text(values.next())?.filter(|v| !v.is_empty()),
+            });
+        }
+        // Rows of the same constraint are grouped, keeping the order they first appear in
+        let mut groups = Vec::<(String, String, Vec<_>)>::new();
+        for row in constraints {
+            let mut values = row.values.into_vec();
+            values.resize(9, Value::Null);
+            let name = decode_text(Some(values[0].clone()))?.unwrap_or_default();
+            let kind = decode_text(Some(values[1].clone()))?
+                .unwrap_or_default()
+                .to_ascii_uppercase();
+            let position = Option::<i64>::try_from_value(values[3].clone())?.unwrap_or_default();
+            match groups.iter_mut().find(|(n, k, _)| *n == name && *k == kind) {
+                Some((.., rows)) => rows.push((position, values)),
+                None => groups.push((name, kind, vec![(position, values)])),
+            }
+        }
+        for (name, kind, mut rows) in groups {
+            rows.sort_by_key(|(position, _)| *position);
+            let mut columns = Vec::with_capacity(rows.len());
+            let mut references = TableName::default();
+            let mut references_columns = Vec::new();
+            let mut on_update = None;
+            let mut on_delete = None;
+            for (_, values) in rows {
+                let mut values = values.into_iter().skip(2);
+                columns.push(decode_text(values.next())?.unwrap_or_default());
+                values.next();
+                references.schema = decode_text(values.next())?.unwrap_or_default();
+                references.name = decode_text(values.next())?.unwrap_or_default();
+                if let Some(column) = decode_text(values.next())? {
+                    references_columns.push(column);
+                }
+                on_update = decode_text(values.next())?.and_then(|v| decode_action(&v));
+                on_delete = decode_text(values.next())?.and_then(|v| decode_action(&v));
+            }
+            match kind.as_str() {
+                "PRIMARY KEY" => result.primary_key = columns,
+                "UNIQUE" => result.unique.push(UniqueSchema { name, columns }),
+                "FOREIGN KEY" => result.foreign_keys.push(ForeignKeySchema {
+                    name,
+                    columns,
+                    references,
+                    references_columns,
+                    on_delete,
+                    on_update,
+                }),
+                _ => {}
+            }
+        }
+        for row in indexes {
+            let mut values = row.values.into_iter();
+            let name = decode_text(values.next())?.unwrap_or_default();
+            let unique = bool::try_from_value(values.next().unwrap_or_default())?;
+            let column = decode_text(values.next())?.unwrap_or_default();
+            match result.indexes.iter_mut().find(|v| v.name == name) {
+                Some(index) => index.columns.push(column),
+                None => result.indexes.push(IndexSchema {
+                    name,
+                    columns: vec![column],
+                    unique,
+                }),
+            }
+        }
+        Ok(Some(result))
+    }
+}
+
+/// Best effort conversion of a SQL type name, in any of the supported dialects, to the `Value`
+/// variant holding it.
+///
+/// Arguments are used where they matter (`DECIMAL(10,2)`, `INTEGER[3]`, `MAP(VARCHAR, INTEGER)`),
+/// a type that is not recognized results in `Value::Unknown`.
+pub fn sql_type_to_value(column_type: &str) -> Value {
+    let column_type = column_type.trim().to_ascii_uppercase();
+    if let Some(inner) = column_type.strip_suffix("[]") {
+        return Value::List(None, sql_type_to_value(inner).into());
+    }
+    if let Some((inner, len)) = column_type
+        .strip_suffix(']')
+        .and_then(|v| v.rsplit_once('['))
+        && let Ok(len) = len.trim().parse::<u32>()
+    {
+        return Value::Array(None, sql_type_to_value(inner).into(), len);
+    }
+    let (name, args) = match column_type.split_once('(') {
+        Some((name, rest)) => match rest.rsplit_once(')') {
+            Some((args, tail)) => (format!("{}{}", name.trim(), tail), args),
+            None => (column_type.clone(), ""),
+        },
+        None => (column_type.clone(), ""),
+    };
+    let unsigned = name.contains(" UNSIGNED");
+    let name = name
+        .replace(" UNSIGNED", "")
+        .replace(" ZEROFILL", "")
+        .replace(" SIGNED", "");
+    let args = args.split(',').map(str::trim).collect::<Vec<_>>();
+    let arg = |i: usize| args.get(i).and_then(|v| v.parse::<u8>().ok());
+    match name.trim() {
+        "BOOLEAN" | "BOOL" => Value::Boolean(None),
+        "TINYINT" if arg(0) == Some(1) => Value::Boolean(None),
+        "TINYINT" | "INT1" if unsigned => Value::UInt8(None),
+        "TINYINT" | "INT1" => Value::Int8(None),
+        "UTINYINT" => Value::UInt8(None),
+        "SMALLINT" | "INT2" | "SMALLSERIAL" if unsigned => Value::UInt16(None),
+        "SMALLINT" | "INT2" | "SMALLSERIAL" => Value::Int16(None),
+        "USMALLINT" => Value::UInt16(None),
+        "INTEGER" | "INT" | "INT4" | "MEDIUMINT" | "SERIAL" if unsigned => Value::UInt32(None),
+        "INTEGER" | "INT" | "INT4" | "MEDIUMINT" | "SERIAL" => Value::Int32(None),
+        "UINTEGER" => Value::UInt32(None),
+        "BIGINT" | "INT8" | "BIGSERIAL" | "LONG" if unsigned => Value::UInt64(None),
+        "BIGINT" | "INT8" | "BIGSERIAL" | "LONG" => Value::Int64(None),
+        "UBIGINT" => Value::UInt64(None),
+        "HUGEINT" | "INT128" => Value::Int128(None),
+        "UHUGEINT" => Value::UInt128(None),
+        "REAL" | "FLOAT4" | "FLOAT" => Value::Float32(None),
+        "DOUBLE" | "DOUBLE PRECISION" | "FLOAT8" => Value::Float64(None),
+        "DECIMAL" | "NUMERIC" => Value::Decimal(None, arg(0).unwrap_or(0), arg(1).unwrap_or(0)),
+        "CHAR" | "CHARACTER" | "BPCHAR" if arg(0) == Some(1) => Value::Char(None),
+        "CHAR" | "CHARACTER" | "BPCHAR" | "VARCHAR" | "CHARACTER VARYING" | "NVARCHAR"
+        | "NCHAR" | "TEXT" | "TINYTEXT" | "MEDIUMTEXT" | "LONGTEXT" | "STRING" | "CLOB" => {
+            Value::Varchar(None)
+        }
+        "BLOB" | "BYTEA" | "BINARY" | "VARBINARY" | "TINYBLOB" | "MEDIUMBLOB" | "LONGBLOB" => {
+            Value::Blob(None)
+        }
+        "DATE" => Value::Date(None),
+        "TIME" | "TIME WITHOUT TIME ZONE" => Value::Time(None),
+        "TIMESTAMP" | "TIMESTAMP WITHOUT TIME ZONE" | "DATETIME" => Value::Timestamp(None),
+        "TIMESTAMPTZ" | "TIMESTAMP WITH TIME ZONE" => Value::TimestampWithTimezone(None),
+        "INTERVAL" => Value::Interval(None),
+        "UUID" => Value::Uuid(None),
+        "JSON" | "JSONB" => Value::Json(None),
+        "MAP" if args.len() == 2 => Value::Map(
+            None,
+            sql_type_to_value(args[0]).into(),
+            sql_type_to_value(args[1]).into(),
+        ),
+        _ => Value::Unknown(None),
+    }
+}
+
+fn decode_text(value: Option<Value>) -> Result<Option<String>> {
+    Ok(match value {
+        None | Some(Value::Null) => None,
+        Some(Value::Blob(v)) => v.map(|v| String::from_utf8_lossy(&v).into_owned()),
+        Some(v) => Option::<String>::try_from_value(v)
+            .map_err(|e| Error::msg(format!("Unexpected value in the catalog: {:#}", e)))?,
+    })
+}
+
+fn decode_action(value: &str) -> Option<Action> {
+    Some(match value.trim().to_ascii_uppercase().as_str() {
+        "NO ACTION" => Action::NoAction,
+        "RESTRICT" => Action::Restrict,
+        "CASCADE" => Action::Cascade,
+        "SET NULL" => Action::SetNull,
+        "SET DEFAULT" => Action::SetDefault,
+        _ => return None,
+    })
+}
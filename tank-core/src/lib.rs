@@ -98,16 +98,28 @@ mod data_set;
 mod decode_type;
 mod driver;
 mod entity;
+mod entity_codegen;
+mod entity_hooks;
+mod entity_registry;
 mod executor;
 mod expression;
 mod interval;
+mod introspection;
 mod join;
+mod migration;
+mod on_conflict;
+mod pool;
 mod prepared;
 mod query;
 mod relations;
+mod schema_diff;
+mod schema_export;
+mod schema_override;
 mod table_ref;
+mod tracked;
 mod transaction;
 mod util;
+mod validation;
 mod value;
 mod writer;
 
@@ -119,16 +131,28 @@ pub use data_set::*;
 pub use decode_type::*;
 pub use driver::*;
 pub use entity::*;
+pub use entity_codegen::*;
+pub use entity_hooks::*;
+pub use entity_registry::*;
 pub use executor::*;
 pub use expression::*;
 pub use interval::*;
+pub use introspection::*;
 pub use join::*;
+pub use migration::*;
+pub use on_conflict::*;
+pub use pool::*;
 pub use prepared::*;
 pub use query::*;
 pub use relations::*;
+pub use schema_diff::*;
+pub use schema_export::*;
+pub use schema_override::*;
 pub use table_ref::*;
+pub use tracked::*;
 pub use transaction::*;
 pub use util::*;
+pub use validation::*;
 pub use value::*;
 pub use writer::*;
 pub mod stream {
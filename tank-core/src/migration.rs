@@ -0,0 +1,690 @@
+use crate::{
+    AsQuery, AsValue, Connection, Context, Driver, Entity, Error, Executor, Fragment, Query,
+    QueryResult, Result, RowsAffected, TableRef, Transaction, TransactionOptions, Value,
+    future::{BoxFuture, Either},
+    separated_by,
+    stream::{Stream, TryStreamExt},
+    table_ref::without_schema_override,
+    writer::SqlWriter,
+};
+use std::{borrow::Cow, fmt::Write, fs, future::Future, path::Path};
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+type MigrationFn<C> = Box<
+    dyn for<'a, 'c> Fn(&'a mut MigrationExecutor<'c, C>) -> BoxFuture<'a, Result<()>> + Send + Sync,
+>;
+
+/// Executor the migrations run on: the transaction of the migration if the driver has
+/// transactional DDL (see `Driver::TRANSACTIONAL_DDL`), the connection itself otherwise.
+pub enum MigrationExecutor<'c, C: Connection> {
+    Connection(&'c mut C),
+    Transaction(<C::Driver as Driver>::Transaction<'c>),
+}
+
+impl<'c, C: Connection> Executor for MigrationExecutor<'c, C> {
+    type Driver = C::Driver;
+
+    fn driver(&self) -> &Self::Driver {
+        match self {
+            Self::Connection(v) => v.driver(),
+            Self::Transaction(v) => v.driver(),
+        }
+    }
+
+    fn in_transaction(&self) -> bool {
+        match self {
+            Self::Connection(v) => v.in_transaction(),
+            Self::Transaction(v) => v.in_transaction(),
+        }
+    }
+
+    fn prepare(
+        &mut self,
+        query: String,
+    ) -> impl Future<Output = Result<Query<Self::Driver>>> + Send {
+        match self {
+            Self::Connection(v) => Either::Left(v.prepare(query)),
+            Self::Transaction(v) => Either::Right(v.prepare(query)),
+        }
+    }
+
+    fn run<'s>(
+        &'s mut self,
+        query: impl AsQuery<Self::Driver> + 's,
+    ) -> impl Stream<Item = Result<QueryResult>> + Send {
+        match self {
+            Self::Connection(v) => Either::Left(v.run(query)),
+            Self::Transaction(v) => Either::Right(v.run(query)),
+        }
+    }
+
+    fn append<'a, E, It>(
+        &mut self,
+        entities: It,
+    ) -> impl Future<Output = Result<RowsAffected>> + Send
+    where
+        E: Entity + 'a,
+        It: IntoIterator<Item = &'a E> + Send,
+    {
+        match self {
+            Self::Connection(v) => Either::Left(v.append(entities)),
+            Self::Transaction(v) => Either::Right(v.append(entities)),
+        }
+    }
+}
+
+/// Work done by one direction of a migration.
+enum MigrationStep<C: Connection> {
+    /// Statements sent as a single query.
+    Sql(Cow<'static, str>),
+    /// Arbitrary code using the executor.
+    Fn(MigrationFn<C>),
+}
+
+impl<C: Connection> MigrationStep<C> {
+    async fn run(&self, executor: &mut MigrationExecutor<'_, C>) -> Result<()> {
+        match self {
+            MigrationStep::Sql(sql) if sql.trim().is_empty() => Ok(()),
+            MigrationStep::Sql(sql) => executor.execute(sql.as_ref()).await.map(|_| ()),
+            MigrationStep::Fn(f) => f(executor).await,
+        }
+    }
+}
+
+/// Versioned change of the database, run once by the `Migrator`.
+///
+/// The checksum of SQL migrations is computed from the text of the `up` statements, so that
+/// editing an already applied migration is detected. Closure migrations use the name instead.
+pub struct Migration<C: Connection> {
+    /// Unique version, migrations are applied in increasing order.
+    pub version: i64,
+    /// Human-readable name.
+    pub name: Cow<'static, str>,
+    /// Fingerprint stored in the history table.
+    pub checksum: String,
+    up: MigrationStep<C>,
+    down: Option<MigrationStep<C>>,
+}
+
+impl<C: Connection> Migration<C> {
+    /// Migration running the given SQL statements.
+    pub fn sql(
+        version: i64,
+        name: impl Into<Cow<'static, str>>,
+        up: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        let up = up.into();
+        Self {
+            version,
+            name: name.into(),
+            checksum: checksum(&up),
+            up: MigrationStep::Sql(up),
+            down: None,
+        }
+    }
+
+    /// Migration running a closure over the executor.
+    ///
+    /// *Example:*
+    /// ```ignore
+    /// Migration::new(1, "create_operators", |executor| {
+    ///     Box::pin(Operator::create_table(executor, false, true))
+    /// })
+    /// ```
+    pub fn new<F>(version: i64, name: impl Into<Cow<'static, str>>, up: F) -> Self
+    where
+        F: for<'a, 'c> Fn(&'a mut MigrationExecutor<'c, C>) -> BoxFuture<'a, Result<()>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let name = name.into();
+        Self {
+            version,
+            checksum: checksum(&name),
+            name,
+            up: MigrationStep::Fn(Box::new(up)),
+            down: None,
+        }
+    }
+
+    /// SQL statements reverting the migration.
+    pub fn down_sql(mut self, down: impl Into<Cow<'static, str>>) -> Self {
+        self.down = Some(MigrationStep::Sql(down.into()));
+        self
+    }
+
+    /// Closure reverting the migration.
+    pub fn down<F>(mut self, down: F) -> Self
+    where
+        F: for<'a, 'c> Fn(&'a mut MigrationExecutor<'c, C>) -> BoxFuture<'a, Result<()>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.down = Some(MigrationStep::Fn(Box::new(down)));
+        self
+    }
+
+    /// True if the migration can be rolled back.
+    pub fn is_reversible(&self) -> bool {
+        self.down.is_some()
+    }
+}
+
+/// State of a migration compared to the history table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationState {
+    /// Not applied yet.
+    Pending,
+    /// Applied, with the same checksum.
+    Applied,
+    /// Applied, but the migration changed since then.
+    Modified,
+    /// Found in the history table but not among the migrations.
+    Unknown,
+}
+
+/// Migration as reported by `Migrator::status`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStatus {
+    /// Migration version.
+    pub version: i64,
+    /// Migration name.
+    pub name: String,
+    /// State of the migration.
+    pub state: MigrationState,
+    /// When it was applied (UTC), `None` if pending.
+    pub applied_at: Option<PrimitiveDateTime>,
+}
+
+/// Applies and rolls back versioned migrations, keeping track of them in a history table.
+///
+/// Each migration and its history row are run in a transaction when the driver supports
+/// transactional DDL (see `Driver::TRANSACTIONAL_DDL`), a failing migration then leaves no trace.
+/// A lock table prevents two instances from migrating the same database at once, if a process
+/// died while holding it, release it with `force_unlock`. The history and lock tables are not moved
+/// by `with_schema`, the migrations themselves are.
+///
+/// *Example:*
+/// ```ignore
+/// let migrator = Migrator::new()
+///     .migration(Migration::new(1, "create_operators", |executor| {
+///         Box::pin(Operator::create_table(executor, false, true))
+///     }))
+///     .migration(Migration::sql(2, "operators_rank", include_str!("../sql/0002_operators_rank.sql"))
+///         .down_sql(include_str!("../sql/0002_operators_rank.down.sql")));
+/// let applied = migrator.apply(&mut connection).await?;
+/// ```
+pub struct Migrator<C: Connection> {
+    migrations: Vec<Migration<C>>,
+    history: TableRef,
+    lock: TableRef,
+}
+
+impl<C: Connection> Default for Migrator<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Connection> Migrator<C> {
+    /// Migrator without migrations, using the `tank_migrations` and `tank_migrations_lock`
+    /// tables.
+    pub fn new() -> Self {
+        Self {
+            migrations: Vec::new(),
+            history: TableRef {
+                name: "tank_migrations",
+                ..Default::default()
+            },
+            lock: TableRef {
+                name: "tank_migrations_lock",
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Uses other tables for the history and the lock.
+    pub fn tables(mut self, history: TableRef, lock: TableRef) -> Self {
+        self.history = history;
+        self.lock = lock;
+        self
+    }
+
+    /// Adds a migration.
+    pub fn migration(mut self, migration: Migration<C>) -> Self {
+        self.migrations.push(migration);
+        self
+    }
+
+    /// Adds the SQL migrations found in a directory.
+    ///
+    /// Files are named `<version>_<name>.sql` (or `.up.sql`), the optional
+    /// `<version>_<name>.down.sql` reverts it. Other files are ignored.
+    pub fn load_dir(mut self, path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let mut entries = fs::read_dir(path)
+            .and_then(|v| v.collect::<std::io::Result<Vec<_>>>())
+            .map_err(|e| {
+                Error::new(e).context(format!(
+                    "While reading the migrations directory `{}`",
+                    path.display()
+                ))
+            })?;
+        entries.sort_by_key(|v| v.file_name());
+        let mut down = Vec::new();
+        for entry in entries {
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            let Some(stem) = file_name.strip_suffix(".sql") else {
+                continue;
+            };
+            let (stem, is_down) = match stem.strip_suffix(".down") {
+                Some(stem) => (stem, true),
+                None => (stem.strip_suffix(".up").unwrap_or(stem), false),
+            };
+            let Some((version, name)) = stem
+                .split_once('_')
+                .and_then(|(v, n)| Some((v.parse::<i64>().ok()?, n.trim_start_matches('_'))))
+            else {
+                continue;
+            };
+            let sql = fs::read_to_string(entry.path()).map_err(|e| {
+                Error::new(e).context(format!("While reading the migration `{}`", file_name))
+            })?;
+            if is_down {
+                down.push((version, sql));
+            } else {
+                self.migrations
+                    .push(Migration::sql(version, name.to_owned(), sql));
+            }
+        }
+        for (version, sql) in down {
+            let Some(migration) = self.migrations.iter_mut().find(|v| v.version == version) else {
+                let error = Error::msg(format!(
+                    "Migration {} has a down file but no up file",
+                    version
+                ));
+                log::error!("{:#}", error);
+                return Err(error);
+            };
+            migration.down = Some(MigrationStep::Sql(sql.into()));
+        }
+        Ok(self)
+    }
+
+    /// Migrations sorted by version, error if two of them share the same version.
+    fn sorted(&self) -> Result<Vec<&Migration<C>>> {
+        let mut result = self.migrations.iter().collect::<Vec<_>>();
+        result.sort_by_key(|v| v.version);
+        if let Some(pair) = result.windows(2).find(|v| v[0].version == v[1].version) {
+            let error = Error::msg(format!(
+                "Migrations `{}` and `{}` have the same version {}",
+                pair[0].name, pair[1].name, pair[0].version
+            ));
+            log::error!("{:#}", error);
+            return Err(error);
+        }
+        Ok(result)
+    }
+
+    /// Lists every migration, the defined ones and the ones found in the history table.
+    pub async fn status(&self, executor: &mut C) -> Result<Vec<MigrationStatus>> {
+        self.create_tables(executor).await?;
+        let history = self.history(executor).await?;
+        let mut result = self
+            .sorted()?
+            .into_iter()
+            .map(|migration| {
+                let applied = history.iter().find(|v| v.version == migration.version);
+                MigrationStatus {
+                    version: migration.version,
+                    name: migration.name.to_string(),
+                    state: match applied {
+                        None => MigrationState::Pending,
+                        Some(v) if v.checksum == migration.checksum => MigrationState::Applied,
+                        Some(..) => MigrationState::Modified,
+                    },
+                    applied_at: applied.map(|v| v.applied_at),
+                }
+            })
+            .collect::<Vec<_>>();
+        result.extend(
+            history
+                .into_iter()
+                .filter(|v| !self.migrations.iter().any(|m| m.version == v.version))
+                .map(|v| MigrationStatus {
+                    version: v.version,
+                    name: v.name,
+                    state: MigrationState::Unknown,
+                    applied_at: Some(v.applied_at),
+                }),
+        );
+        result.sort_by_key(|v| v.version);
+        Ok(result)
+    }
+
+    /// Applies the pending migrations in version order.
+    ///
+    /// Error if an applied migration was modified, nothing is applied in that case. Stops at the
+    /// first migration failing, the ones before it stay applied.
+    ///
+    /// Returns the versions applied.
+    pub async fn apply(&self, executor: &mut C) -> Result<Vec<i64>> {
+        self.create_tables(executor).await?;
+        self.locked(executor, async |executor: &mut C| {
+            let history = self.history(executor).await?;
+            let mut pending = Vec::new();
+            for migration in self.sorted()? {
+                match history.iter().find(|v| v.version == migration.version) {
+                    None => pending.push(migration),
+                    Some(v) if v.checksum != migration.checksum => {
+                        let error = Error::msg(format!(
+                            "Migration {} `{}` was modified after being applied (checksum {} instead of {})",
+                            migration.version, migration.name, migration.checksum, v.checksum
+                        ));
+                        log::error!("{:#}", error);
+                        return Err(error);
+                    }
+                    Some(..) => {}
+                }
+            }
+            let mut result = Vec::with_capacity(pending.len());
+            for migration in pending {
+                let mut record = String::with_capacity(256);
+                self.write_record(executor, &mut record, migration);
+                self.transactional(executor, &migration.up, record)
+                    .await
+                    .map_err(|e| {
+                        e.context(format!(
+                            "While applying the migration {} `{}`",
+                            migration.version, migration.name
+                        ))
+                    })?;
+                log::info!("Applied migration {} `{}`", migration.version, migration.name);
+                result.push(migration.version);
+            }
+            Ok(result)
+        })
+        .await
+    }
+
+    /// Reverts the last `steps` applied migrations, latest first.
+    ///
+    /// Error if one of them is unknown or has no down step, nothing is reverted in that case.
+    ///
+    /// Returns the versions reverted.
+    pub async fn rollback(&self, executor: &mut C, steps: usize) -> Result<Vec<i64>> {
+        self.create_tables(executor).await?;
+        self.locked(executor, async |executor: &mut C| {
+            let history = self.history(executor).await?;
+            let mut reverting = Vec::with_capacity(steps);
+            for applied in history.iter().rev().take(steps) {
+                let Some(migration) = self
+                    .migrations
+                    .iter()
+                    .find(|v| v.version == applied.version)
+                else {
+                    let error = Error::msg(format!(
+                        "Cannot rollback the migration {} `{}`, it is not defined",
+                        applied.version, applied.name
+                    ));
+                    log::error!("{:#}", error);
+                    return Err(error);
+                };
+                let Some(down) = &migration.down else {
+                    let error = Error::msg(format!(
+                        "Cannot rollback the migration {} `{}`, it has no down step",
+                        migration.version, migration.name
+                    ));
+                    log::error!("{:#}", error);
+                    return Err(error);
+                };
+                reverting.push((migration, down));
+            }
+            let mut result = Vec::with_capacity(reverting.len());
+            for (migration, down) in reverting {
+                let mut forget = String::with_capacity(128);
+                self.write_forget(executor, &mut forget, migration.version);
+                self.transactional(executor, down, forget)
+                    .await
+                    .map_err(|e| {
+                        e.context(format!(
+                            "While reverting the migration {} `{}`",
+                            migration.version, migration.name
+                        ))
+                    })?;
+                log::info!(
+                    "Reverted migration {} `{}`",
+                    migration.version,
+                    migration.name
+                );
+                result.push(migration.version);
+            }
+            Ok(result)
+        })
+        .await
+    }
+
+    /// Releases the lock left behind by a process that stopped while migrating.
+    pub async fn force_unlock(&self, executor: &mut C) -> Result<()> {
+        self.create_tables(executor).await?;
+        let mut query = String::with_capacity(64);
+        self.write_unlock(executor, &mut query);
+        executor.execute(query).await.map(|_| ())
+    }
+
+    async fn create_tables(&self, executor: &mut C) -> Result<()> {
+        let writer = executor.driver().sql_writer();
+        let mut context = Context::new(Fragment::SqlCreateTable, false);
+        let mut query = String::with_capacity(512);
+        query.push_str("CREATE TABLE IF NOT EXISTS ");
+        without_schema_override(|| writer.write_table_ref(&mut context, &mut query, &self.history));
+        query.push_str(" (\n");
+        let columns = [
+            ("version", Value::Int64(None), " PRIMARY KEY"),
+            ("name", Value::Varchar(None), " NOT NULL"),
+            ("checksum", Value::Varchar(None), " NOT NULL"),
+            ("applied_at", Value::Timestamp(None), " NOT NULL"),
+        ];
+        separated_by(
+            &mut query,
+            columns,
+            |out, (name, value, constraint)| {
+                writer.write_identifier_quoted(&mut context, out, name);
+                out.push(' ');
+                writer.write_column_type(&mut context, out, &value);
+                out.push_str(constraint);
+            },
+            ",\n",
+        );
+        query.push_str(");\nCREATE TABLE IF NOT EXISTS ");
+        without_schema_override(|| writer.write_table_ref(&mut context, &mut query, &self.lock));
+        query.push_str(" (\n");
+        writer.write_identifier_quoted(&mut context, &mut query, "id");
+        query.push(' ');
+        writer.write_column_type(&mut context, &mut query, &Value::Int32(None));
+        query.push_str(" PRIMARY KEY,\n");
+        writer.write_identifier_quoted(&mut context, &mut query, "locked_at");
+        query.push(' ');
+        writer.write_column_type(&mut context, &mut query, &Value::Timestamp(None));
+        query.push_str(" NOT NULL);");
+        executor.execute(query).await.map(|_| ())
+    }
+
+    async fn history(&self, executor: &mut C) -> Result<Vec<AppliedMigration>> {
+        let writer = executor.driver().sql_writer();
+        let mut context = Context::new(Fragment::SqlSelect, false);
+        let mut query = String::with_capacity(128);
+        query.push_str("SELECT ");
+        separated_by(
+            &mut query,
+            ["version", "name", "checksum", "applied_at"],
+            |out, v| writer.write_identifier_quoted(&mut context, out, v),
+            ", ",
+        );
+        query.push_str(" FROM ");
+        without_schema_override(|| writer.write_table_ref(&mut context, &mut query, &self.history));
+        query.push_str(" ORDER BY ");
+        writer.write_identifier_quoted(&mut context, &mut query, "version");
+        query.push(';');
+        executor
+            .fetch(query)
+            .map_err(|e| e.context("While reading the migrations history"))
+            .and_then(|row| async move {
+                let mut values = row.values.into_iter();
+                let mut next = || values.next().unwrap_or_default();
+                Ok(AppliedMigration {
+                    version: i64::try_from_value(next())?,
+                    name: String::try_from_value(next())?,
+                    checksum: String::try_from_value(next())?,
+                    applied_at: PrimitiveDateTime::try_from_value(next())?,
+                })
+            })
+            .try_collect()
+            .await
+    }
+
+    /// Runs `body` while holding the lock, releasing it whatever the outcome.
+    async fn locked<T>(
+        &self,
+        executor: &mut C,
+        body: impl AsyncFnOnce(&mut C) -> Result<T>,
+    ) -> Result<T> {
+        let writer = executor.driver().sql_writer();
+        let mut context = Context::new(Fragment::SqlInsertInto, false);
+        let mut query = String::with_capacity(128);
+        query.push_str("INSERT INTO ");
+        without_schema_override(|| writer.write_table_ref(&mut context, &mut query, &self.lock));
+        query.push_str(" (");
+        writer.write_identifier_quoted(&mut context, &mut query, "id");
+        query.push_str(", ");
+        writer.write_identifier_quoted(&mut context, &mut query, "locked_at");
+        query.push_str(") VALUES (1, ");
+        writer.write_value(&mut context, &mut query, &Value::Timestamp(Some(now())));
+        query.push_str(");");
+        if let Err(e) = executor.execute(query).await {
+            let error = e.context(format!(
+                "The migrations are locked by another instance, if no other instance is running call `Migrator::force_unlock` or delete the row in `{}`",
+                without_schema_override(|| self.lock.full_name())
+            ));
+            log::error!("{:#}", error);
+            return Err(error);
+        }
+        let result = body(executor).await;
+        let mut query = String::with_capacity(64);
+        self.write_unlock(executor, &mut query);
+        if let Err(e) = executor.execute(query).await {
+            log::error!("{:#}", e);
+            if result.is_ok() {
+                return Err(e.context("While releasing the migrations lock"));
+            }
+        }
+        result
+    }
+
+    /// Runs `step` followed by `bookkeeping`, in a transaction if the driver has transactional
+    /// DDL.
+    async fn transactional(
+        &self,
+        connection: &mut C,
+        step: &MigrationStep<C>,
+        bookkeeping: String,
+    ) -> Result<()> {
+        let mut executor = if <C::Driver as Driver>::TRANSACTIONAL_DDL {
+            MigrationExecutor::Transaction(
+                connection
+                    .begin_driver_transaction(TransactionOptions::default())
+                    .await?,
+            )
+        } else {
+            MigrationExecutor::Connection(connection)
+        };
+        let result = match step.run(&mut executor).await {
+            Ok(..) => executor.execute(bookkeeping).await.map(|_| ()),
+            Err(e) => Err(e),
+        };
+        let MigrationExecutor::Transaction(transaction) = executor else {
+            return result;
+        };
+        match result {
+            Ok(..) => transaction.commit().await,
+            Err(e) => {
+                if let Err(rollback) = transaction.rollback().await {
+                    log::error!("{:#}", rollback);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    fn write_record(&self, executor: &C, out: &mut String, migration: &Migration<C>) {
+        let writer = executor.driver().sql_writer();
+        let mut context = Context::new(Fragment::SqlInsertInto, false);
+        out.push_str("INSERT INTO ");
+        without_schema_override(|| writer.write_table_ref(&mut context, out, &self.history));
+        out.push_str(" (");
+        separated_by(
+            out,
+            ["version", "name", "checksum", "applied_at"],
+            |out, v| writer.write_identifier_quoted(&mut context, out, v),
+            ", ",
+        );
+        out.push_str(") VALUES (");
+        let values = [
+            Value::Int64(Some(migration.version)),
+            Value::Varchar(Some(migration.name.to_string())),
+            Value::Varchar(Some(migration.checksum.clone())),
+            Value::Timestamp(Some(now())),
+        ];
+        separated_by(
+            out,
+            values,
+            |out, v| writer.write_value(&mut context, out, &v),
+            ", ",
+        );
+        out.push_str(");");
+    }
+
+    fn write_forget(&self, executor: &C, out: &mut String, version: i64) {
+        let writer = executor.driver().sql_writer();
+        let mut context = Context::new(Fragment::SqlDeleteFrom, false);
+        out.push_str("DELETE FROM ");
+        without_schema_override(|| writer.write_table_ref(&mut context, out, &self.history));
+        out.push_str(" WHERE ");
+        writer.write_identifier_quoted(&mut context, out, "version");
+        let _ = write!(out, " = {};", version);
+    }
+
+    fn write_unlock(&self, executor: &C, out: &mut String) {
+        let writer = executor.driver().sql_writer();
+        let mut context = Context::new(Fragment::SqlDeleteFrom, false);
+        out.push_str("DELETE FROM ");
+        without_schema_override(|| writer.write_table_ref(&mut context, out, &self.lock));
+        out.push(';');
+    }
+}
+
+/// Row of the history table.
+struct AppliedMigration {
+    version: i64,
+    name: String,
+    checksum: String,
+    applied_at: PrimitiveDateTime,
+}
+
+fn now() -> PrimitiveDateTime {
+    let now = OffsetDateTime::now_utc();
+    PrimitiveDateTime::new(now.date(), now.time())
+}
+
+/// FNV-1a hash of the text, line endings normalized so that the checkout platform does not matter.
+fn checksum(value: &str) -> String {
+    let hash = value
+        .replace("\r\n", "\n")
+        .bytes()
+        .fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+    format!("{:016x}", hash)
+}
@@ -0,0 +1,100 @@
+use crate::{ColumnDef, ColumnRef, Entity, PrimaryKeyType};
+
+/// Behavior of a INSERT hitting a row that already exists (upsert).
+///
+/// The default updates every inserted column when the primary key conflicts, it is what
+/// `save()` and `write_insert(.., true)` use.
+///
+/// *Example:*
+/// ```ignore
+/// // Keep the existing row when the callsign is taken
+/// let on_conflict = OnConflict::target([Operator::callsign]).do_nothing();
+/// // Refresh only the rank of the operator having the same callsign
+/// let on_conflict = OnConflict::target([Operator::callsign]).update([Operator::rank]);
+/// ```
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct OnConflict {
+    /// Columns of the constraint that conflicts, the primary key if empty.
+    ///
+    /// MySQL does not support a target, any unique constraint triggers the conflict.
+    pub target: Vec<ColumnRef>,
+    /// Action taken on the existing row.
+    pub action: ConflictAction,
+}
+
+/// Action taken by a upsert on the conflicting row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConflictAction {
+    /// Leave the existing row untouched (`DO NOTHING`).
+    Nothing,
+    /// Overwrite the listed columns, or every inserted column neither in the target nor in the
+    /// primary key when `None`.
+    Update(Option<Vec<ColumnRef>>),
+}
+
+impl Default for ConflictAction {
+    fn default() -> Self {
+        Self::Update(None)
+    }
+}
+
+impl OnConflict {
+    /// Conflict on the given columns, usually the ones of a `unique` constraint.
+    pub fn target(columns: impl IntoIterator<Item = ColumnRef>) -> Self {
+        Self {
+            target: columns.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+    /// Conflict on the `index`-th unique constraint of the entity, see `Entity::unique_defs`.
+    ///
+    /// Panics if the entity has fewer unique constraints.
+    pub fn unique<E: Entity>(index: usize) -> Self {
+        let unique = E::unique_defs().nth(index).unwrap_or_else(|| {
+            panic!(
+                "Entity `{}` does not have a unique constraint at index {}",
+                E::table().full_name(),
+                index
+            )
+        });
+        Self::target(unique.map(|c| c.column_ref))
+    }
+    /// Leave the existing row untouched.
+    pub fn do_nothing(mut self) -> Self {
+        self.action = ConflictAction::Nothing;
+        self
+    }
+    /// Overwrite only the given columns.
+    pub fn update(mut self, columns: impl IntoIterator<Item = ColumnRef>) -> Self {
+        self.action = ConflictAction::Update(Some(columns.into_iter().collect()));
+        self
+    }
+    /// Names of the columns identifying the conflict, falling back to the primary key.
+    pub fn target_names<E: Entity>(&self) -> Vec<&'static str> {
+        if self.target.is_empty() {
+            E::primary_key_def().map(|c| c.name()).collect()
+        } else {
+            self.target.iter().map(|c| c.name).collect()
+        }
+    }
+    /// Columns to overwrite among the `inserted` ones, empty when nothing must be updated.
+    pub fn update_columns<'a, E: Entity>(
+        &self,
+        inserted: impl Iterator<Item = &'a ColumnDef>,
+    ) -> Vec<&'a ColumnDef> {
+        match &self.action {
+            ConflictAction::Nothing => Vec::new(),
+            ConflictAction::Update(None) => {
+                let target = self.target_names::<E>();
+                inserted
+                    .filter(|c| {
+                        c.primary_key == PrimaryKeyType::None && !target.contains(&c.name())
+                    })
+                    .collect()
+            }
+            ConflictAction::Update(Some(columns)) => inserted
+                .filter(|c| columns.iter().any(|v| v.name == c.name()))
+                .collect(),
+        }
+    }
+}
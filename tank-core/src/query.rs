@@ -166,6 +166,19 @@ impl RowLabeled {
     }
 }
 
+/// Types that can be decoded from a labeled row.
+///
+/// Implemented through `#[derive(FromRow)]` for plain structs receiving the result of
+/// custom projections, joins or aggregates. Unlike `Entity` the type is not bound to any table.
+pub trait FromRow {
+    /// Constructs `Self` from a labeled database row.
+    ///
+    /// Error if mandatory columns are missing or type conversion fails.
+    fn from_row(row: RowLabeled) -> Result<Self>
+    where
+        Self: Sized;
+}
+
 /// Heterogeneous items emitted by `Executor::run` combining rows and modify results.
 #[derive(Debug)]
 pub enum QueryResult {
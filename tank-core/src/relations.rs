@@ -1,6 +1,19 @@
-use crate::{AsValue, ColumnDef, Entity, TableRef};
+use crate::{
+    AsValue, BinaryOp, BinaryOpType, ColumnDef, ColumnRef, Driver, Entity, Error, Executor,
+    Expression, Result, TableRef, Value,
+    future::Either,
+    stream::{self, Stream},
+    writer::SqlWriter,
+};
+use futures::{StreamExt, TryStreamExt};
 use rust_decimal::Decimal;
-use std::{marker::PhantomData, mem};
+use std::{
+    collections::{HashMap, HashSet},
+    future::{self, Future},
+    marker::PhantomData,
+    mem,
+    pin::pin,
+};
 
 /// Decimal wrapper enforcing compile-time width/scale.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -93,3 +106,283 @@ impl<T: Entity> References<T> {
         &self.columns
     }
 }
+
+/// Navigation across the foreign keys declared with `#[tank(references = ...)]`.
+///
+/// Every relation is identified by the column holding the foreign key (for example `Book::author`),
+/// the referenced column is taken from its definition. Implemented for every `Entity`.
+///
+/// *Example:*
+/// ```ignore
+/// let author: Option<Author> = book.belongs_to(executor, Book::author).await?;
+/// let books: Vec<Book> = author.has_many(executor, Book::author).try_collect().await?;
+/// let tags: Vec<Tag> = book.many_to_many::<BookTag, Tag>(executor, BookTag::book, BookTag::tag).await?;
+/// let books_by_author = Author::load_has_many::<Book>(executor, &authors, Book::author).await?;
+/// ```
+pub trait Related: Entity + Sized {
+    /// Belongs-to: loads the entity referenced by the foreign key `column` of `self`.
+    ///
+    /// Returns `Ok(None)` when the foreign key is NULL or the referenced row does not exist.
+    fn belongs_to<P: Entity + Send>(
+        &self,
+        executor: &mut impl Executor,
+        column: ColumnRef,
+    ) -> impl Future<Output = Result<Option<P>>> + Send {
+        let (foreign_key, referenced) = match foreign_key::<Self, P>(&column) {
+            Ok(v) => v,
+            Err(error) => return Either::Left(future::ready(Err(error))),
+        };
+        let value = column_value(self, foreign_key.name());
+        if value.is_null() {
+            return Either::Left(future::ready(Ok(None)));
+        }
+        let condition = BinaryOp {
+            op: BinaryOpType::Equal,
+            lhs: referenced,
+            rhs: value,
+        };
+        Either::Right(async move { P::find_one(executor, &condition).await })
+    }
+
+    /// Has-many: streams the entities of `C` whose foreign key `column` references `self`.
+    fn has_many<C: Entity + Send>(
+        &self,
+        executor: &mut impl Executor,
+        column: ColumnRef,
+    ) -> impl Stream<Item = Result<C>> + Send {
+        let (foreign_key, referenced) = match foreign_key::<C, Self>(&column) {
+            Ok(v) => v,
+            Err(error) => return Either::Left(stream::once(future::ready(Err(error)))),
+        };
+        let condition = BinaryOp {
+            op: BinaryOpType::Equal,
+            lhs: foreign_key.column_ref,
+            rhs: column_value(self, referenced.name),
+        };
+        let mut query = String::with_capacity(512);
+        executor.driver().sql_writer().write_select(
+            &mut query,
+            C::columns()
+                .iter()
+                .map(|c| &c.column_ref as &dyn Expression),
+            C::table(),
+            &condition,
+            None,
+        );
+        Either::Right(
+            executor
+                .fetch(query)
+                .map(|result| result.and_then(C::from_row)),
+        )
+    }
+
+    /// Many-to-many: loads the entities of `T` linked to `self` through the join entity `J`.
+    ///
+    /// `from` is the column of `J` referencing `Self`, `to` is the column of `J` referencing `T`.
+    fn many_to_many<J: Entity + Send, T: Entity + Send>(
+        &self,
+        executor: &mut impl Executor,
+        from: ColumnRef,
+        to: ColumnRef,
+    ) -> impl Future<Output = Result<Vec<T>>> + Send {
+        let relations = foreign_key::<J, Self>(&from)
+            .and_then(|(from, referenced)| Ok((from, referenced, foreign_key::<J, T>(&to)?)));
+        let value = relations
+            .as_ref()
+            .map(|(_, referenced, _)| column_value(self, referenced.name))
+            .unwrap_or_default();
+        async move {
+            let (from, _, (to, target)) = relations?;
+            let condition = BinaryOp {
+                op: BinaryOpType::Equal,
+                lhs: from.column_ref,
+                rhs: value,
+            };
+            let links = J::find_many(executor, &condition, None)
+                .try_collect::<Vec<_>>()
+                .await?;
+            let keys = distinct_values(links.iter().map(|v| column_value(v, to.name())));
+            find_in(executor, target, keys).await
+        }
+    }
+
+    /// Eager loads the has-many relation of every entity in `parents` using `IN` queries, split
+    /// in chunks of `Driver::MAX_INSERT_ROWS` keys.
+    ///
+    /// The result is aligned with `parents`: the i-th vector holds the children of the i-th parent.
+    fn load_has_many<C: Entity + Clone + Send>(
+        executor: &mut impl Executor,
+        parents: &[Self],
+        column: ColumnRef,
+    ) -> impl Future<Output = Result<Vec<Vec<C>>>> + Send {
+        let relation = foreign_key::<C, Self>(&column);
+        let keys = relation.as_ref().map_or(Vec::new(), |(_, referenced)| {
+            parents
+                .iter()
+                .map(|v| column_value(v, referenced.name))
+                .collect::<Vec<_>>()
+        });
+        async move {
+            let (foreign_key, _) = relation?;
+            let mut result = keys.iter().map(|_| Vec::new()).collect::<Vec<_>>();
+            let mut positions = HashMap::<_, Vec<_>>::with_capacity(keys.len());
+            for (i, key) in keys.iter().enumerate() {
+                if !key.is_null() {
+                    positions.entry(relation_key(key)).or_default().push(i);
+                }
+            }
+            let children = find_in::<C>(
+                executor,
+                foreign_key.column_ref,
+                distinct_values(keys.into_iter()),
+            )
+            .await?;
+            for child in children {
+                let Some(positions) =
+                    positions.get(&relation_key(&column_value(&child, foreign_key.name())))
+                else {
+                    continue;
+                };
+                if let Some((&last, rest)) = positions.split_last() {
+                    for &i in rest {
+                        result[i].push(child.clone());
+                    }
+                    result[last].push(child);
+                }
+            }
+            Ok(result)
+        }
+    }
+
+    /// Eager loads the belongs-to relation of every entity in `children` using `IN` queries, split
+    /// in chunks of `Driver::MAX_INSERT_ROWS` keys.
+    ///
+    /// The result is aligned with `children`, `None` where the foreign key is NULL or dangling.
+    fn load_belongs_to<P: Entity + Clone + Send>(
+        executor: &mut impl Executor,
+        children: &[Self],
+        column: ColumnRef,
+    ) -> impl Future<Output = Result<Vec<Option<P>>>> + Send {
+        let relation = foreign_key::<Self, P>(&column);
+        let keys = relation.as_ref().map_or(Vec::new(), |(foreign_key, _)| {
+            children
+                .iter()
+                .map(|v| column_value(v, foreign_key.name()))
+                .collect::<Vec<_>>()
+        });
+        async move {
+            let (_, referenced) = relation?;
+            let parents = find_in::<P>(executor, referenced, distinct_values(keys.iter().cloned()))
+                .await?
+                .into_iter()
+                .map(|v| (relation_key(&column_value(&v, referenced.name)), v))
+                .collect::<HashMap<_, _>>();
+            Ok(keys
+                .iter()
+                .map(|v| parents.get(&relation_key(v)).cloned())
+                .collect())
+        }
+    }
+}
+
+impl<E: Entity> Related for E {}
+
+/// Resolves the foreign key `column` declared on `C` and the column of `P` it references.
+fn foreign_key<C: Entity, P: Entity>(
+    column: &ColumnRef,
+) -> Result<(&'static ColumnDef, ColumnRef)> {
+    let table = C::table();
+    let Some(def) = C::columns().iter().find(|c| c.name() == column.name) else {
+        let error = Error::msg(format!(
+            "Column `{}` does not exist in table `{}`",
+            column.name,
+            table.full_name()
+        ));
+        log::error!("{:#}", error);
+        return Err(error);
+    };
+    let target = P::table();
+    match def.references {
+        Some(referenced)
+            if referenced.table == target.name && referenced.schema == target.schema =>
+        {
+            Ok((def, referenced))
+        }
+        _ => {
+            let error = Error::msg(format!(
+                "Column `{}` of table `{}` does not reference table `{}`",
+                column.name,
+                table.full_name(),
+                target.full_name()
+            ));
+            log::error!("{:#}", error);
+            Err(error)
+        }
+    }
+}
+
+/// Value of the column `name` of `entity`, NULL if the column does not exist.
+fn column_value<E: Entity>(entity: &E, name: &str) -> Value {
+    E::columns()
+        .iter()
+        .position(|c| c.name() == name)
+        .and_then(|i| entity.row_full().into_vec().into_iter().nth(i))
+        .unwrap_or_default()
+}
+
+/// Non null values without duplicates, in order of first appearance.
+fn distinct_values(values: impl Iterator<Item = Value>) -> Vec<Value> {
+    let mut seen = HashSet::new();
+    values
+        .filter(|v| !v.is_null() && seen.insert(v.clone()))
+        .collect()
+}
+
+/// Integer values widened to `Int128`, a foreign key declared `i32` matches a primary key declared `i64`.
+fn relation_key(value: &Value) -> Value {
+    let widened = match value {
+        Value::Int8(v) => v.map(i128::from),
+        Value::Int16(v) => v.map(i128::from),
+        Value::Int32(v) => v.map(i128::from),
+        Value::Int64(v) => v.map(i128::from),
+        Value::UInt8(v) => v.map(i128::from),
+        Value::UInt16(v) => v.map(i128::from),
+        Value::UInt32(v) => v.map(i128::from),
+        Value::UInt64(v) => v.map(i128::from),
+        _ => return value.clone(),
+    };
+    Value::Int128(widened)
+}
+
+/// `Driver::MAX_INSERT_ROWS` of the driver.
+fn max_rows<D: Driver>(_driver: &D) -> usize {
+    D::MAX_INSERT_ROWS.max(1)
+}
+
+/// Loads the entities whose `column` is one of `values`, one `IN` query per chunk of values.
+async fn find_in<E: Entity + Send>(
+    executor: &mut impl Executor,
+    column: ColumnRef,
+    values: Vec<Value>,
+) -> Result<Vec<E>> {
+    let mut result = Vec::new();
+    let chunk = max_rows(executor.driver());
+    for values in values.chunks(chunk) {
+        let condition = in_condition(column, values.to_vec());
+        let mut stream = pin!(E::find_many(executor, &condition, None));
+        while let Some(entity) = stream.try_next().await? {
+            result.push(entity);
+        }
+    }
+    Ok(result)
+}
+
+/// `column IN (values...)` condition.
+fn in_condition(column: ColumnRef, values: Vec<Value>) -> BinaryOp<ColumnRef, Value> {
+    let ty = values.first().map(Value::as_null).unwrap_or_default();
+    BinaryOp {
+        op: BinaryOpType::In,
+        lhs: column,
+        rhs: Value::List(Some(values), Box::new(ty)),
+    }
+}
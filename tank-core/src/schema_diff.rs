@@ -0,0 +1,301 @@
+use crate::{
+    ColumnDef, Context, Entity, Fragment, PrimaryKeyType, SqlWriter, TableRef, TableSchema, Value,
+    sql_type_to_value,
+};
+use std::mem;
+
+/// Single change bringing a live table in line with the entity declaring it.
+#[derive(Debug)]
+pub enum SchemaChange {
+    /// Column declared by the entity but missing from the table.
+    AddColumn(&'static ColumnDef),
+    /// Column found in the table but not declared by the entity.
+    DropColumn(String),
+    /// Column whose type differs from the declared one.
+    AlterColumnType(&'static ColumnDef),
+    /// Column declared `NOT NULL` but nullable in the table.
+    SetNotNull(&'static ColumnDef),
+    /// Column declared nullable but `NOT NULL` in the table.
+    DropNotNull(&'static ColumnDef),
+    /// Column whose declared default is missing or different in the table.
+    SetDefault(&'static ColumnDef),
+    /// Column having a default in the table but none declared.
+    DropDefault(&'static ColumnDef),
+    /// Unique constraint declared by the entity but missing from the table.
+    AddUnique(Vec<&'static str>),
+    /// Foreign key declared on the column but missing from the table.
+    AddForeignKey(&'static ColumnDef),
+    /// Unique or foreign key constraint found in the table but not declared by the entity.
+    DropConstraint(String),
+}
+
+impl SchemaChange {
+    /// Change that can lose data or relax the table guarantees: dropping columns or
+    /// constraints and converting column types.
+    pub fn is_destructive(&self) -> bool {
+        matches!(
+            self,
+            SchemaChange::DropColumn(..)
+                | SchemaChange::AlterColumnType(..)
+                | SchemaChange::DropConstraint(..)
+        )
+    }
+
+    /// Change that requires more than adding or dropping columns, see `Driver::ALTER_COLUMN`.
+    pub fn requires_alter_column(&self) -> bool {
+        !matches!(
+            self,
+            SchemaChange::AddColumn(..) | SchemaChange::DropColumn(..)
+        )
+    }
+}
+
+/// Migration plan: the changes needed to turn a live table (see `Executor::describe_table`) into
+/// the one declared by an entity.
+///
+/// Render it with `SqlWriter::write_schema_diff` to review the SQL before running it, the
+/// destructive changes are left out unless explicitly requested.
+///
+/// *Example:*
+/// ```ignore
+/// let diff = Operator::diff_table(executor).await?.expect("Table must exist");
+/// let mut sql = String::new();
+/// executor.driver().sql_writer().write_schema_diff(&mut sql, &diff, false);
+/// println!("{sql}");
+/// ```
+#[derive(Debug)]
+pub struct SchemaDiff {
+    /// Table being altered.
+    pub table: &'static TableRef,
+    /// Changes, in the order they must be applied.
+    pub changes: Vec<SchemaChange>,
+}
+
+impl SchemaDiff {
+    /// Compares the entity declaration with the live table.
+    ///
+    /// Types are compared through the `Value` they map to (see `sql_type_to_value`), the declared
+    /// one being rendered by `writer` so that `column_type` overrides are honored. Types the
+    /// database reports in a form that is not recognized are assumed to match. Defaults are
+    /// compared as text after removing the parentheses, casts and quotes the databases add.
+    ///
+    /// Primary keys and indexes are not compared.
+    pub fn compute<E: Entity>(writer: &impl SqlWriter, live: &TableSchema) -> Self {
+        let mut drop_constraints = Vec::new();
+        let mut columns = Vec::new();
+        let mut alter = Vec::new();
+        let mut add_constraints = Vec::new();
+        let mut drop_columns = Vec::new();
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        for column in E::columns() {
+            let Some(existing) = live.column(column.name()) else {
+                columns.push(SchemaChange::AddColumn(column));
+                if column.references.is_some() {
+                    add_constraints.push(SchemaChange::AddForeignKey(column));
+                }
+                continue;
+            };
+            let mut declared_type = String::new();
+            writer.write_column_def_type(&mut context, &mut declared_type, column);
+            if !same_type(&sql_type_to_value(&declared_type), &existing.value) {
+                alter.push(SchemaChange::AlterColumnType(column));
+            }
+            let primary_key = column.primary_key != PrimaryKeyType::None
+                || live.primary_key.iter().any(|v| v == column.name());
+            if primary_key || column.is_generated() {
+                continue;
+            }
+            if column.nullable != existing.nullable {
+                alter.push(if column.nullable {
+                    SchemaChange::DropNotNull(column)
+                } else {
+                    SchemaChange::SetNotNull(column)
+                });
+            }
+            if column.auto_increment {
+                continue;
+            }
+            let existing_default = existing
+                .default
+                .as_deref()
+                .map(normalize_default)
+                .filter(|v| v != "null");
+            match &column.default {
+                Some(default) => {
+                    let mut declared = String::new();
+                    default.write_query(writer.as_dyn(), &mut context, &mut declared);
+                    if existing_default != Some(normalize_default(&declared)) {
+                        alter.push(SchemaChange::SetDefault(column));
+                    }
+                }
+                None if existing_default.is_some() => {
+                    alter.push(SchemaChange::DropDefault(column));
+                }
+                None => {}
+            }
+        }
+        for existing in &live.columns {
+            if !E::columns().iter().any(|c| c.name() == existing.name) {
+                drop_columns.push(SchemaChange::DropColumn(existing.name.clone()));
+            }
+        }
+
+        // Unique constraints, the ones on new columns are part of the column definition
+        let mut declared_unique = E::columns()
+            .iter()
+            .filter(|c| c.unique && c.primary_key != PrimaryKeyType::PrimaryKey)
+            .map(|c| vec![c.name()])
+            .collect::<Vec<_>>();
+        declared_unique.extend(
+            E::unique_defs()
+                .filter(|v| v.len() > 1)
+                .map(|v| v.map(|c| c.name()).collect()),
+        );
+        for unique in &declared_unique {
+            if unique.len() == 1 && live.column(unique[0]).is_none() {
+                continue;
+            }
+            if !live.unique.iter().any(|v| same_columns(&v.columns, unique)) {
+                add_constraints.push(SchemaChange::AddUnique(unique.clone()));
+            }
+        }
+        for existing in &live.unique {
+            if !declared_unique
+                .iter()
+                .any(|v| same_columns(&existing.columns, v))
+            {
+                drop_constraints.push(SchemaChange::DropConstraint(existing.name.clone()));
+            }
+        }
+
+        // Foreign keys, compared by table name because the schema reported may be the default one
+        let declared_foreign_keys = E::columns()
+            .iter()
+            .filter_map(|c| c.references.map(|r| (c, r)))
+            .collect::<Vec<_>>();
+        for (column, references) in &declared_foreign_keys {
+            if live.column(column.name()).is_none() {
+                continue;
+            }
+            if !live.foreign_keys.iter().any(|v| {
+                v.columns == [column.name()]
+                    && v.references.name == references.table
+                    && v.references_columns == [references.name]
+            }) {
+                add_constraints.push(SchemaChange::AddForeignKey(column));
+            }
+        }
+        for existing in &live.foreign_keys {
+            if !declared_foreign_keys.iter().any(|(column, references)| {
+                existing.columns == [column.name()]
+                    && existing.references.name == references.table
+                    && existing.references_columns == [references.name]
+            }) {
+                drop_constraints.push(SchemaChange::DropConstraint(existing.name.clone()));
+            }
+        }
+
+        let mut changes = drop_constraints;
+        changes.extend(columns);
+        changes.extend(alter);
+        changes.extend(add_constraints);
+        changes.extend(drop_columns);
+        Self {
+            table: E::table(),
+            changes,
+        }
+    }
+
+    /// True when the table already matches the entity.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// True when some of the changes are destructive, see `SchemaChange::is_destructive`.
+    pub fn is_destructive(&self) -> bool {
+        self.changes.iter().any(SchemaChange::is_destructive)
+    }
+}
+
+fn same_type(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Unknown(..), _) | (_, Value::Unknown(..)) => true,
+        (Value::Decimal(_, a_width, a_scale), Value::Decimal(_, b_width, b_scale)) => {
+            *a_width == 0 || *b_width == 0 || (a_width == b_width && a_scale == b_scale)
+        }
+        (Value::List(_, a), Value::List(_, b))
+        | (Value::Array(_, a, ..), Value::Array(_, b, ..)) => same_type(a, b),
+        (Value::Map(_, a_key, a_value), Value::Map(_, b_key, b_value)) => {
+            same_type(a_key, b_key) && same_type(a_value, b_value)
+        }
+        _ => mem::discriminant(a) == mem::discriminant(b),
+    }
+}
+
+fn same_columns(a: &[String], b: &[&str]) -> bool {
+    a.len() == b.len() && a.iter().all(|v| b.contains(&v.as_str()))
+}
+
+fn normalize_default(value: &str) -> String {
+    let mut value = value.trim();
+    loop {
+        if let Some(inner) = strip_parentheses(value) {
+            value = inner.trim();
+        } else if let Some(inner) = strip_cast(value) {
+            value = inner;
+        } else if is_literal(value) {
+            value = &value[1..value.len() - 1];
+            break;
+        } else {
+            break;
+        }
+    }
+    match value.to_ascii_lowercase().as_str() {
+        // MySQL reports booleans as integers
+        "true" => "1".into(),
+        "false" => "0".into(),
+        v => v.into(),
+    }
+}
+
+/// `value` without the parentheses enclosing all of it, `(a) + (b)` is left as it is.
+fn strip_parentheses(value: &str) -> Option<&str> {
+    let inner = value.strip_prefix('(')?.strip_suffix(')')?;
+    let mut depth = 0_usize;
+    let mut quoted = false;
+    for c in inner.chars() {
+        match c {
+            '\'' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted => depth = depth.checked_sub(1)?,
+            _ => {}
+        }
+    }
+    (depth == 0 && !quoted).then_some(inner)
+}
+
+/// `value` is a single string literal, quotes inside are doubled.
+fn is_literal(value: &str) -> bool {
+    value.len() >= 2
+        && value.starts_with('\'')
+        && value.ends_with('\'')
+        && !value[1..value.len() - 1].replace("\'\'", "").contains('\'')
+}
+
+/// `value` without the trailing Postgres cast (`'a'::text`, `(1)::bigint`, `NULL::integer`).
+///
+/// The cast follows a whole operand, MySQL reports the string defaults without quotes and `a::b`
+/// must be left as it is.
+fn strip_cast(value: &str) -> Option<&str> {
+    let (inner, cast) = value.rsplit_once("::")?;
+    let inner = inner.trim_end();
+    let is_type = !cast.trim().is_empty()
+        && cast
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || " _,.()[]".contains(c));
+    let is_operand = is_literal(inner)
+        || strip_parentheses(inner).is_some()
+        || inner.eq_ignore_ascii_case("null")
+        || inner.parse::<f64>().is_ok();
+    (is_type && is_operand).then_some(inner)
+}
@@ -0,0 +1,94 @@
+use crate::{
+    Entity, SqlWriter, TableName,
+    entity_registry::{EntityNode, creation_order},
+};
+use std::collections::BTreeSet;
+
+/// Renders the DDL creating a set of entities without a connection, to be reviewed and run by hand.
+///
+/// The output contains the CREATE SCHEMA, CREATE TABLE and comment statements of the writer, the
+/// tables referenced by a foreign key come first (cycles are kept in registration order), views
+/// follow every table and each schema is created once, before its first table.
+///
+/// *Example:*
+/// ```ignore
+/// let sql = SchemaExport::new(PostgresDriver::new().sql_writer())
+///     .entity::<Soldier>()
+///     .entity::<Unit>()
+///     .to_sql();
+/// std::fs::write("schema.sql", sql)?;
+/// ```
+pub struct SchemaExport<W> {
+    writer: W,
+    if_not_exists: bool,
+    entities: Vec<ExportedEntity<W>>,
+}
+
+struct ExportedEntity<W> {
+    node: EntityNode,
+    write_create_schema: fn(&W, &mut String, bool),
+    write_create_table: fn(&W, &mut String, bool),
+}
+
+impl<W: SqlWriter> SchemaExport<W> {
+    /// Export without entities, rendering the statements with `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            if_not_exists: false,
+            entities: Vec::new(),
+        }
+    }
+
+    /// Adds the entity `E`, adding the same table twice has no effect.
+    pub fn entity<E: Entity>(mut self) -> Self {
+        let node = EntityNode::new::<E>();
+        if self.entities.iter().any(|v| v.node.table == node.table) {
+            return self;
+        }
+        self.entities.push(ExportedEntity {
+            node,
+            write_create_schema: W::write_create_schema::<E>,
+            write_create_table: if E::is_view() {
+                W::write_create_view::<E>
+            } else {
+                W::write_create_table::<E>
+            },
+        });
+        self
+    }
+
+    /// Guards the CREATE statements with IF NOT EXISTS (where the driver supports it).
+    pub fn if_not_exists(mut self, if_not_exists: bool) -> Self {
+        self.if_not_exists = if_not_exists;
+        self
+    }
+
+    /// Tables of the entities added, in creation order.
+    pub fn tables(&self) -> Vec<&TableName> {
+        self.ordered().into_iter().map(|v| &v.node.table).collect()
+    }
+
+    /// Writes the DDL to `out`.
+    pub fn write(&self, out: &mut String) {
+        let mut schemas = BTreeSet::new();
+        for entity in self.ordered() {
+            let schema = &entity.node.table.schema;
+            if !schema.is_empty() && schemas.insert(schema) {
+                (entity.write_create_schema)(&self.writer, out, self.if_not_exists);
+            }
+            (entity.write_create_table)(&self.writer, out, self.if_not_exists);
+        }
+    }
+
+    /// Returns the DDL.
+    pub fn to_sql(&self) -> String {
+        let mut out = String::with_capacity(self.entities.len() * 512);
+        self.write(&mut out);
+        out
+    }
+
+    fn ordered(&self) -> Vec<&ExportedEntity<W>> {
+        creation_order(&self.entities, |v| &v.node).0
+    }
+}
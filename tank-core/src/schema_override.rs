@@ -0,0 +1,742 @@
+use crate::{
+    Action, AsQuery, BinaryOp, BinaryOpType, ColumnDef, ColumnRef, ColumnSchema, Connection,
+    DataSet, Driver, Entity, Error, Executor, Expression, Interval, IsolationLevel, Join, JoinType,
+    OnConflict, Operand, Ordered, Query, QueryResult, Result, RowsAffected, SchemaChange,
+    SchemaDiff, TableName, TableRef, TableSchema, Transaction, TransactionOptions, UnaryOp,
+    UnaryOpType, Value,
+    future::Either,
+    future::TryFutureExt,
+    stream::{Stream, StreamExt},
+    table_ref::is_schema_override_disabled,
+    with_schema, with_schema_sync,
+    writer::{Context, SqlWriter},
+};
+use async_stream::try_stream;
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    mem,
+    pin::pin,
+    time::Duration,
+};
+use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
+
+/// Executor rendering the tables declared without a schema in `schema`, see
+/// [`Executor::with_schema`].
+///
+/// Unlike the `with_schema` function, the override is part of the driver: the SQL built eagerly
+/// when an operation is called (`insert_one`, `delete_one`, `delete_many`, ...) renders the schema
+/// too, so the operation futures can be awaited directly.
+pub struct WithSchema<'e, E: Executor>
+where
+    E::Driver: Clone + Send,
+{
+    executor: &'e mut E,
+    driver: SchemaDriver<E::Driver>,
+}
+
+impl<'e, E: Executor> WithSchema<'e, E>
+where
+    E::Driver: Clone + Send,
+{
+    pub fn new(executor: &'e mut E, schema: impl Into<Cow<'static, str>>) -> Self {
+        let driver = SchemaDriver {
+            driver: executor.driver().clone(),
+            schema: schema.into(),
+        };
+        Self { executor, driver }
+    }
+
+    /// Schema of the tables declared without one.
+    pub fn schema(&self) -> &str {
+        &self.driver.schema
+    }
+}
+
+impl<'e, E: Executor> Executor for WithSchema<'e, E>
+where
+    E::Driver: Clone + Send,
+{
+    type Driver = SchemaDriver<E::Driver>;
+
+    fn driver(&self) -> &Self::Driver {
+        &self.driver
+    }
+
+    fn in_transaction(&self) -> bool {
+        self.executor.in_transaction()
+    }
+
+    fn prepare(
+        &mut self,
+        query: String,
+    ) -> impl Future<Output = Result<Query<Self::Driver>>> + Send {
+        SchemaDriver::prepare(&mut *self.executor, query)
+    }
+
+    fn run<'s>(
+        &'s mut self,
+        query: impl AsQuery<Self::Driver> + 's,
+    ) -> impl Stream<Item = Result<QueryResult>> + Send {
+        SchemaDriver::run(&mut *self.executor, query)
+    }
+
+    fn append<'a, T, It>(
+        &mut self,
+        entities: It,
+    ) -> impl Future<Output = Result<RowsAffected>> + Send
+    where
+        T: Entity + 'a,
+        It: IntoIterator<Item = &'a T> + Send,
+    {
+        SchemaDriver::append(self.driver.schema.clone(), &mut *self.executor, entities)
+    }
+}
+
+/// Transactions begun through a `WithSchema` keep the override.
+impl<'e, C: Connection> Connection for WithSchema<'e, C>
+where
+    C::Driver: Clone + Send,
+{
+    /// Connects without an override, call `Executor::with_schema` on the connection.
+    fn connect(
+        url: Cow<'static, str>,
+    ) -> impl Future<Output = Result<<Self::Driver as Driver>::Connection>> {
+        C::connect(url)
+    }
+
+    fn begin(&mut self) -> impl Future<Output = Result<impl Transaction<'_>>> {
+        self.begin_driver_transaction(TransactionOptions::default())
+    }
+
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> impl Future<Output = Result<impl Transaction<'_>>> {
+        self.begin_driver_transaction(options)
+    }
+
+    fn begin_driver_transaction(
+        &mut self,
+        options: TransactionOptions,
+    ) -> impl Future<Output = Result<<Self::Driver as Driver>::Transaction<'_>>> {
+        let driver = self.driver.clone();
+        self.executor
+            .begin_driver_transaction(options)
+            .map_ok(move |transaction| SchemaTransaction {
+                transaction,
+                driver,
+            })
+    }
+}
+
+/// Transaction of [`SchemaDriver`], begun from a [`WithSchema`] over a connection.
+pub struct SchemaTransaction<'c, D: Driver + Clone + Send> {
+    transaction: D::Transaction<'c>,
+    driver: SchemaDriver<D>,
+}
+
+impl<'c, D: Driver + Clone + Send> Executor for SchemaTransaction<'c, D> {
+    type Driver = SchemaDriver<D>;
+
+    fn driver(&self) -> &Self::Driver {
+        &self.driver
+    }
+
+    fn in_transaction(&self) -> bool {
+        self.transaction.in_transaction()
+    }
+
+    fn prepare(
+        &mut self,
+        query: String,
+    ) -> impl Future<Output = Result<Query<Self::Driver>>> + Send {
+        SchemaDriver::prepare(&mut self.transaction, query)
+    }
+
+    fn run<'s>(
+        &'s mut self,
+        query: impl AsQuery<Self::Driver> + 's,
+    ) -> impl Stream<Item = Result<QueryResult>> + Send {
+        SchemaDriver::run(&mut self.transaction, query)
+    }
+
+    fn append<'a, T, It>(
+        &mut self,
+        entities: It,
+    ) -> impl Future<Output = Result<RowsAffected>> + Send
+    where
+        T: Entity + 'a,
+        It: IntoIterator<Item = &'a T> + Send,
+    {
+        SchemaDriver::append(self.driver.schema.clone(), &mut self.transaction, entities)
+    }
+}
+
+impl<'c, D: Driver + Clone + Send> Transaction<'c> for SchemaTransaction<'c, D> {
+    fn commit(self) -> impl Future<Output = Result<()>> {
+        self.transaction.commit()
+    }
+
+    fn rollback(self) -> impl Future<Output = Result<()>> {
+        self.transaction.rollback()
+    }
+}
+
+/// Driver of [`WithSchema`], it forwards to `driver` with a `SqlWriter` rendering the tables
+/// declared without a schema in `schema`.
+#[derive(Debug, Clone)]
+pub struct SchemaDriver<D: Driver + Clone + Send> {
+    driver: D,
+    schema: Cow<'static, str>,
+}
+
+impl<D: Driver + Clone + Send> SchemaDriver<D> {
+    fn wrap_query(query: Query<D>) -> Query<Self> {
+        match query {
+            Query::Raw(v) => Query::Raw(v),
+            Query::Prepared(v) => Query::Prepared(v),
+        }
+    }
+
+    fn unwrap_query(query: Query<Self>) -> Query<D> {
+        match query {
+            Query::Raw(v) => Query::Raw(v),
+            Query::Prepared(v) => Query::Prepared(v),
+        }
+    }
+
+    fn prepare<E: Executor<Driver = D>>(
+        executor: &mut E,
+        query: String,
+    ) -> impl Future<Output = Result<Query<Self>>> + Send {
+        executor.prepare(query).map_ok(Self::wrap_query)
+    }
+
+    fn run<'s, E: Executor<Driver = D>>(
+        executor: &'s mut E,
+        query: impl AsQuery<Self> + 's,
+    ) -> impl Stream<Item = Result<QueryResult>> + Send {
+        let mut query = query.as_query();
+        try_stream! {
+            let query = query.as_mut();
+            let mut inner = Self::unwrap_query(mem::replace(query, Query::Raw(String::new())));
+            let result = {
+                let mut stream = pin!(executor.run(&mut inner));
+                loop {
+                    match stream.next().await {
+                        Some(Ok(v)) => yield v,
+                        Some(Err(e)) => break Err(e),
+                        None => break Ok(()),
+                    }
+                }
+            };
+            // Give the prepared statement back to the caller, also when it failed
+            *query = Self::wrap_query(inner);
+            result?;
+        }
+    }
+
+    fn append<'a, E, T, It>(
+        schema: Cow<'static, str>,
+        executor: &mut E,
+        entities: It,
+    ) -> impl Future<Output = Result<RowsAffected>> + Send
+    where
+        E: Executor<Driver = D>,
+        T: Entity + 'a,
+        It: IntoIterator<Item = &'a T> + Send,
+    {
+        // Drivers build the statements either when called or when polled
+        let future = with_schema_sync(schema.clone(), move || executor.append(entities));
+        with_schema(schema, future)
+    }
+}
+
+impl<D: Driver + Clone + Send> Driver for SchemaDriver<D> {
+    type Connection = D::Connection;
+    type SqlWriter = SchemaSqlWriter<D::SqlWriter>;
+    type Prepared = D::Prepared;
+    type Transaction<'c> = SchemaTransaction<'c, D>;
+
+    const NAME: &'static str = D::NAME;
+    const MAX_INSERT_ROWS: usize = D::MAX_INSERT_ROWS;
+    const MAX_INSERT_LENGTH: usize = D::MAX_INSERT_LENGTH;
+    const TRANSACTIONAL_DDL: bool = D::TRANSACTIONAL_DDL;
+    const ALTER_COLUMN: bool = D::ALTER_COLUMN;
+    const SAVEPOINTS: bool = D::SAVEPOINTS;
+
+    fn connect(&self, url: Cow<'static, str>) -> impl Future<Output = Result<impl Connection>> {
+        self.driver.connect(url)
+    }
+
+    fn sql_writer(&self) -> Self::SqlWriter {
+        SchemaSqlWriter {
+            writer: self.driver.sql_writer(),
+            schema: self.schema.clone(),
+        }
+    }
+
+    fn is_retryable(&self, error: &Error) -> bool {
+        self.driver.is_retryable(error)
+    }
+
+    fn sleep(duration: Duration) -> impl Future<Output = ()> + Send {
+        D::sleep(duration)
+    }
+}
+
+/// `SqlWriter` of [`SchemaDriver`], every method renders through `writer` inside a
+/// `with_schema` scope, except inside `without_schema_override`.
+pub struct SchemaSqlWriter<W: SqlWriter> {
+    writer: W,
+    schema: Cow<'static, str>,
+}
+
+impl<W: SqlWriter> SchemaSqlWriter<W> {
+    fn scoped<R>(&self, f: impl FnOnce() -> R) -> R {
+        if is_schema_override_disabled() {
+            return f();
+        }
+        with_schema_sync(self.schema.clone(), f)
+    }
+}
+
+/// Implements the listed `SqlWriter` methods by forwarding to `self.writer` inside the schema
+/// scope. Lifetimes go in brackets and the type parameters in angle brackets, the latter are
+/// also passed explicitly to the forwarded call.
+macro_rules! forward_sql_writer {
+    ($(
+        fn $name:ident $([$($lt:lifetime),+])? $(<$($ty:ident),+>)? (
+            &self $(, $arg:ident: $arg_ty:ty)* $(,)?
+        ) $(-> $ret:ty)? $(where [$($bound:tt)*])?;
+    )*) => {
+        $(
+            fn $name<$($($lt,)+)? $($($ty),+)?>(&self $(, $arg: $arg_ty)*) $(-> $ret)?
+            $(where $($bound)*)?
+            {
+                self.scoped(|| self.writer.$name$(::<$($ty),+>)?($($arg),*))
+            }
+        )*
+    };
+}
+
+impl<W: SqlWriter> SqlWriter for SchemaSqlWriter<W> {
+    fn as_dyn(&self) -> &dyn SqlWriter {
+        self
+    }
+
+    fn value_interval_units(&self) -> &[(&str, i128)] {
+        self.writer.value_interval_units()
+    }
+
+    forward_sql_writer! {
+        fn alias_declaration(&self, context: &mut Context) -> bool;
+        fn write_escaped(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+            value: &str,
+            search: char,
+            replace: &str,
+        );
+        fn write_identifier_quoted(&self, context: &mut Context, out: &mut String, value: &str);
+        fn write_table_ref(&self, context: &mut Context, out: &mut String, value: &TableRef);
+        fn write_column_ref(&self, context: &mut Context, out: &mut String, value: &ColumnRef);
+        fn write_column_overridden_type(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+            column: &ColumnDef,
+            types: &BTreeMap<&'static str, &'static str>,
+        );
+        fn write_column_type(&self, context: &mut Context, out: &mut String, value: &Value);
+        fn write_value(&self, context: &mut Context, out: &mut String, value: &Value);
+        fn write_value_none(&self, context: &mut Context, out: &mut String);
+        fn write_value_bool(&self, context: &mut Context, out: &mut String, value: bool);
+        fn write_value_infinity(&self, context: &mut Context, out: &mut String, negative: bool);
+        fn write_value_nan(&self, context: &mut Context, out: &mut String);
+        fn write_value_string(&self, context: &mut Context, out: &mut String, value: &str);
+        fn write_value_blob(&self, context: &mut Context, out: &mut String, value: &[u8]);
+        fn write_value_date(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+            value: &Date,
+            timestamp: bool,
+        );
+        fn write_value_time(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+            value: &Time,
+            timestamp: bool,
+        );
+        fn write_value_timestamp(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+            value: &PrimitiveDateTime,
+        );
+        fn write_value_timestamptz(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+            value: &OffsetDateTime,
+        );
+        fn write_value_interval(&self, context: &mut Context, out: &mut String, value: &Interval);
+        fn write_value_list(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+            value: Either<&Box<[Value]>, &Vec<Value>>,
+            ty: &Value,
+        );
+        fn write_value_in_list(&self, context: &mut Context, out: &mut String, value: &[Value]);
+        fn write_value_map(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+            value: &HashMap<Value, Value>,
+        );
+        fn write_value_struct(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+            value: &Vec<(String, Value)>,
+        );
+        fn expression_unary_op_precedence(&self, value: &UnaryOpType) -> i32;
+        fn expression_binary_op_precedence(&self, value: &BinaryOpType) -> i32;
+        fn write_expression_operand(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+            value: &Operand,
+        );
+        fn write_expression_operand_question_mark(&self, context: &mut Context, out: &mut String);
+        fn write_expression_unary_op(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+            value: &UnaryOp<&dyn Expression>,
+        );
+        fn write_expression_binary_op(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+            value: &BinaryOp<&dyn Expression, &dyn Expression>,
+        );
+        fn write_expression_ordered(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+            value: &Ordered<&dyn Expression>,
+        );
+        fn write_join_type(&self, context: &mut Context, out: &mut String, join_type: &JoinType);
+        fn write_join(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+            join: &Join<&dyn DataSet, &dyn DataSet, &dyn Expression>,
+        );
+        fn write_transaction_begin(&self, out: &mut String);
+        fn write_transaction_begin_with(&self, out: &mut String, options: &TransactionOptions);
+        fn write_transaction_isolation_level(&self, out: &mut String, isolation: IsolationLevel);
+        fn write_transaction_commit(&self, out: &mut String);
+        fn write_transaction_rollback(&self, out: &mut String);
+        fn write_transaction_savepoint(&self, out: &mut String, name: &str);
+        fn write_transaction_release_savepoint(&self, out: &mut String, name: &str);
+        fn write_transaction_rollback_to_savepoint(&self, out: &mut String, name: &str);
+        fn write_create_schema<E>(
+            &self,
+            out: &mut String,
+            if_not_exists: bool,
+        ) where [Self: Sized, E: Entity];
+        fn write_drop_schema<E>(
+            &self,
+            out: &mut String,
+            if_exists: bool,
+        ) where [Self: Sized, E: Entity];
+        fn write_create_table<E>(
+            &self,
+            out: &mut String,
+            if_not_exists: bool,
+        ) where [Self: Sized, E: Entity];
+        fn write_create_table_body<E>(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+        ) where [Self: Sized, E: Entity];
+        fn write_create_table_column_fragment(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+            column: &ColumnDef,
+        ) where [Self: Sized];
+        fn write_column_def_type(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+            column: &ColumnDef,
+        ) where [Self: Sized];
+        fn write_column_check(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+            column: &ColumnDef,
+        ) where [Self: Sized];
+        fn write_column_auto_increment(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+            column: &ColumnDef,
+        ) where [Self: Sized];
+        fn write_column_generated(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+            column: &ColumnDef,
+        ) where [Self: Sized];
+        fn write_create_table_foreign_key_fragment(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+            column: &ColumnDef,
+        ) where [Self: Sized];
+        fn write_create_table_references_action(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+            action: &Action,
+        );
+        fn write_column_comment_inline(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+            column: &ColumnDef,
+        ) where [Self: Sized];
+        fn write_column_comments_statements<E>(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+        ) where [Self: Sized, E: Entity];
+        fn write_drop_table<E>(
+            &self,
+            out: &mut String,
+            if_exists: bool,
+        ) where [Self: Sized, E: Entity];
+        fn write_create_view<E>(
+            &self,
+            out: &mut String,
+            if_not_exists: bool,
+        ) where [Self: Sized, E: Entity];
+        fn write_drop_view<E>(
+            &self,
+            out: &mut String,
+            if_exists: bool,
+        ) where [Self: Sized, E: Entity];
+        fn write_create_sequences<E>(
+            &self,
+            out: &mut String,
+            if_not_exists: bool,
+        ) where [Self: Sized, E: Entity];
+        fn write_drop_sequences<E>(
+            &self,
+            out: &mut String,
+            if_exists: bool,
+        ) where [Self: Sized, E: Entity];
+        fn write_table_name(&self, context: &mut Context, out: &mut String, value: &TableName);
+        fn write_create_table_schema(
+            &self,
+            out: &mut String,
+            table: &TableSchema,
+            if_not_exists: bool,
+        ) where [Self: Sized];
+        fn write_column_schema_type(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+            column: &ColumnSchema,
+        );
+        fn write_schema_diff(
+            &self,
+            out: &mut String,
+            diff: &SchemaDiff,
+            destructive: bool,
+        ) where [Self: Sized];
+        fn write_alter_table_prefix(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+            table: &TableRef,
+        );
+        fn write_schema_change(
+            &self,
+            out: &mut String,
+            table: &TableRef,
+            change: &SchemaChange,
+        ) where [Self: Sized];
+        fn write_alter_table_add_column(
+            &self,
+            out: &mut String,
+            table: &TableRef,
+            column: &ColumnDef,
+        ) where [Self: Sized];
+        fn write_alter_table_drop_column(
+            &self,
+            out: &mut String,
+            table: &TableRef,
+            name: &str,
+        ) where [Self: Sized];
+        fn write_alter_table_alter_type(
+            &self,
+            out: &mut String,
+            table: &TableRef,
+            column: &ColumnDef,
+        ) where [Self: Sized];
+        fn write_alter_table_set_not_null(
+            &self,
+            out: &mut String,
+            table: &TableRef,
+            column: &ColumnDef,
+        ) where [Self: Sized];
+        fn write_alter_table_drop_not_null(
+            &self,
+            out: &mut String,
+            table: &TableRef,
+            column: &ColumnDef,
+        ) where [Self: Sized];
+        fn write_alter_table_set_default(
+            &self,
+            out: &mut String,
+            table: &TableRef,
+            column: &ColumnDef,
+        ) where [Self: Sized];
+        fn write_alter_table_drop_default(
+            &self,
+            out: &mut String,
+            table: &TableRef,
+            column: &ColumnDef,
+        ) where [Self: Sized];
+        fn write_alter_table_add_unique(
+            &self,
+            out: &mut String,
+            table: &TableRef,
+            columns: &[&str],
+        ) where [Self: Sized];
+        fn write_alter_table_add_foreign_key(
+            &self,
+            out: &mut String,
+            table: &TableRef,
+            column: &ColumnDef,
+        ) where [Self: Sized];
+        fn write_alter_table_drop_constraint(
+            &self,
+            out: &mut String,
+            table: &TableRef,
+            name: &str,
+        ) where [Self: Sized];
+        fn write_alter_table_rename_column(
+            &self,
+            out: &mut String,
+            table: &TableRef,
+            name: &str,
+            new_name: &str,
+        ) where [Self: Sized];
+        fn write_alter_table_rename_table(
+            &self,
+            out: &mut String,
+            table: &TableName,
+            new_name: &str,
+        ) where [Self: Sized];
+        fn write_alter_table_rebuild<E>(
+            &self,
+            out: &mut String,
+            live: &TableSchema,
+        ) where [Self: Sized, E: Entity];
+        fn write_foreign_keys_enforced(&self, out: &mut String);
+        fn write_set_foreign_keys_enforced(&self, out: &mut String, enforced: bool);
+        fn write_foreign_key_check(&self, out: &mut String);
+        fn write_select<Item, Cols, Data, Cond>(
+            &self,
+            out: &mut String,
+            columns: Cols,
+            from: &Data,
+            condition: &Cond,
+            limit: Option<u32>,
+        ) where [
+            Self: Sized,
+            Item: Expression,
+            Cols: IntoIterator<Item = Item> + Clone,
+            Data: DataSet,
+            Cond: Expression,
+        ];
+        fn write_count<E>(
+            &self,
+            out: &mut String,
+            condition: &impl Expression,
+        ) where [Self: Sized, E: Entity];
+        fn write_exists<E>(
+            &self,
+            out: &mut String,
+            condition: &impl Expression,
+        ) where [Self: Sized, E: Entity];
+        fn write_insert['b]<E>(
+            &self,
+            out: &mut String,
+            entities: impl IntoIterator<Item = &'b E>,
+            update: bool,
+        ) where [Self: Sized, E: Entity + 'b];
+        fn write_insert_on_conflict['b]<E>(
+            &self,
+            out: &mut String,
+            entities: impl IntoIterator<Item = &'b E>,
+            on_conflict: Option<&OnConflict>,
+        ) where [Self: Sized, E: Entity + 'b];
+        fn write_insert_chunks['b]<E>(
+            &self,
+            out: &mut Vec<String>,
+            entities: &[&'b E],
+            on_conflict: Option<&OnConflict>,
+            max_rows: usize,
+            max_length: usize,
+        ) where [Self: Sized, E: Entity + 'b];
+        fn write_insert_returning<E>(
+            &self,
+            out: &mut String,
+            entity: &E,
+            returning: &ColumnDef,
+        ) where [Self: Sized, E: Entity];
+        fn write_insert_update_fragment['a]<E>(
+            &self,
+            context: &mut Context,
+            out: &mut String,
+            columns: impl Iterator<Item = &'a ColumnDef>,
+            on_conflict: &OnConflict,
+        ) where [Self: Sized, E: Entity];
+        fn write_update['a]<E>(
+            &self,
+            out: &mut String,
+            values: impl IntoIterator<Item = (&'a ColumnDef, &'a Value)>,
+            condition: &impl Expression,
+        ) where [Self: Sized, E: Entity];
+        fn write_delete<E>(
+            &self,
+            out: &mut String,
+            condition: &impl Expression,
+        ) where [Self: Sized, E: Entity];
+        fn write_current_schema(&self, out: &mut String);
+        fn write_catalog_table_condition(
+            &self,
+            out: &mut String,
+            schema_column: &str,
+            name_column: &str,
+            table: &TableName,
+        );
+        fn write_select_tables(&self, out: &mut String, schema: Option<&str>);
+        fn write_select_table_columns(&self, out: &mut String, table: &TableName);
+        fn write_select_table_constraints(&self, out: &mut String, table: &TableName);
+        fn write_select_table_indexes(&self, out: &mut String, table: &TableName);
+    }
+}
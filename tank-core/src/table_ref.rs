@@ -4,7 +4,94 @@ use crate::{
 };
 use proc_macro2::TokenStream;
 use quote::{ToTokens, TokenStreamExt, quote};
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    future::{self, Future},
+    pin::pin,
+};
+
+thread_local! {
+    /// Innermost schema scope of the thread: `None` outside of any scope, `Some(None)` inside
+    /// `without_schema_override`. Futures set it while they are polled.
+    static SCHEMA: RefCell<Option<Option<Cow<'static, str>>>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` with `schema` as the innermost scope, then takes it back in `schema` (the previous
+/// scope is restored also when `f` panics).
+fn scope<R>(schema: &mut Option<Cow<'static, str>>, f: impl FnOnce() -> R) -> R {
+    struct Restore<'a> {
+        schema: &'a mut Option<Cow<'static, str>>,
+        previous: Option<Option<Cow<'static, str>>>,
+    }
+    impl Drop for Restore<'_> {
+        fn drop(&mut self) {
+            *self.schema = SCHEMA.with(|v| v.replace(self.previous.take())).flatten();
+        }
+    }
+    let previous = SCHEMA.with(|v| v.replace(Some(schema.take())));
+    let _restore = Restore { schema, previous };
+    f()
+}
+
+/// Runs `future` rendering the table and column references declared without a schema in
+/// `schema`.
+///
+/// Meant for multi-tenant deployments where each tenant has its own schema with the same tables:
+/// the tenant entities leave the schema out, while the tables declared with a schema are shared by
+/// every tenant and keep it (foreign keys and joins included).
+///
+/// The override covers only the SQL built while the future is polled. Operations building their
+/// SQL when called (`insert_one`, `delete_one`, `delete_many`) must be called inside an `async`
+/// block, passing their future directly renders the default schema. Prefer
+/// `Executor::with_schema`, which covers every operation.
+///
+/// *Example:*
+/// ```ignore
+/// let orders = tank::with_schema("tenant_42", async {
+///     Order::delete_many(executor, &expr!(Order::closed == true)).await?;
+///     Order::find_many(executor, &true, None).try_collect::<Vec<_>>().await
+/// })
+/// .await?;
+/// ```
+pub fn with_schema<F: Future>(
+    schema: impl Into<Cow<'static, str>>,
+    future: F,
+) -> impl Future<Output = F::Output> {
+    let mut schema = Some(schema.into());
+    async move {
+        let mut future = pin!(future);
+        future::poll_fn(|cx| scope(&mut schema, || future.as_mut().poll(cx))).await
+    }
+}
+
+/// Synchronous version of `with_schema`, useful to render queries with a `SqlWriter` directly.
+pub fn with_schema_sync<R>(schema: impl Into<Cow<'static, str>>, f: impl FnOnce() -> R) -> R {
+    scope(&mut Some(schema.into()), f)
+}
+
+/// Runs `f` ignoring the enclosing `with_schema` scope, for the tables owned by the database
+/// rather than by a tenant. The `SqlWriter` of `Executor::with_schema` does not override it.
+pub(crate) fn without_schema_override<R>(f: impl FnOnce() -> R) -> R {
+    scope(&mut None, f)
+}
+
+/// True inside `without_schema_override`.
+pub(crate) fn is_schema_override_disabled() -> bool {
+    SCHEMA.with(|v| matches!(*v.borrow(), Some(None)))
+}
+
+/// Schema of a table declared in `schema`: the one set by the innermost `with_schema` scope when
+/// `schema` is empty.
+pub fn resolve_schema(schema: &'static str) -> Cow<'static, str> {
+    if !schema.is_empty() {
+        return Cow::Borrowed(schema);
+    }
+    SCHEMA
+        .with(|v| v.borrow().clone())
+        .flatten()
+        .unwrap_or(Cow::Borrowed(schema))
+}
 
 /// Reference to a table (schema-qualified + optional alias).
 #[derive(Default, Clone, PartialEq, Eq, Debug)]
@@ -18,13 +105,18 @@ pub struct TableRef {
 }
 
 impl TableRef {
+    /// Schema used when rendering, honoring the `with_schema` override.
+    pub fn effective_schema(&self) -> Cow<'static, str> {
+        resolve_schema(self.schema)
+    }
     pub fn full_name(&self) -> String {
         let mut result = String::new();
         if !self.alias.is_empty() {
             result.push_str(&self.alias);
         } else {
-            if !self.schema.is_empty() {
-                result.push_str(&self.schema);
+            let schema = self.effective_schema();
+            if !schema.is_empty() {
+                result.push_str(&schema);
                 result.push('.');
             }
             result.push_str(&self.name);
@@ -0,0 +1,187 @@
+use crate::{
+    BinaryOp, BinaryOpType, ColumnDef, ColumnRef, Driver, Entity, Error, Executor, Expression,
+    OpPrecedence, Result, Row, RowsAffected, Value,
+    future::Either,
+    separated_by,
+    writer::{Context, SqlWriter},
+};
+use std::{
+    collections::HashSet,
+    future::{self, Future},
+    ops::{Deref, DerefMut},
+};
+
+/// Entity wrapper that remembers the values last synchronized with the database.
+///
+/// `update` compares the current state with that snapshot and writes only the columns that
+/// changed, identifying the row by the primary key values of the snapshot.
+///
+/// *Example:*
+/// ```ignore
+/// let mut operator = Tracked::new(Operator::find_pk(executor, &(&id,)).await?.unwrap());
+/// operator.callsign = "Ghost".into();
+/// operator.update(executor).await?; // UPDATE ... SET "callsign" = 'Ghost' WHERE "id" = ...
+/// operator.update(executor).await?; // No query sent, nothing changed
+/// ```
+#[derive(Debug)]
+pub struct Tracked<T: Entity> {
+    entity: T,
+    snapshot: Row,
+}
+
+impl<T: Entity> Tracked<T> {
+    /// Starts tracking `entity`, assuming it matches the row currently stored in the database.
+    pub fn new(entity: T) -> Self {
+        Self {
+            snapshot: entity.row_full(),
+            entity,
+        }
+    }
+
+    /// Stops tracking and returns the entity.
+    pub fn into_inner(self) -> T {
+        self.entity
+    }
+
+    /// Columns whose value differs from the snapshot, in declaration order.
+    ///
+    /// Passive fields that are not set are never considered changed.
+    pub fn changed_columns(&self) -> Vec<&'static ColumnDef> {
+        let row = self.entity.row_full();
+        self.changed(&row)
+            .into_iter()
+            .map(|i| &T::columns()[i])
+            .collect()
+    }
+
+    /// Whether any column differs from the snapshot.
+    pub fn is_dirty(&self) -> bool {
+        !self.changed(&self.entity.row_full()).is_empty()
+    }
+
+    /// Writes the changed columns to the database.
+    ///
+    /// Completes without sending any query when nothing changed. After a successful update the
+    /// snapshot matches the current state. Validates the entity and invokes the `before_save` hook.
+    ///
+    /// Errors:
+    /// - Missing PK in the table.
+    /// - Execution failures from underlying driver.
+    pub fn update(
+        &mut self,
+        executor: &mut impl Executor,
+    ) -> impl Future<Output = Result<RowsAffected>> + Send {
+        let row = self.entity.row_full();
+        let changed = self.changed(&row);
+        if changed.is_empty() {
+            return Either::Left(future::ready(Ok(RowsAffected::default())));
+        }
+        if T::primary_key_def().len() == 0 {
+            let error = Error::msg(format!(
+                "Cannot update a entity of `{}` without a primary key",
+                T::table().full_name()
+            ));
+            log::error!("{:#}", error);
+            return Either::Left(future::ready(Err(error)));
+        }
+        if let Err(error) = T::writable()
+            .and_then(|_| self.entity.validate())
+            .and_then(|_| self.entity.before_save())
+        {
+            return Either::Left(future::ready(Err(error)));
+        }
+        let condition = PrimaryKeyCondition(
+            T::primary_key_def()
+                .map(|pk| {
+                    let i = T::columns()
+                        .iter()
+                        .position(|c| c.name() == pk.name())
+                        .expect("Primary key column must be one of the columns");
+                    (pk.column_ref, self.snapshot[i].clone())
+                })
+                .collect(),
+        );
+        let mut query = String::with_capacity(256);
+        executor.driver().sql_writer().write_update::<T>(
+            &mut query,
+            changed.iter().map(|&i| (&T::columns()[i], &row[i])),
+            &condition,
+        );
+        let snapshot = &mut self.snapshot;
+        let future = executor.execute(query);
+        Either::Right(async move {
+            let result = future.await?;
+            if result.rows_affected > 0 {
+                *snapshot = row;
+            }
+            Ok(result)
+        })
+    }
+
+    /// Indexes of the columns in `row` that differ from the snapshot.
+    fn changed(&self, row: &Row) -> Vec<usize> {
+        let filtered = self
+            .entity
+            .row_filtered()
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<HashSet<_>>();
+        T::columns()
+            .iter()
+            .enumerate()
+            .filter(|(i, c)| row[*i] != self.snapshot[*i] && filtered.contains(c.name()))
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+impl<T: Entity> From<T> for Tracked<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: Entity> Deref for Tracked<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.entity
+    }
+}
+
+impl<T: Entity> DerefMut for Tracked<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.entity
+    }
+}
+
+/// Conjunction of equalities `column = value` identifying a single row.
+#[derive(Debug)]
+struct PrimaryKeyCondition(Vec<(ColumnRef, Value)>);
+
+impl OpPrecedence for PrimaryKeyCondition {
+    fn precedence(&self, writer: &dyn SqlWriter) -> i32 {
+        writer.expression_binary_op_precedence(if self.0.len() > 1 {
+            &BinaryOpType::And
+        } else {
+            &BinaryOpType::Equal
+        })
+    }
+}
+
+impl Expression for PrimaryKeyCondition {
+    fn write_query(&self, writer: &dyn SqlWriter, context: &mut Context, out: &mut String) {
+        separated_by(
+            out,
+            &self.0,
+            |out, (column, value)| {
+                BinaryOp {
+                    op: BinaryOpType::Equal,
+                    lhs: column,
+                    rhs: value,
+                }
+                .write_query(writer, context, out);
+            },
+            " AND ",
+        );
+    }
+}
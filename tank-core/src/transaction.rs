@@ -1,13 +1,212 @@
-use crate::{Executor, Result};
+use crate::{
+    AsQuery, Driver, Entity, Error, Executor, Query, QueryResult, Result, RowLabeled, RowsAffected,
+    stream::Stream, writer::SqlWriter,
+};
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
 
 /// A mutable transactional context implementing [`Executor`].
 ///
 /// Consuming methods (`commit`, `rollback`) finalize the transaction. Dropping
 /// without explicit finalization should implicitly rollback, prefer an explicit
-/// choice for clarity.
+/// choice for clarity. [`Savepoint`] is the exception: it cannot run statements
+/// when dropped, its changes stay in the enclosing transaction.
 pub trait Transaction<'c>: Executor {
     /// Commit the outstanding changes.
     fn commit(self) -> impl Future<Output = Result<()>>;
     /// Rollback any uncommitted changes.
     fn rollback(self) -> impl Future<Output = Result<()>>;
+
+    /// Begin a nested transaction inside this one.
+    ///
+    /// Committing the nested transaction keeps its changes as part of this transaction, rolling
+    /// it back discards only the changes made since it began. Dropping it does neither, see
+    /// [`Savepoint`].
+    ///
+    /// Error if the driver does not support savepoints (see `Driver::SAVEPOINTS`).
+    fn savepoint(&mut self) -> impl Future<Output = Result<Savepoint<'_, Self>>> {
+        Savepoint::new(self)
+    }
+}
+
+/// Isolation level of a transaction, see [`TransactionOptions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IsolationLevel {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+/// Options of a transaction begun with [`Connection::begin_with`](crate::Connection::begin_with).
+///
+/// The default options leave every choice to the database. Drivers map them to what the database
+/// supports, see their `SqlWriter::write_transaction_begin_with`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TransactionOptions {
+    /// Isolation level, `None` keeps the database default.
+    pub isolation: Option<IsolationLevel>,
+    /// Reject writes inside the transaction.
+    pub read_only: bool,
+    /// Wait for a snapshot that cannot cause serialization failures (serializable read only
+    /// transactions).
+    pub deferrable: bool,
+}
+
+/// How [`Connection::transaction_with`](crate::Connection::transaction_with) retries a
+/// transaction failing with a serialization failure or a deadlock.
+///
+/// The wait before the attempt `n + 1` is `initial_backoff * 2^(n - 1)`, capped at `max_backoff`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RetryPolicy {
+    /// Maximum number of times the transaction runs, including the first one.
+    pub max_attempts: u32,
+    /// Wait before the first retry.
+    pub initial_backoff: Duration,
+    /// Maximum wait between two attempts.
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Policy running the transaction once, without retries.
+    pub const fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+        }
+    }
+
+    /// Wait after the failed attempt number `attempt` (starting from 1).
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(1 << attempt.saturating_sub(1).min(31))
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Up to 5 attempts, waiting from 10ms to 1s.
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_secs(1),
+        }
+    }
+}
+
+static SAVEPOINTS: AtomicU64 = AtomicU64::new(0);
+
+/// Nested transaction backed by a `SAVEPOINT`, see [`Transaction::savepoint`].
+///
+/// Unlike the other transactions, dropping it without finalization does not roll back: the
+/// savepoint is left open and its changes stay in the enclosing transaction, they are discarded
+/// only if that one rolls back. Call `rollback` to discard them.
+pub struct Savepoint<'t, T: Executor> {
+    executor: &'t mut T,
+    name: String,
+}
+
+impl<'t, T: Executor> Savepoint<'t, T> {
+    /// Set a new savepoint on `executor` (which must be in a transaction).
+    pub(crate) async fn new(executor: &'t mut T) -> Result<Self> {
+        if !<T::Driver as Driver>::SAVEPOINTS {
+            let error = Error::msg(format!(
+                "The driver `{}` does not support savepoints",
+                <T::Driver as Driver>::NAME
+            ));
+            log::error!("{:#}", error);
+            return Err(error);
+        }
+        let name = format!(
+            "tank_savepoint_{}",
+            SAVEPOINTS.fetch_add(1, Ordering::Relaxed)
+        );
+        let mut sql = String::new();
+        executor
+            .driver()
+            .sql_writer()
+            .write_transaction_savepoint(&mut sql, &name);
+        executor.execute(sql).await?;
+        Ok(Self { executor, name })
+    }
+
+    /// Name of the savepoint.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl<'t, T: Executor> Executor for Savepoint<'t, T> {
+    type Driver = T::Driver;
+
+    fn driver(&self) -> &Self::Driver {
+        self.executor.driver()
+    }
+
+    fn in_transaction(&self) -> bool {
+        true
+    }
+
+    fn prepare(
+        &mut self,
+        query: String,
+    ) -> impl Future<Output = Result<Query<Self::Driver>>> + Send {
+        self.executor.prepare(query)
+    }
+
+    fn run<'s>(
+        &'s mut self,
+        query: impl AsQuery<Self::Driver> + 's,
+    ) -> impl Stream<Item = Result<QueryResult>> + Send {
+        self.executor.run(query)
+    }
+
+    fn fetch<'s>(
+        &'s mut self,
+        query: impl AsQuery<Self::Driver> + 's,
+    ) -> impl Stream<Item = Result<RowLabeled>> + Send + 's {
+        self.executor.fetch(query)
+    }
+
+    fn execute<'s>(
+        &'s mut self,
+        query: impl AsQuery<Self::Driver> + 's,
+    ) -> impl Future<Output = Result<RowsAffected>> + Send {
+        self.executor.execute(query)
+    }
+
+    fn append<'a, E, It>(
+        &mut self,
+        entities: It,
+    ) -> impl Future<Output = Result<RowsAffected>> + Send
+    where
+        E: Entity + 'a,
+        It: IntoIterator<Item = &'a E> + Send,
+    {
+        self.executor.append(entities)
+    }
+}
+
+impl<'t, T: Executor> Transaction<'t> for Savepoint<'t, T> {
+    async fn commit(self) -> Result<()> {
+        let mut sql = String::new();
+        self.driver()
+            .sql_writer()
+            .write_transaction_release_savepoint(&mut sql, &self.name);
+        self.executor.execute(sql).await.map(|_| ())
+    }
+
+    async fn rollback(self) -> Result<()> {
+        let writer = self.driver().sql_writer();
+        let mut sql = String::new();
+        writer.write_transaction_rollback_to_savepoint(&mut sql, &self.name);
+        self.executor.execute(sql).await?;
+        let mut sql = String::new();
+        writer.write_transaction_release_savepoint(&mut sql, &self.name);
+        self.executor.execute(sql).await.map(|_| ())
+    }
 }
@@ -246,7 +246,8 @@ macro_rules! take_until {
 ///
 /// This reduces boilerplate across driver implementations. The macro expands
 /// into an `impl Executor for $transaction<'c>` with forwarding methods for
-/// `prepare`, `run`, `fetch`, `execute`, and `append`.
+/// `prepare`, `run`, `fetch`, `execute`, and `append`. `in_transaction` is true when the type
+/// has a lifetime, false otherwise.
 ///
 /// Parameters:
 /// * `$driver`: concrete driver type.
@@ -267,8 +268,14 @@ macro_rules! take_until {
 /// impl<'c> Transaction<'c> for YourDBTransaction<'c> { ... }
 /// ```
 macro_rules! impl_executor_transaction {
-    // Case 1: Lifetime is present (necessary for transactions)
-    ($driver:ty, $transaction:ident $(< $lt:lifetime >)?, $connection:ident) => {
+    // Lifetime is present: a transaction borrowing the connection
+    ($driver:ty, $transaction:ident < $lt:lifetime >, $connection:ident) => {
+        ::tank_core::impl_executor_transaction!(@impl true, $driver, $transaction<$lt>, $connection);
+    };
+    ($driver:ty, $transaction:ident, $connection:ident) => {
+        ::tank_core::impl_executor_transaction!(@impl false, $driver, $transaction, $connection);
+    };
+    (@impl $in_transaction:literal, $driver:ty, $transaction:ident $(< $lt:lifetime >)?, $connection:ident) => {
        impl $(<$lt>)? ::tank_core::Executor for $transaction $(<$lt>)? {
             type Driver = $driver;
 
@@ -276,6 +283,10 @@ macro_rules! impl_executor_transaction {
                 self.$connection.driver()
             }
 
+            fn in_transaction(&self) -> bool {
+                $in_transaction
+            }
+
             fn prepare(
                 &mut self,
                 query: String,
@@ -0,0 +1,207 @@
+use crate::{ColumnDef, Error, Result, Value};
+use proc_macro2::TokenStream;
+use quote::{ToTokens, TokenStreamExt, quote};
+use regex::Regex;
+use rust_decimal::prelude::ToPrimitive;
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    sync::{LazyLock, RwLock},
+};
+
+/// Declarative rule checked on a column value before it is written.
+///
+/// NULL values always satisfy the rules, use a non optional field to require a value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Validation {
+    /// Number of characters (text), bytes (blob) or elements (collections).
+    Length {
+        min: Option<usize>,
+        max: Option<usize>,
+    },
+    /// Inclusive bounds of a numeric value.
+    Range { min: Option<f64>, max: Option<f64> },
+    /// Text must match the regular expression.
+    Regex(Cow<'static, str>),
+}
+
+impl Validation {
+    /// Checks `value`, returning the description of the violation if any.
+    pub fn check(&self, value: &Value) -> Option<String> {
+        match self {
+            Validation::Length { min, max } => {
+                let len = value_length(value)?;
+                if min.is_some_and(|min| len < min) || max.is_some_and(|max| len > max) {
+                    return Some(format!(
+                        "length {} is outside {}",
+                        len,
+                        describe_bounds(min, max)
+                    ));
+                }
+            }
+            Validation::Range { min, max } => {
+                let number = value_number(value)?;
+                if min.is_some_and(|min| number < min) || max.is_some_and(|max| number > max) {
+                    return Some(format!(
+                        "value {} is outside {}",
+                        number,
+                        describe_bounds(min, max)
+                    ));
+                }
+            }
+            Validation::Regex(pattern) => {
+                let text = match value {
+                    Value::Varchar(Some(v)) => Cow::Borrowed(v.as_str()),
+                    Value::Char(Some(v)) => Cow::Owned(v.to_string()),
+                    _ => return None,
+                };
+                match regex_matches(pattern, &text) {
+                    Ok(true) => {}
+                    Ok(false) => return Some(format!("{:?} does not match `{}`", text, pattern)),
+                    Err(error) => return Some(format!("{:#}", error)),
+                }
+            }
+        }
+        None
+    }
+}
+
+fn describe_bounds<T: Display>(min: &Option<T>, max: &Option<T>) -> String {
+    match (min, max) {
+        (Some(min), Some(max)) => format!("[{}, {}]", min, max),
+        (Some(min), None) => format!("[{}, ..]", min),
+        (None, Some(max)) => format!("[.., {}]", max),
+        (None, None) => "[.., ..]".into(),
+    }
+}
+
+fn value_length(value: &Value) -> Option<usize> {
+    Some(match value {
+        Value::Varchar(Some(v)) => v.chars().count(),
+        Value::Char(Some(..)) => 1,
+        Value::Blob(Some(v)) => v.len(),
+        Value::Array(Some(v), ..) => v.len(),
+        Value::List(Some(v), ..) => v.len(),
+        Value::Map(Some(v), ..) => v.len(),
+        _ => return None,
+    })
+}
+
+fn value_number(value: &Value) -> Option<f64> {
+    Some(match value {
+        Value::Int8(Some(v)) => *v as _,
+        Value::Int16(Some(v)) => *v as _,
+        Value::Int32(Some(v)) => *v as _,
+        Value::Int64(Some(v)) => *v as _,
+        Value::Int128(Some(v)) => *v as _,
+        Value::UInt8(Some(v)) => *v as _,
+        Value::UInt16(Some(v)) => *v as _,
+        Value::UInt32(Some(v)) => *v as _,
+        Value::UInt64(Some(v)) => *v as _,
+        Value::UInt128(Some(v)) => *v as _,
+        Value::Float32(Some(v)) => *v as _,
+        Value::Float64(Some(v)) => *v,
+        Value::Decimal(Some(v), ..) => v.to_f64()?,
+        _ => return None,
+    })
+}
+
+fn regex_matches(pattern: &str, text: &str) -> Result<bool> {
+    static CACHE: LazyLock<RwLock<HashMap<String, Regex>>> = LazyLock::new(Default::default);
+    if let Some(regex) = CACHE.read().ok().and_then(|v| v.get(pattern).cloned()) {
+        return Ok(regex.is_match(text));
+    }
+    let regex = Regex::new(pattern)
+        .map_err(|e| Error::msg(format!("Invalid regular expression `{}`: {}", pattern, e)))?;
+    let result = regex.is_match(text);
+    if let Ok(mut cache) = CACHE.write() {
+        cache.insert(pattern.to_string(), regex);
+    }
+    Ok(result)
+}
+
+impl ToTokens for Validation {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        fn quote_option<T: ToTokens>(value: &Option<T>) -> TokenStream {
+            match value {
+                Some(v) => quote!(Some(#v)),
+                None => quote!(None),
+            }
+        }
+        tokens.append_all(match self {
+            Validation::Length { min, max } => {
+                let (min, max) = (quote_option(min), quote_option(max));
+                quote!(::tank::Validation::Length { min: #min, max: #max })
+            }
+            Validation::Range { min, max } => {
+                let (min, max) = (quote_option(min), quote_option(max));
+                quote!(::tank::Validation::Range { min: #min, max: #max })
+            }
+            Validation::Regex(pattern) => {
+                let pattern = pattern.as_ref();
+                quote!(::tank::Validation::Regex(::std::borrow::Cow::Borrowed(#pattern)))
+            }
+        });
+    }
+}
+
+/// Rule violated by a column.
+#[derive(Debug, Clone)]
+pub struct Violation {
+    /// Column name.
+    pub column: &'static str,
+    /// Human readable description.
+    pub message: String,
+}
+
+/// Error listing every violation found on a entity, returned when a write is rejected.
+///
+/// Retrieve it from the returned `Error` with `error.downcast_ref::<ValidationError>()`.
+#[derive(Debug, Clone)]
+pub struct ValidationError {
+    /// Full name of the table.
+    pub table: String,
+    /// Violations in column declaration order.
+    pub violations: Vec<Violation>,
+}
+
+impl ValidationError {
+    /// Checks the values of a row against the rules of the columns.
+    pub fn check<'a>(
+        table: impl Into<String>,
+        columns: impl IntoIterator<Item = (&'a ColumnDef, &'a Value)>,
+    ) -> Option<Self> {
+        let violations = columns
+            .into_iter()
+            .flat_map(|(column, value)| {
+                column.validate.iter().filter_map(move |rule| {
+                    rule.check(value).map(|message| Violation {
+                        column: column.name(),
+                        message,
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+        if violations.is_empty() {
+            None
+        } else {
+            Some(Self {
+                table: table.into(),
+                violations,
+            })
+        }
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Validation failed for `{}`", self.table)?;
+        for violation in &self.violations {
+            write!(f, "\n- `{}`: {}", violation.column, violation.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationError {}
@@ -3,23 +3,31 @@ pub enum Fragment {
     #[default]
     None,
     Casting,
+    InList,
+    SqlAlterTable,
     SqlCommentOnColumn,
     SqlCreateSchema,
     SqlCreateTable,
     SqlCreateTablePrimaryKey,
     SqlCreateTableUnique,
+    SqlCreateView,
     SqlDeleteFrom,
     SqlDeleteFromWhere,
     SqlDropSchema,
     SqlDropTable,
+    SqlDropView,
     SqlInsertInto,
     SqlInsertIntoOnConflict,
+    SqlInsertIntoReturning,
     SqlInsertIntoValues,
     SqlJoin,
     SqlSelect,
     SqlSelectFrom,
     SqlSelectOrderBy,
     SqlSelectWhere,
+    SqlUpdate,
+    SqlUpdateSet,
+    SqlUpdateWhere,
     Json,
     JsonKey,
 }
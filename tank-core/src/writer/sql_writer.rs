@@ -1,11 +1,13 @@
 use crate::{
-    Action, BinaryOp, BinaryOpType, ColumnDef, ColumnRef, DataSet, EitherIterator, Entity,
-    Expression, Fragment, Interval, Join, JoinType, Operand, Order, Ordered, PrimaryKeyType,
-    TableRef, UnaryOp, UnaryOpType, Value, possibly_parenthesized, print_timer, separated_by,
-    writer::Context,
+    Action, BinaryOp, BinaryOpType, ColumnDef, ColumnRef, ColumnSchema, ConflictAction, DataSet,
+    EitherIterator, Entity, Expression, Fragment, Interval, IsolationLevel, Join, JoinType,
+    OnConflict, Operand, Order, Ordered, PrimaryKeyType, SchemaChange, SchemaDiff, TableName,
+    TableRef, TableSchema, TransactionOptions, UnaryOp, UnaryOpType, Validation, Value,
+    possibly_parenthesized, print_timer, separated_by, writer::Context,
 };
 use core::f64;
 use futures::future::Either;
+use indoc::indoc;
 use std::{
     collections::{BTreeMap, HashMap},
     fmt::Write,
@@ -77,8 +79,9 @@ pub trait SqlWriter {
     /// Render a table reference with optional alias.
     fn write_table_ref(&self, context: &mut Context, out: &mut String, value: &TableRef) {
         if self.alias_declaration(context) || value.alias.is_empty() {
-            if !value.schema.is_empty() {
-                self.write_identifier_quoted(context, out, &value.schema);
+            let schema = value.effective_schema();
+            if !schema.is_empty() {
+                self.write_identifier_quoted(context, out, &schema);
                 out.push('.');
             }
             self.write_identifier_quoted(context, out, &value.name);
@@ -91,8 +94,9 @@ pub trait SqlWriter {
     /// Render a column reference optionally qualifying with schema/table.
     fn write_column_ref(&self, context: &mut Context, out: &mut String, value: &ColumnRef) {
         if context.qualify_columns && !value.table.is_empty() {
-            if !value.schema.is_empty() {
-                self.write_identifier_quoted(context, out, &value.schema);
+            let schema = value.effective_schema();
+            if !schema.is_empty() {
+                self.write_identifier_quoted(context, out, &schema);
                 out.push('.');
             }
             self.write_identifier_quoted(context, out, &value.table);
@@ -211,6 +215,12 @@ pub trait SqlWriter {
                 let b = if context.is_inside_json() { '"' } else { '\'' };
                 let _ = write!(out, "{b}{v}{b}");
             }
+            Value::Array(Some(v), ..) if context.fragment == Fragment::InList => {
+                self.write_value_in_list(context, out, v)
+            }
+            Value::List(Some(v), ..) if context.fragment == Fragment::InList => {
+                self.write_value_in_list(context, out, v)
+            }
             Value::Array(Some(..), ..) | Value::List(Some(..), ..) => match value {
                 Value::Array(Some(v), ..) => {
                     self.write_value_list(context, out, Either::Left(v), value)
@@ -480,6 +490,25 @@ pub trait SqlWriter {
         out.push(']');
     }
 
+    /// Render the comma separated items of an `IN (...)` list.
+    ///
+    /// An empty list renders `NULL` so that the predicate is never satisfied.
+    fn write_value_in_list(&self, context: &mut Context, out: &mut String, value: &[Value]) {
+        if value.is_empty() {
+            out.push_str("NULL");
+            return;
+        }
+        let mut context = context.switch_fragment(Fragment::None);
+        separated_by(
+            out,
+            value,
+            |out, v| {
+                self.write_value(&mut context.current, out, v);
+            },
+            ", ",
+        );
+    }
+
     /// Render map literal.
     fn write_value_map(
         &self,
@@ -549,6 +578,8 @@ pub trait SqlWriter {
             BinaryOpType::NotRegexp => 400,
             BinaryOpType::Glob => 400,
             BinaryOpType::NotGlob => 400,
+            BinaryOpType::In => 400,
+            BinaryOpType::NotIn => 400,
             BinaryOpType::BitwiseOr => 500,
             BinaryOpType::BitwiseAnd => 600,
             BinaryOpType::ShiftLeft => 700,
@@ -578,6 +609,17 @@ pub trait SqlWriter {
             Operand::LitField(v) => separated_by(out, *v, |out, v| out.push_str(v), "."),
             Operand::LitInt(v) => write_integer!(out, *v, delimiter),
             Operand::LitStr(v) => self.write_value_string(context, out, v),
+            Operand::LitArray(v) if context.fragment == Fragment::InList => {
+                let mut context = context.switch_fragment(Fragment::None);
+                separated_by(
+                    out,
+                    *v,
+                    |out, v| {
+                        v.write_query(self.as_dyn(), &mut context.current, out);
+                    },
+                    ", ",
+                );
+            }
             Operand::LitArray(v) => {
                 out.push('[');
                 separated_by(
@@ -661,6 +703,8 @@ pub trait SqlWriter {
             BinaryOpType::NotRegexp => ("", " NOT REGEXP ", "", false, false),
             BinaryOpType::Glob => ("", " GLOB ", "", false, false),
             BinaryOpType::NotGlob => ("", " NOT GLOB ", "", false, false),
+            BinaryOpType::In => ("", " IN (", ")", false, true),
+            BinaryOpType::NotIn => ("", " NOT IN (", ")", false, true),
             BinaryOpType::Equal => ("", " = ", "", false, false),
             BinaryOpType::NotEqual => ("", " != ", "", false, false),
             BinaryOpType::Less => ("", " < ", "", false, false),
@@ -692,12 +736,19 @@ pub trait SqlWriter {
                 .write_query(self.as_dyn(), &mut context.current, out)
         );
         out.push_str(infix);
+        let mut rhs_context = context.current.switch_fragment(
+            if matches!(value.op, BinaryOpType::In | BinaryOpType::NotIn) {
+                Fragment::InList
+            } else {
+                context.current.fragment
+            },
+        );
         possibly_parenthesized!(
             out,
             !rhs_parenthesized && value.rhs.precedence(self.as_dyn()) <= precedence,
             value
                 .rhs
-                .write_query(self.as_dyn(), &mut context.current, out)
+                .write_query(self.as_dyn(), &mut rhs_context.current, out)
         );
         out.push_str(suffix);
     }
@@ -762,6 +813,32 @@ pub trait SqlWriter {
         out.push_str("BEGIN;");
     }
 
+    /// Emit BEGIN statement with the isolation level and access mode of `options`.
+    fn write_transaction_begin_with(&self, out: &mut String, options: &TransactionOptions) {
+        out.push_str("BEGIN");
+        if let Some(isolation) = options.isolation {
+            out.push_str(" ISOLATION LEVEL ");
+            self.write_transaction_isolation_level(out, isolation);
+        }
+        if options.read_only {
+            out.push_str(" READ ONLY");
+        }
+        if options.deferrable {
+            out.push_str(" DEFERRABLE");
+        }
+        out.push(';');
+    }
+
+    /// Emit the name of a isolation level.
+    fn write_transaction_isolation_level(&self, out: &mut String, isolation: IsolationLevel) {
+        out.push_str(match isolation {
+            IsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+        });
+    }
+
     /// Emit COMMIT statement.
     fn write_transaction_commit(&self, out: &mut String) {
         out.push_str("COMMIT;");
@@ -772,13 +849,41 @@ pub trait SqlWriter {
         out.push_str("ROLLBACK;");
     }
 
-    /// Emit CREATE SCHEMA.
+    /// Emit SAVEPOINT statement.
+    fn write_transaction_savepoint(&self, out: &mut String, name: &str) {
+        out.reserve(12 + name.len());
+        out.push_str("SAVEPOINT ");
+        self.write_identifier_quoted(&mut Context::new(Fragment::None, false), out, name);
+        out.push(';');
+    }
+
+    /// Emit RELEASE SAVEPOINT statement.
+    fn write_transaction_release_savepoint(&self, out: &mut String, name: &str) {
+        out.reserve(20 + name.len());
+        out.push_str("RELEASE SAVEPOINT ");
+        self.write_identifier_quoted(&mut Context::new(Fragment::None, false), out, name);
+        out.push(';');
+    }
+
+    /// Emit ROLLBACK TO SAVEPOINT statement.
+    fn write_transaction_rollback_to_savepoint(&self, out: &mut String, name: &str) {
+        out.reserve(24 + name.len());
+        out.push_str("ROLLBACK TO SAVEPOINT ");
+        self.write_identifier_quoted(&mut Context::new(Fragment::None, false), out, name);
+        out.push(';');
+    }
+
+    /// Emit CREATE SCHEMA, nothing if the table has no schema.
     fn write_create_schema<E>(&self, out: &mut String, if_not_exists: bool)
     where
         Self: Sized,
         E: Entity,
     {
-        out.reserve(32 + E::table().schema.len());
+        let schema = E::table().effective_schema();
+        if schema.is_empty() {
+            return;
+        }
+        out.reserve(32 + schema.len());
         if !out.is_empty() {
             out.push('\n');
         }
@@ -787,17 +892,21 @@ pub trait SqlWriter {
         if if_not_exists {
             out.push_str("IF NOT EXISTS ");
         }
-        self.write_identifier_quoted(&mut context, out, E::table().schema);
+        self.write_identifier_quoted(&mut context, out, &schema);
         out.push(';');
     }
 
-    /// Emit DROP SCHEMA.
+    /// Emit DROP SCHEMA, nothing if the table has no schema.
     fn write_drop_schema<E>(&self, out: &mut String, if_exists: bool)
     where
         Self: Sized,
         E: Entity,
     {
-        out.reserve(24 + E::table().schema.len());
+        let schema = E::table().effective_schema();
+        if schema.is_empty() {
+            return;
+        }
+        out.reserve(24 + schema.len());
         if !out.is_empty() {
             out.push('\n');
         }
@@ -806,7 +915,7 @@ pub trait SqlWriter {
         if if_exists {
             out.push_str("IF EXISTS ");
         }
-        self.write_identifier_quoted(&mut context, out, E::table().schema);
+        self.write_identifier_quoted(&mut context, out, &schema);
         out.push(';');
     }
 
@@ -816,6 +925,7 @@ pub trait SqlWriter {
         Self: Sized,
         E: Entity,
     {
+        self.write_create_sequences::<E>(out, if_not_exists);
         let mut context = Context::new(Fragment::SqlCreateTable, E::qualified_columns());
         let estimated = 128 + E::columns().len() * 64 + E::primary_key_def().len() * 24;
         out.reserve(estimated);
@@ -827,12 +937,22 @@ pub trait SqlWriter {
             out.push_str("IF NOT EXISTS ");
         }
         self.write_table_ref(&mut context, out, E::table());
+        self.write_create_table_body::<E>(&mut context, out);
+        self.write_column_comments_statements::<E>(&mut context, out);
+    }
+
+    /// Emit the parenthesized columns and constraints of CREATE TABLE, up to the closing `;`.
+    fn write_create_table_body<E>(&self, context: &mut Context, out: &mut String)
+    where
+        Self: Sized,
+        E: Entity,
+    {
         out.push_str(" (\n");
         separated_by(
             out,
             E::columns(),
             |out, v| {
-                self.write_create_table_column_fragment(&mut context, out, v);
+                self.write_create_table_column_fragment(context, out, v);
             },
             ",\n",
         );
@@ -880,27 +1000,12 @@ pub trait SqlWriter {
             out,
             foreign_keys,
             |out, column| {
-                let references = column.references.unwrap();
-                out.push_str(",\nFOREIGN KEY (");
-                self.write_identifier_quoted(&mut context, out, &column.name());
-                out.push_str(") REFERENCES ");
-                self.write_table_ref(&mut context, out, &references.table());
-                out.push('(');
-                self.write_column_ref(&mut context, out, &references);
-                out.push(')');
-                if let Some(on_delete) = &column.on_delete {
-                    out.push_str(" ON DELETE ");
-                    self.write_create_table_references_action(&mut context, out, on_delete);
-                }
-                if let Some(on_update) = &column.on_update {
-                    out.push_str(" ON UPDATE ");
-                    self.write_create_table_references_action(&mut context, out, on_update);
-                }
+                out.push_str(",\n");
+                self.write_create_table_foreign_key_fragment(context, out, column);
             },
             "",
         );
         out.push_str(");");
-        self.write_column_comments_statements::<E>(&mut context, out);
     }
 
     /// Emit single column definition fragment.
@@ -914,18 +1019,20 @@ pub trait SqlWriter {
     {
         self.write_identifier_quoted(context, out, &column.name());
         out.push(' ');
-        let len = out.len();
-        self.write_column_overridden_type(context, out, column, &column.column_type);
-        let didnt_write_type = out.len() == len;
-        if didnt_write_type {
-            SqlWriter::write_column_type(self, context, out, &column.value);
-        }
-        if !column.nullable && column.primary_key == PrimaryKeyType::None {
-            out.push_str(" NOT NULL");
-        }
-        if let Some(default) = &column.default {
-            out.push_str(" DEFAULT ");
-            default.write_query(self.as_dyn(), context, out);
+        self.write_column_def_type(context, out, column);
+        if column.is_generated() {
+            self.write_column_generated(context, out, column);
+        } else {
+            if !column.nullable && column.primary_key == PrimaryKeyType::None {
+                out.push_str(" NOT NULL");
+            }
+            if let Some(default) = &column.default {
+                out.push_str(" DEFAULT ");
+                default.write_query(self.as_dyn(), context, out);
+            }
+            if column.auto_increment {
+                self.write_column_auto_increment(context, out, column);
+            }
         }
         if column.primary_key == PrimaryKeyType::PrimaryKey {
             // Composite primary key will be printed elsewhere
@@ -937,6 +1044,123 @@ pub trait SqlWriter {
         if !column.comment.is_empty() {
             self.write_column_comment_inline(context, out, column);
         }
+        self.write_column_check(context, out, column);
+    }
+
+    /// Emit the SQL type of a column: the driver override if any, otherwise the one inferred from
+    /// its value and max length.
+    fn write_column_def_type(&self, context: &mut Context, out: &mut String, column: &ColumnDef)
+    where
+        Self: Sized,
+    {
+        let len = out.len();
+        self.write_column_overridden_type(context, out, column, &column.column_type);
+        let didnt_write_type = out.len() == len;
+        if didnt_write_type {
+            match (&column.value, column.max_length()) {
+                (Value::Varchar(..), Some(max_length)) => {
+                    let _ = write!(out, "VARCHAR({})", max_length);
+                }
+                _ => SqlWriter::write_column_type(self, context, out, &column.value),
+            }
+        }
+    }
+
+    /// Emit CHECK constraint enforcing the `Validation::Range` rules of the column.
+    fn write_column_check(&self, context: &mut Context, out: &mut String, column: &ColumnDef)
+    where
+        Self: Sized,
+    {
+        let bounds = column
+            .validate
+            .iter()
+            .flat_map(|v| match v {
+                Validation::Range { min, max } => [
+                    min.map(|v| (BinaryOpType::GreaterEqual, v)),
+                    max.map(|v| (BinaryOpType::LessEqual, v)),
+                ],
+                _ => [None, None],
+            })
+            .flatten()
+            .collect::<Vec<_>>();
+        if bounds.is_empty() {
+            return;
+        }
+        out.push_str(" CHECK (");
+        separated_by(
+            out,
+            bounds,
+            |out, (op, bound)| {
+                self.write_identifier_quoted(context, out, column.name());
+                out.push(' ');
+                out.push_str(if op == BinaryOpType::GreaterEqual {
+                    ">="
+                } else {
+                    "<="
+                });
+                let _ = write!(out, " {}", bound);
+            },
+            " AND ",
+        );
+        out.push(')');
+    }
+
+    /// Emit the clause making the database assign the column value on insert.
+    fn write_column_auto_increment(
+        &self,
+        _context: &mut Context,
+        out: &mut String,
+        _column: &ColumnDef,
+    ) where
+        Self: Sized,
+    {
+        out.push_str(" GENERATED BY DEFAULT AS IDENTITY");
+    }
+
+    /// Emit the generation clause of a generated column.
+    fn write_column_generated(&self, context: &mut Context, out: &mut String, column: &ColumnDef)
+    where
+        Self: Sized,
+    {
+        let Some(generated) = &column.generated else {
+            return;
+        };
+        out.push_str(" GENERATED ALWAYS AS (");
+        generated.write_query(self.as_dyn(), context, out);
+        out.push_str(if column.stored {
+            ") STORED"
+        } else {
+            ") VIRTUAL"
+        });
+    }
+
+    /// Emit the FOREIGN KEY constraint of a column having `references`.
+    fn write_create_table_foreign_key_fragment(
+        &self,
+        context: &mut Context,
+        out: &mut String,
+        column: &ColumnDef,
+    ) where
+        Self: Sized,
+    {
+        let Some(references) = column.references else {
+            return;
+        };
+        out.push_str("FOREIGN KEY (");
+        self.write_identifier_quoted(context, out, column.name());
+        out.push_str(") REFERENCES ");
+        self.write_table_ref(context, out, &references.table());
+        out.push('(');
+        self.write_column_ref(context, out, &references);
+        out.push(')');
+        if let Some(on_delete) = &column.on_delete {
+            out.push_str(" ON DELETE ");
+            self.write_create_table_references_action(context, out, on_delete);
+        }
+        if let Some(on_update) = &column.on_update {
+            out.push_str(" ON UPDATE ");
+            self.write_create_table_references_action(context, out, on_update);
+        }
     }
 
     /// Emit referential action keyword.
@@ -999,8 +1223,532 @@ pub trait SqlWriter {
         }
         self.write_table_ref(&mut context, out, E::table());
         out.push(';');
+        self.write_drop_sequences::<E>(out, if_exists);
+    }
+
+    /// Emit CREATE VIEW for a entity backed by a view, its query comes from
+    /// `Entity::write_view_query`.
+    fn write_create_view<E>(&self, out: &mut String, if_not_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        let mut query = String::with_capacity(256);
+        E::write_view_query(self, &mut query);
+        out.reserve(32 + E::table().name.len() + query.len());
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("CREATE VIEW ");
+        let mut context = Context::new(Fragment::SqlCreateView, E::qualified_columns());
+        if if_not_exists {
+            out.push_str("IF NOT EXISTS ");
+        }
+        self.write_table_ref(&mut context, out, E::table());
+        out.push_str(" AS\n");
+        out.push_str(query.trim().trim_end_matches(';'));
+        out.push(';');
+    }
+
+    /// Emit DROP VIEW.
+    fn write_drop_view<E>(&self, out: &mut String, if_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        out.reserve(24 + E::table().schema.len() + E::table().name.len());
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("DROP VIEW ");
+        let mut context = Context::new(Fragment::SqlDropView, E::qualified_columns());
+        if if_exists {
+            out.push_str("IF EXISTS ");
+        }
+        self.write_table_ref(&mut context, out, E::table());
+        out.push(';');
+    }
+
+    /// Emit CREATE SEQUENCE statements backing the auto increment columns, for dialects lacking
+    /// identity columns.
+    fn write_create_sequences<E>(&self, _out: &mut String, _if_not_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+    }
+
+    /// Emit DROP SEQUENCE statements matching `write_create_sequences`.
+    fn write_drop_sequences<E>(&self, _out: &mut String, _if_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+    }
+
+    /// Render a table found in the database catalog.
+    fn write_table_name(&self, context: &mut Context, out: &mut String, value: &TableName) {
+        if !value.schema.is_empty() {
+            self.write_identifier_quoted(context, out, &value.schema);
+            out.push('.');
+        }
+        self.write_identifier_quoted(context, out, &value.name);
+    }
+
+    /// Emit CREATE TABLE and CREATE INDEX statements recreating a table read from the database
+    /// catalog.
+    ///
+    /// Recognized column types are translated to this dialect, the other ones and the default
+    /// expressions are copied as reported by the database.
+    fn write_create_table_schema(&self, out: &mut String, table: &TableSchema, if_not_exists: bool)
+    where
+        Self: Sized,
+    {
+        let mut context = Context::new(Fragment::SqlCreateTable, false);
+        out.reserve(128 + table.columns.len() * 64);
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("CREATE TABLE ");
+        if if_not_exists {
+            out.push_str("IF NOT EXISTS ");
+        }
+        self.write_table_name(&mut context, out, &table.table);
+        out.push_str(" (\n");
+        separated_by(
+            out,
+            &table.columns,
+            |out, column| {
+                self.write_identifier_quoted(&mut context, out, &column.name);
+                out.push(' ');
+                self.write_column_schema_type(&mut context, out, column);
+                if !column.nullable && !table.primary_key.contains(&column.name) {
+                    out.push_str(" NOT NULL");
+                }
+                if let Some(default) = &column.default {
+                    let _ = write!(out, " DEFAULT {}", default);
+                }
+            },
+            ",\n",
+        );
+        let write_columns = |context: &mut Context, out: &mut String, columns: &[String]| {
+            out.push('(');
+            separated_by(
+                out,
+                columns,
+                |out, v| self.write_identifier_quoted(context, out, v),
+                ", ",
+            );
+            out.push(')');
+        };
+        if !table.primary_key.is_empty() {
+            out.push_str(",\nPRIMARY KEY ");
+            write_columns(
+                &mut context
+                    .switch_fragment(Fragment::SqlCreateTablePrimaryKey)
+                    .current,
+                out,
+                &table.primary_key,
+            );
+        }
+        for unique in &table.unique {
+            out.push_str(",\nUNIQUE ");
+            write_columns(
+                &mut context
+                    .switch_fragment(Fragment::SqlCreateTableUnique)
+                    .current,
+                out,
+                &unique.columns,
+            );
+        }
+        for foreign_key in &table.foreign_keys {
+            out.push_str(",\nFOREIGN KEY ");
+            write_columns(&mut context, out, &foreign_key.columns);
+            out.push_str(" REFERENCES ");
+            self.write_table_name(&mut context, out, &foreign_key.references);
+            write_columns(&mut context, out, &foreign_key.references_columns);
+            if let Some(on_delete) = &foreign_key.on_delete {
+                out.push_str(" ON DELETE ");
+                self.write_create_table_references_action(&mut context, out, on_delete);
+            }
+            if let Some(on_update) = &foreign_key.on_update {
+                out.push_str(" ON UPDATE ");
+                self.write_create_table_references_action(&mut context, out, on_update);
+            }
+        }
+        out.push_str(");");
+        for index in &table.indexes {
+            out.push_str("\nCREATE ");
+            if index.unique {
+                out.push_str("UNIQUE ");
+            }
+            out.push_str("INDEX ");
+            if if_not_exists {
+                out.push_str("IF NOT EXISTS ");
+            }
+            self.write_identifier_quoted(&mut context, out, &index.name);
+            out.push_str(" ON ");
+            self.write_table_name(&mut context, out, &table.table);
+            out.push(' ');
+            write_columns(&mut context, out, &index.columns);
+            out.push(';');
+        }
+    }
+
+    /// Emit the SQL type of a column read from the database catalog: the one of its value in this
+    /// dialect when recognized, otherwise the type reported by the database.
+    fn write_column_schema_type(
+        &self,
+        context: &mut Context,
+        out: &mut String,
+        column: &ColumnSchema,
+    ) {
+        let max_length = column
+            .column_type
+            .split_once('(')
+            .and_then(|(_, v)| v.trim_end_matches(')').trim().parse::<u32>().ok());
+        match (&column.value, max_length) {
+            (Value::Unknown(..), _) => out.push_str(&column.column_type),
+            (Value::Varchar(..), Some(max_length)) => {
+                let _ = write!(out, "VARCHAR({})", max_length);
+            }
+            _ => self.write_column_type(context, out, &column.value),
+        }
+    }
+
+    /// Emit the ALTER TABLE statements applying a `SchemaDiff`, the destructive changes are
+    /// skipped unless `destructive` is true.
+    fn write_schema_diff(&self, out: &mut String, diff: &SchemaDiff, destructive: bool)
+    where
+        Self: Sized,
+    {
+        let table = diff.table;
+        for change in &diff.changes {
+            if change.is_destructive() && !destructive {
+                continue;
+            }
+            self.write_schema_change(out, table, change);
+        }
+    }
+
+    /// Emit the `ALTER TABLE name ` prefix shared by the alter statements.
+    fn write_alter_table_prefix(&self, context: &mut Context, out: &mut String, table: &TableRef) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("ALTER TABLE ");
+        self.write_table_ref(context, out, table);
+        out.push(' ');
+    }
+
+    /// Emit the ALTER TABLE statement applying a single `SchemaChange` to the table.
+    fn write_schema_change(&self, out: &mut String, table: &TableRef, change: &SchemaChange)
+    where
+        Self: Sized,
+    {
+        match change {
+            SchemaChange::AddColumn(column) => {
+                self.write_alter_table_add_column(out, table, column)
+            }
+            SchemaChange::DropColumn(name) => self.write_alter_table_drop_column(out, table, name),
+            SchemaChange::AlterColumnType(column) => {
+                self.write_alter_table_alter_type(out, table, column)
+            }
+            SchemaChange::SetNotNull(column) => {
+                self.write_alter_table_set_not_null(out, table, column)
+            }
+            SchemaChange::DropNotNull(column) => {
+                self.write_alter_table_drop_not_null(out, table, column)
+            }
+            SchemaChange::SetDefault(column) => {
+                self.write_alter_table_set_default(out, table, column)
+            }
+            SchemaChange::DropDefault(column) => {
+                self.write_alter_table_drop_default(out, table, column)
+            }
+            SchemaChange::AddUnique(columns) => {
+                self.write_alter_table_add_unique(out, table, columns)
+            }
+            SchemaChange::AddForeignKey(column) => {
+                self.write_alter_table_add_foreign_key(out, table, column)
+            }
+            SchemaChange::DropConstraint(name) => {
+                self.write_alter_table_drop_constraint(out, table, name)
+            }
+        }
+    }
+
+    /// Emit ALTER TABLE adding a column.
+    fn write_alter_table_add_column(&self, out: &mut String, table: &TableRef, column: &ColumnDef)
+    where
+        Self: Sized,
+    {
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        self.write_alter_table_prefix(&mut context, out, table);
+        out.push_str("ADD COLUMN ");
+        self.write_create_table_column_fragment(&mut context, out, column);
+        out.push(';');
+    }
+
+    /// Emit ALTER TABLE dropping a column.
+    fn write_alter_table_drop_column(&self, out: &mut String, table: &TableRef, name: &str)
+    where
+        Self: Sized,
+    {
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        self.write_alter_table_prefix(&mut context, out, table);
+        out.push_str("DROP COLUMN ");
+        self.write_identifier_quoted(&mut context, out, name);
+        out.push(';');
+    }
+
+    /// Emit ALTER TABLE changing the type of a column to the declared one.
+    fn write_alter_table_alter_type(&self, out: &mut String, table: &TableRef, column: &ColumnDef)
+    where
+        Self: Sized,
+    {
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        self.write_alter_table_prefix(&mut context, out, table);
+        out.push_str("ALTER COLUMN ");
+        self.write_identifier_quoted(&mut context, out, column.name());
+        out.push_str(" TYPE ");
+        self.write_column_def_type(&mut context, out, column);
+        out.push(';');
+    }
+
+    /// Emit ALTER TABLE making a column `NOT NULL`.
+    fn write_alter_table_set_not_null(&self, out: &mut String, table: &TableRef, column: &ColumnDef)
+    where
+        Self: Sized,
+    {
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        self.write_alter_table_prefix(&mut context, out, table);
+        out.push_str("ALTER COLUMN ");
+        self.write_identifier_quoted(&mut context, out, column.name());
+        out.push_str(" SET NOT NULL;");
+    }
+
+    /// Emit ALTER TABLE making a column nullable.
+    fn write_alter_table_drop_not_null(
+        &self,
+        out: &mut String,
+        table: &TableRef,
+        column: &ColumnDef,
+    ) where
+        Self: Sized,
+    {
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        self.write_alter_table_prefix(&mut context, out, table);
+        out.push_str("ALTER COLUMN ");
+        self.write_identifier_quoted(&mut context, out, column.name());
+        out.push_str(" DROP NOT NULL;");
+    }
+
+    /// Emit ALTER TABLE setting the declared default of a column (nothing if it has none).
+    fn write_alter_table_set_default(&self, out: &mut String, table: &TableRef, column: &ColumnDef)
+    where
+        Self: Sized,
+    {
+        let Some(default) = &column.default else {
+            return;
+        };
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        self.write_alter_table_prefix(&mut context, out, table);
+        out.push_str("ALTER COLUMN ");
+        self.write_identifier_quoted(&mut context, out, column.name());
+        out.push_str(" SET DEFAULT ");
+        default.write_query(self.as_dyn(), &mut context, out);
+        out.push(';');
+    }
+
+    /// Emit ALTER TABLE removing the default of a column.
+    fn write_alter_table_drop_default(&self, out: &mut String, table: &TableRef, column: &ColumnDef)
+    where
+        Self: Sized,
+    {
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        self.write_alter_table_prefix(&mut context, out, table);
+        out.push_str("ALTER COLUMN ");
+        self.write_identifier_quoted(&mut context, out, column.name());
+        out.push_str(" DROP DEFAULT;");
+    }
+
+    /// Emit ALTER TABLE adding a unique constraint over the columns.
+    fn write_alter_table_add_unique(&self, out: &mut String, table: &TableRef, columns: &[&str])
+    where
+        Self: Sized,
+    {
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        self.write_alter_table_prefix(&mut context, out, table);
+        out.push_str("ADD UNIQUE (");
+        separated_by(
+            out,
+            columns,
+            |out, v| self.write_identifier_quoted(&mut context, out, v),
+            ", ",
+        );
+        out.push_str(");");
+    }
+
+    /// Emit ALTER TABLE adding the foreign key of a column having `references`.
+    fn write_alter_table_add_foreign_key(
+        &self,
+        out: &mut String,
+        table: &TableRef,
+        column: &ColumnDef,
+    ) where
+        Self: Sized,
+    {
+        if column.references.is_none() {
+            return;
+        }
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        self.write_alter_table_prefix(&mut context, out, table);
+        out.push_str("ADD ");
+        self.write_create_table_foreign_key_fragment(&mut context, out, column);
+        out.push(';');
+    }
+
+    /// Emit ALTER TABLE dropping a named constraint.
+    fn write_alter_table_drop_constraint(&self, out: &mut String, table: &TableRef, name: &str)
+    where
+        Self: Sized,
+    {
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        self.write_alter_table_prefix(&mut context, out, table);
+        out.push_str("DROP CONSTRAINT ");
+        self.write_identifier_quoted(&mut context, out, name);
+        out.push(';');
+    }
+
+    /// Emit ALTER TABLE renaming a column.
+    fn write_alter_table_rename_column(
+        &self,
+        out: &mut String,
+        table: &TableRef,
+        name: &str,
+        new_name: &str,
+    ) where
+        Self: Sized,
+    {
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        self.write_alter_table_prefix(&mut context, out, table);
+        out.push_str("RENAME COLUMN ");
+        self.write_identifier_quoted(&mut context, out, name);
+        out.push_str(" TO ");
+        self.write_identifier_quoted(&mut context, out, new_name);
+        out.push(';');
+    }
+
+    /// Emit ALTER TABLE renaming a table, it stays in the same schema.
+    ///
+    /// The table is a catalog name, once renamed it no longer matches the `TableRef` of a entity.
+    fn write_alter_table_rename_table(&self, out: &mut String, table: &TableName, new_name: &str)
+    where
+        Self: Sized,
+    {
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("ALTER TABLE ");
+        self.write_table_name(&mut context, out, table);
+        out.push_str(" RENAME TO ");
+        self.write_identifier_quoted(&mut context, out, new_name);
+        out.push(';');
+    }
+
+    /// Emit the statements rebuilding the `live` table into the one declared by `E`, for the
+    /// changes that cannot be made in place.
+    ///
+    /// The declared table is created as `<table>_tank_rebuild` in the same schema, the rows of the
+    /// columns found in both are copied, then the live table is dropped and replaced. Its non
+    /// unique indexes are created again on the columns left.
+    ///
+    /// The statements are meant to run in a single transaction, with the foreign keys not
+    /// enforced (otherwise dropping a referenced table fails or triggers its actions), see
+    /// `write_foreign_keys_enforced` and `write_foreign_key_check`. `Entity::migrate_table` takes
+    /// care of both.
+    fn write_alter_table_rebuild<E>(&self, out: &mut String, live: &TableSchema)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        let table = E::table();
+        let mut temporary = TableName::from(table);
+        temporary.name.push_str("_tank_rebuild");
+        let mut context = Context::new(Fragment::SqlCreateTable, E::qualified_columns());
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("CREATE TABLE ");
+        self.write_table_name(&mut context, out, &temporary);
+        self.write_create_table_body::<E>(&mut context, out);
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        let columns = E::columns()
+            .iter()
+            .filter(|c| !c.is_generated() && live.column(c.name()).is_some())
+            .map(|c| c.name())
+            .collect::<Vec<_>>();
+        if !columns.is_empty() {
+            out.push_str("\nINSERT INTO ");
+            self.write_table_name(&mut context, out, &temporary);
+            out.push_str(" (");
+            separated_by(
+                out,
+                &columns,
+                |out, v| self.write_identifier_quoted(&mut context, out, v),
+                ", ",
+            );
+            out.push_str(")\nSELECT ");
+            separated_by(
+                out,
+                &columns,
+                |out, v| self.write_identifier_quoted(&mut context, out, v),
+                ", ",
+            );
+            out.push_str(" FROM ");
+            self.write_table_ref(&mut context, out, table);
+            out.push(';');
+        }
+        out.push_str("\nDROP TABLE ");
+        self.write_table_ref(&mut context, out, table);
+        out.push(';');
+        self.write_alter_table_rename_table(out, &temporary, table.name);
+        self.write_column_comments_statements::<E>(&mut context, out);
+        for index in live.indexes.iter().filter(|v| {
+            !v.unique
+                && v.columns
+                    .iter()
+                    .all(|c| E::columns().iter().any(|v| v.name() == c))
+        }) {
+            out.push_str("\nCREATE INDEX ");
+            self.write_identifier_quoted(&mut context, out, &index.name);
+            out.push_str(" ON ");
+            self.write_table_ref(&mut context, out, table);
+            out.push_str(" (");
+            separated_by(
+                out,
+                &index.columns,
+                |out, v| self.write_identifier_quoted(&mut context, out, v),
+                ", ",
+            );
+            out.push_str(");");
+        }
     }
 
+    /// Emit the query reading whether the connection enforces the foreign keys, returning a
+    /// single boolean. Nothing where they are always enforced.
+    fn write_foreign_keys_enforced(&self, _out: &mut String) {}
+
+    /// Emit the statement turning the enforcement of the foreign keys on or off for the
+    /// connection, it must run outside of any transaction.
+    fn write_set_foreign_keys_enforced(&self, _out: &mut String, _enforced: bool) {}
+
+    /// Emit the query returning a row for each foreign key violation in the database. Nothing
+    /// where the database cannot check them on demand.
+    fn write_foreign_key_check(&self, _out: &mut String) {}
+
     /// Emit SELECT statement (projection, FROM, WHERE, ORDER, LIMIT).
     fn write_select<Item, Cols, Data, Cond>(
         &self,
@@ -1063,7 +1811,52 @@ pub trait SqlWriter {
         out.push(';');
     }
 
-    /// Emit INSERT (single/multi-row) optionally with ON CONFLICT DO UPDATE.
+    /// Emit SELECT COUNT(*) of the entity rows matching the condition.
+    fn write_count<E>(&self, out: &mut String, condition: &impl Expression)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        out.reserve(128 + E::table().schema.len() + E::table().name.len());
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("SELECT COUNT(*)\nFROM ");
+        let mut context = Context::new(Fragment::SqlSelectFrom, E::qualified_columns());
+        self.write_table_ref(&mut context, out, E::table());
+        out.push_str("\nWHERE ");
+        condition.write_query(
+            self,
+            &mut context.switch_fragment(Fragment::SqlSelectWhere).current,
+            out,
+        );
+        out.push(';');
+    }
+
+    /// Emit query returning a single boolean-like value telling whether any entity row matches
+    /// the condition.
+    fn write_exists<E>(&self, out: &mut String, condition: &impl Expression)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        out.reserve(128 + E::table().schema.len() + E::table().name.len());
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("SELECT EXISTS (SELECT 1\nFROM ");
+        let mut context = Context::new(Fragment::SqlSelectFrom, E::qualified_columns());
+        self.write_table_ref(&mut context, out, E::table());
+        out.push_str("\nWHERE ");
+        condition.write_query(
+            self,
+            &mut context.switch_fragment(Fragment::SqlSelectWhere).current,
+            out,
+        );
+        out.push_str(");");
+    }
+
+    /// Emit INSERT (single/multi-row) optionally with ON CONFLICT DO UPDATE on the primary key.
     fn write_insert<'b, E>(
         &self,
         out: &mut String,
@@ -1072,6 +1865,20 @@ pub trait SqlWriter {
     ) where
         Self: Sized,
         E: Entity + 'b,
+    {
+        self.write_insert_on_conflict(out, entities, update.then(OnConflict::default).as_ref());
+    }
+
+    /// Emit INSERT (single/multi-row) optionally handling the conflicts as described by
+    /// `on_conflict` (upsert).
+    fn write_insert_on_conflict<'b, E>(
+        &self,
+        out: &mut String,
+        entities: impl IntoIterator<Item = &'b E>,
+        on_conflict: Option<&OnConflict>,
+    ) where
+        Self: Sized,
+        E: Entity + 'b,
     {
         let mut rows = entities.into_iter().map(Entity::row_filtered).peekable();
         let Some(mut row) = rows.next() else {
@@ -1086,7 +1893,7 @@ pub trait SqlWriter {
         let mut context = Context::new(Fragment::SqlInsertInto, E::qualified_columns());
         self.write_table_ref(&mut context, out, E::table());
         out.push_str(" (");
-        let columns = E::columns().iter();
+        let columns = E::columns().iter().filter(|c| !c.is_generated());
         let single = rows.peek().is_none();
         if single {
             // Inserting a single row uses row_labeled to filter out Passive::NotSet columns
@@ -1121,7 +1928,7 @@ pub trait SqlWriter {
             let mut field = fields.next();
             separated_by(
                 out,
-                E::columns(),
+                columns.clone(),
                 |out, col| {
                     if Some(col.name()) == field.map(|v| v.0) {
                         self.write_value(
@@ -1153,7 +1960,7 @@ pub trait SqlWriter {
             .expect("Should have at least one row")
             .into_iter()
             .map(|(v, _)| v);
-        if update {
+        if let Some(on_conflict) = on_conflict {
             self.write_insert_update_fragment::<E>(
                 &mut context.current,
                 out,
@@ -1165,43 +1972,98 @@ pub trait SqlWriter {
                 } else {
                     EitherIterator::Right(columns)
                 },
+                on_conflict,
             );
         }
         out.push(';');
     }
 
-    /// Emit ON CONFLICT DO UPDATE fragment for upsert.
+    /// Emit the INSERT of the entities split into multiple queries, one for each entry in `out`.
+    ///
+    /// Each query holds at most `max_rows` rows, a query longer than `max_length` bytes is split in
+    /// halves until it fits (or it holds a single row).
+    fn write_insert_chunks<'b, E>(
+        &self,
+        out: &mut Vec<String>,
+        entities: &[&'b E],
+        on_conflict: Option<&OnConflict>,
+        max_rows: usize,
+        max_length: usize,
+    ) where
+        Self: Sized,
+        E: Entity + 'b,
+    {
+        for chunk in entities.chunks(max_rows.max(1)) {
+            let mut query = String::new();
+            self.write_insert_on_conflict(&mut query, chunk.iter().copied(), on_conflict);
+            if query.len() > max_length && chunk.len() > 1 {
+                let (left, right) = chunk.split_at(chunk.len() / 2);
+                self.write_insert_chunks(out, left, on_conflict, max_rows, max_length);
+                self.write_insert_chunks(out, right, on_conflict, max_rows, max_length);
+            } else if !query.is_empty() {
+                out.push(query);
+            }
+        }
+    }
+
+    /// Emit INSERT of a single entity also returning the value of the `returning` column.
+    ///
+    /// Dialects lacking `RETURNING` write just the INSERT, the value is then expected in
+    /// `RowsAffected::last_affected_id`.
+    fn write_insert_returning<E>(&self, out: &mut String, entity: &E, returning: &ColumnDef)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        self.write_insert(out, [entity], false);
+        if out.ends_with(';') {
+            out.pop();
+        }
+        out.push_str("\nRETURNING ");
+        let mut context = Context::new(Fragment::SqlInsertIntoReturning, E::qualified_columns());
+        self.write_identifier_quoted(&mut context, out, returning.name());
+        out.push(';');
+    }
+
+    /// Emit ON CONFLICT fragment for upsert, `columns` are the ones inserted.
     fn write_insert_update_fragment<'a, E>(
         &self,
         context: &mut Context,
         out: &mut String,
         columns: impl Iterator<Item = &'a ColumnDef>,
+        on_conflict: &OnConflict,
     ) where
         Self: Sized,
         E: Entity,
     {
-        let pk = E::primary_key_def();
-        if pk.len() == 0 {
+        let target = on_conflict.target_names::<E>();
+        let update = on_conflict.update_columns::<E>(columns);
+        let do_nothing = on_conflict.action == ConflictAction::Nothing;
+        if target.is_empty() && !do_nothing {
             return;
         }
         out.push_str("\nON CONFLICT");
         context.fragment = Fragment::SqlInsertIntoOnConflict;
-        if pk.len() > 0 {
+        if !target.is_empty() {
             out.push_str(" (");
             separated_by(
                 out,
-                pk,
+                target,
                 |out, v| {
-                    self.write_identifier_quoted(context, out, v.name());
+                    self.write_identifier_quoted(context, out, v);
                 },
                 ", ",
             );
             out.push(')');
         }
+        if update.is_empty() {
+            out.push_str(" DO NOTHING");
+            return;
+        }
         out.push_str(" DO UPDATE SET\n");
         separated_by(
             out,
-            columns.filter(|c| c.primary_key == PrimaryKeyType::None),
+            update,
             |out, v| {
                 self.write_identifier_quoted(context, out, v.name());
                 out.push_str(" = EXCLUDED.");
@@ -1211,6 +2073,52 @@ pub trait SqlWriter {
         );
     }
 
+    /// Emit UPDATE statement assigning the given column values, with WHERE clause.
+    ///
+    /// Nothing is written when `values` is empty.
+    fn write_update<'a, E>(
+        &self,
+        out: &mut String,
+        values: impl IntoIterator<Item = (&'a ColumnDef, &'a Value)>,
+        condition: &impl Expression,
+    ) where
+        Self: Sized,
+        E: Entity,
+    {
+        let mut values = values.into_iter().peekable();
+        if values.peek().is_none() {
+            return;
+        }
+        out.reserve(128 + E::table().schema.len() + E::table().name.len());
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("UPDATE ");
+        let mut context = Context::new(Fragment::SqlUpdate, E::qualified_columns());
+        self.write_table_ref(&mut context, out, E::table());
+        out.push_str("\nSET ");
+        {
+            let mut context = context.switch_fragment(Fragment::SqlUpdateSet);
+            separated_by(
+                out,
+                values,
+                |out, (column, value)| {
+                    self.write_identifier_quoted(&mut context.current, out, column.name());
+                    out.push_str(" = ");
+                    self.write_value(&mut context.current, out, value);
+                },
+                ", ",
+            );
+        }
+        out.push_str("\nWHERE ");
+        condition.write_query(
+            self,
+            &mut context.switch_fragment(Fragment::SqlUpdateWhere).current,
+            out,
+        );
+        out.push(';');
+    }
+
     /// Emit DELETE statement with WHERE clause.
     fn write_delete<E>(&self, out: &mut String, condition: &impl Expression)
     where
@@ -1234,6 +2142,89 @@ pub trait SqlWriter {
         );
         out.push(';');
     }
+
+    /// Emit the expression evaluating to the default schema of the connection.
+    fn write_current_schema(&self, out: &mut String) {
+        out.push_str("current_schema()");
+    }
+
+    /// Emit the condition selecting `table` in a catalog view, `schema_column` and `name_column`
+    /// are the catalog columns holding the schema and the table name.
+    fn write_catalog_table_condition(
+        &self,
+        out: &mut String,
+        schema_column: &str,
+        name_column: &str,
+        table: &TableName,
+    ) {
+        let mut context = Context::new(Fragment::SqlSelectWhere, false);
+        let _ = write!(out, "{} = ", schema_column);
+        if table.schema.is_empty() {
+            self.write_current_schema(out);
+        } else {
+            self.write_value_string(&mut context, out, &table.schema);
+        }
+        let _ = write!(out, " AND {} = ", name_column);
+        self.write_value_string(&mut context, out, &table.name);
+    }
+
+    /// Emit the query listing the tables, rows are: schema, name.
+    ///
+    /// When `schema` is `None` every table outside the system schemas is listed.
+    fn write_select_tables(&self, out: &mut String, schema: Option<&str>) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(
+            "SELECT table_schema, table_name\nFROM information_schema.tables\nWHERE table_type = 'BASE TABLE' AND ",
+        );
+        match schema {
+            Some(schema) => {
+                out.push_str("table_schema = ");
+                let mut context = Context::new(Fragment::SqlSelectWhere, false);
+                self.write_value_string(&mut context, out, schema);
+            }
+            None => out.push_str("table_schema NOT IN ('information_schema', 'pg_catalog')"),
+        }
+        out.push_str("\nORDER BY table_schema, table_name;");
+    }
+
+    /// Emit the query describing the columns of `table`, rows are: name, type, nullable,
+    /// default, comment.
+    fn write_select_table_columns(&self, out: &mut String, table: &TableName) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(
+            "SELECT column_name, data_type, is_nullable = 'YES', column_default, NULL\nFROM information_schema.columns\nWHERE ",
+        );
+        self.write_catalog_table_condition(out, "table_schema", "table_name", table);
+        out.push_str("\nORDER BY ordinal_position;");
+    }
+
+    /// Emit the query describing the primary key, unique and foreign key constraints of `table`,
+    /// one row for each column: name, type, column, position, referenced schema, referenced
+    /// table, referenced column, update rule, delete rule.
+    fn write_select_table_constraints(&self, out: &mut String, table: &TableName) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(indoc! {"
+            SELECT tc.constraint_name, tc.constraint_type, kcu.column_name, kcu.ordinal_position, ccu.table_schema, ccu.table_name, ccu.column_name, rc.update_rule, rc.delete_rule
+            FROM information_schema.table_constraints tc
+            JOIN information_schema.key_column_usage kcu ON kcu.constraint_schema = tc.constraint_schema AND kcu.constraint_name = tc.constraint_name AND kcu.table_schema = tc.table_schema AND kcu.table_name = tc.table_name
+            LEFT JOIN information_schema.referential_constraints rc ON rc.constraint_schema = tc.constraint_schema AND rc.constraint_name = tc.constraint_name
+            LEFT JOIN information_schema.key_column_usage ccu ON ccu.constraint_schema = rc.unique_constraint_schema AND ccu.constraint_name = rc.unique_constraint_name AND ccu.ordinal_position = kcu.position_in_unique_constraint
+            WHERE tc.constraint_type IN ('PRIMARY KEY', 'UNIQUE', 'FOREIGN KEY') AND "});
+        self.write_catalog_table_condition(out, "tc.table_schema", "tc.table_name", table);
+        out.push_str("\nORDER BY tc.constraint_name, kcu.ordinal_position;");
+    }
+
+    /// Emit the query describing the indexes of `table` not backing a constraint, one row for
+    /// each column in index order: name, unique, column.
+    ///
+    /// Indexes are not part of `information_schema`, nothing is written by default.
+    fn write_select_table_indexes(&self, _out: &mut String, _table: &TableName) {}
 }
 
 /// Fallback generic SQL writer (closest to PostgreSQL / DuckDB conventions).
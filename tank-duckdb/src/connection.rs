@@ -21,7 +21,8 @@ use std::{
 };
 use tank_core::{
     AsQuery, Connection, Driver, Entity, Error, ErrorContext, Executor, Query, QueryResult, Result,
-    RowLabeled, RowsAffected, Value, as_c_string, send_value, stream::Stream, truncate_long,
+    RowLabeled, RowsAffected, TransactionOptions, Value, as_c_string, send_value, stream::Stream,
+    truncate_long,
 };
 use tokio::task::spawn_blocking;
 use url::form_urlencoded;
@@ -29,6 +30,8 @@ use urlencoding::decode;
 
 pub struct DuckDBConnection {
     pub(crate) connection: CBox<duckdb_connection>,
+    /// A `DuckDBTransaction` was begun and is not yet committed or rolled back.
+    pub(crate) transaction: bool,
 }
 
 impl DuckDBConnection {
@@ -63,11 +66,16 @@ impl DuckDBConnection {
                 return;
             }
             let statement_type = duckdb_result_statement_type(*result);
+            // Modify statements having a RETURNING clause produce rows as well
+            let returns_rows = duckdb_result_return_type(*result)
+                == duckdb_result_type_DUCKDB_RESULT_TYPE_QUERY_RESULT;
             #[allow(non_upper_case_globals)]
-            if !matches!(
-                statement_type,
-                duckdb_statement_type_DUCKDB_STATEMENT_TYPE_SELECT
-            ) {
+            if !returns_rows
+                && !matches!(
+                    statement_type,
+                    duckdb_statement_type_DUCKDB_STATEMENT_TYPE_SELECT
+                )
+            {
                 let rows_affected = duckdb_rows_changed(&mut *result);
                 send_value!(
                     tx,
@@ -222,6 +230,10 @@ impl Executor for DuckDBConnection {
         &DuckDBDriver {}
     }
 
+    fn in_transaction(&self) -> bool {
+        self.transaction
+    }
+
     async fn prepare(&mut self, sql: String) -> Result<Query<DuckDBDriver>> {
         let connection = AtomicPtr::new(*self.connection);
         let context = format!(
@@ -296,18 +308,58 @@ impl Executor for DuckDBConnection {
         E: Entity + 'a,
         It: IntoIterator<Item = &'a E> + Send,
     {
+        E::writable()?;
         let connection = AtomicPtr::new(*self.connection);
-        let rows = rows.into_iter().map(Entity::row_full).collect::<Vec<_>>();
+        // The borrowed entities are not `Send`, they must be dropped before awaiting
+        let (columns, rows) = {
+            let entities = rows.into_iter().collect::<Vec<_>>();
+            for entity in &entities {
+                entity.validate()?;
+            }
+            // Generated columns are computed by the database and cannot be appended, auto increment
+            // columns are left to the database unless some entity provides the value
+            let auto_increment_set = E::auto_increment_def().is_some_and(|column| {
+                entities.iter().any(|entity| {
+                    entity
+                        .row_filtered()
+                        .iter()
+                        .any(|(name, _)| *name == column.name())
+                })
+            });
+            let appended = E::columns()
+                .iter()
+                .map(|c| !c.is_generated() && (!c.auto_increment || auto_increment_set))
+                .collect::<Vec<_>>();
+            let columns = E::columns()
+                .iter()
+                .zip(appended.iter())
+                .filter_map(|(column, appended)| appended.then_some(column))
+                .collect::<Vec<_>>();
+            let rows = entities
+                .into_iter()
+                .map(|entity| {
+                    entity
+                        .row_full()
+                        .into_iter()
+                        .zip(appended.iter())
+                        .filter_map(|(value, appended)| appended.then_some(value))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            (columns, rows)
+        };
         if rows.is_empty() {
             return Ok(Default::default());
         }
+        // Resolved here, the blocking task runs outside the `with_schema` scope
+        let schema = E::table().effective_schema();
         spawn_blocking(move || unsafe {
             let table_ref = E::table();
             let mut appender = CBox::new(ptr::null_mut(), |mut p| {
                 duckdb_appender_destroy(&mut p);
             });
             let connection = connection.load(Ordering::Relaxed);
-            let rc = if let Some((catalog, schema)) = table_ref.schema.rsplit_once('.') {
+            let rc = if let Some((catalog, schema)) = schema.rsplit_once('.') {
                 duckdb_appender_create_ext(
                     connection,
                     as_c_string(catalog).as_ptr(),
@@ -318,7 +370,7 @@ impl Executor for DuckDBConnection {
             } else {
                 duckdb_appender_create(
                     connection,
-                    as_c_string(table_ref.schema).as_ptr(),
+                    as_c_string(&*schema).as_ptr(),
                     as_c_string(table_ref.name).as_ptr(),
                     &mut *appender,
                 )
@@ -329,7 +381,7 @@ impl Executor for DuckDBConnection {
                 )
                 .context("While creating the `duckdb_appender` object"));
             }
-            for column in E::columns() {
+            for column in columns {
                 duckdb_appender_add_column(*appender, as_c_string(column.name()).as_ptr());
             }
             let rows_affected = rows.len() as u64;
@@ -492,6 +544,15 @@ impl Executor for DuckDBConnection {
                 }
                 duckdb_appender_end_row(*appender);
             }
+            // Constraints are checked when flushing, destroying the appender would discard the error
+            if duckdb_appender_flush(*appender) != duckdb_state_DuckDBSuccess {
+                let error = Error::msg(
+                    error_message_from_ptr(&duckdb_appender_error(*appender)).to_string(),
+                )
+                .context("While flushing the `duckdb_appender` object");
+                log::error!("{:#}", error);
+                return Err(error);
+            }
             Ok(RowsAffected {
                 last_affected_id: None,
                 rows_affected,
@@ -600,11 +661,29 @@ impl Connection for DuckDBConnection {
                 return Err(error);
             };
         };
-        Ok(DuckDBConnection { connection })
+        Ok(DuckDBConnection {
+            connection,
+            transaction: false,
+        })
     }
 
     #[allow(refining_impl_trait)]
     fn begin(&mut self) -> impl Future<Output = Result<DuckDBTransaction<'_>>> {
-        DuckDBTransaction::new(self)
+        DuckDBTransaction::new(self, TransactionOptions::default())
+    }
+
+    #[allow(refining_impl_trait)]
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> impl Future<Output = Result<DuckDBTransaction<'_>>> {
+        DuckDBTransaction::new(self, options)
+    }
+
+    fn begin_driver_transaction(
+        &mut self,
+        options: TransactionOptions,
+    ) -> impl Future<Output = Result<DuckDBTransaction<'_>>> {
+        DuckDBTransaction::new(self, options)
     }
 }
@@ -1,6 +1,7 @@
 use crate::{
     DuckDBPrepared, DuckDBTransaction, connection::DuckDBConnection, sql_writer::DuckDBSqlWriter,
 };
+use std::{future::Future, time::Duration};
 use tank_core::Driver;
 
 #[derive(Debug, Default, Clone, Copy)]
@@ -19,7 +20,13 @@ impl Driver for DuckDBDriver {
     type Transaction<'c> = DuckDBTransaction<'c>;
 
     const NAME: &'static str = "duckdb";
+    const SAVEPOINTS: bool = false;
+
     fn sql_writer(&self) -> Self::SqlWriter {
         DuckDBSqlWriter::default()
     }
+
+    fn sleep(duration: Duration) -> impl Future<Output = ()> + Send {
+        tokio::time::sleep(duration)
+    }
 }
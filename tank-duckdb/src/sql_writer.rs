@@ -2,11 +2,30 @@ use std::{
     collections::{BTreeMap, HashMap},
     fmt::Write,
 };
-use tank_core::{ColumnDef, Context, Interval, SqlWriter, Value, separated_by};
+use tank_core::{
+    ColumnDef, Context, DataSet, Entity, Fragment, Interval, SqlWriter, TableName,
+    TransactionOptions, Value, indoc::indoc, resolve_schema, separated_by,
+};
 
 #[derive(Default)]
 pub struct DuckDBSqlWriter {}
 
+impl DuckDBSqlWriter {
+    /// Name of the sequence backing a auto increment column.
+    fn sequence_name(column: &ColumnDef) -> String {
+        format!("{}_{}_seq", column.table(), column.name())
+    }
+
+    fn write_sequence_ref(&self, context: &mut Context, out: &mut String, column: &ColumnDef) {
+        let schema = resolve_schema(column.schema());
+        if !schema.is_empty() {
+            self.write_identifier_quoted(context, out, &schema);
+            out.push('.');
+        }
+        self.write_identifier_quoted(context, out, &Self::sequence_name(column));
+    }
+}
+
 impl SqlWriter for DuckDBSqlWriter {
     fn as_dyn(&self) -> &dyn SqlWriter {
         self
@@ -27,6 +46,73 @@ impl SqlWriter for DuckDBSqlWriter {
         }
     }
 
+    fn write_column_auto_increment(
+        &self,
+        context: &mut Context,
+        out: &mut String,
+        column: &ColumnDef,
+    ) {
+        // DuckDB has no identity columns, the values come from a sequence
+        let mut sequence = String::new();
+        self.write_sequence_ref(context, &mut sequence, column);
+        out.push_str(" DEFAULT nextval(");
+        self.write_value_string(context, out, &sequence);
+        out.push(')');
+    }
+
+    fn write_column_generated(&self, context: &mut Context, out: &mut String, column: &ColumnDef) {
+        let Some(generated) = &column.generated else {
+            return;
+        };
+        // DuckDB only implements virtual generated columns
+        out.push_str(" GENERATED ALWAYS AS (");
+        generated.write_query(self, context, out);
+        out.push_str(") VIRTUAL");
+    }
+
+    /// DuckDB transactions always use snapshot isolation, the options are ignored.
+    fn write_transaction_begin_with(&self, out: &mut String, _options: &TransactionOptions) {
+        self.write_transaction_begin(out);
+    }
+
+    fn write_create_sequences<E>(&self, out: &mut String, if_not_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        let mut context = Context::new(Fragment::SqlCreateTable, E::qualified_columns());
+        for column in E::columns().iter().filter(|c| c.auto_increment) {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str("CREATE SEQUENCE ");
+            if if_not_exists {
+                out.push_str("IF NOT EXISTS ");
+            }
+            self.write_sequence_ref(&mut context, out, column);
+            out.push(';');
+        }
+    }
+
+    fn write_drop_sequences<E>(&self, out: &mut String, if_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        let mut context = Context::new(Fragment::SqlDropTable, E::qualified_columns());
+        for column in E::columns().iter().filter(|c| c.auto_increment) {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str("DROP SEQUENCE ");
+            if if_exists {
+                out.push_str("IF EXISTS ");
+            }
+            self.write_sequence_ref(&mut context, out, column);
+            out.push(';');
+        }
+    }
+
     fn write_value_blob(&self, _context: &mut Context, out: &mut String, value: &[u8]) {
         out.push('\'');
         for b in value {
@@ -65,4 +151,57 @@ impl SqlWriter for DuckDBSqlWriter {
         );
         out.push('}');
     }
+
+    fn write_select_tables(&self, out: &mut String, schema: Option<&str>) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(
+            "SELECT schema_name, table_name\nFROM duckdb_tables()\nWHERE database_name = current_database() AND NOT internal",
+        );
+        if let Some(schema) = schema {
+            out.push_str(" AND schema_name = ");
+            let mut context = Context::new(Fragment::SqlSelectWhere, false);
+            self.write_value_string(&mut context, out, schema);
+        }
+        out.push_str("\nORDER BY schema_name, table_name;");
+    }
+
+    fn write_select_table_columns(&self, out: &mut String, table: &TableName) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(indoc! {"
+            SELECT column_name, data_type, is_nullable, column_default, comment
+            FROM duckdb_columns()
+            WHERE database_name = current_database() AND "});
+        self.write_catalog_table_condition(out, "schema_name", "table_name", table);
+        out.push_str("\nORDER BY column_index;");
+    }
+
+    fn write_select_table_constraints(&self, out: &mut String, table: &TableName) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        // Lists unnested together are zipped, the shorter ones padded with NULL
+        out.push_str(indoc! {"
+            SELECT constraint_name, constraint_type, UNNEST(constraint_column_names), UNNEST(range(1, len(constraint_column_names) + 1)), CASE WHEN referenced_table IS NOT NULL THEN schema_name END, referenced_table, UNNEST(referenced_column_names), NULL, NULL
+            FROM duckdb_constraints()
+            WHERE constraint_type IN ('PRIMARY KEY', 'UNIQUE', 'FOREIGN KEY') AND database_name = current_database() AND "});
+        self.write_catalog_table_condition(out, "schema_name", "table_name", table);
+        out.push_str("\nORDER BY constraint_index;");
+    }
+
+    fn write_select_table_indexes(&self, out: &mut String, table: &TableName) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        // The indexed columns are available just in the CREATE INDEX statement
+        out.push_str(indoc! {r#"
+            SELECT index_name, is_unique, trim(UNNEST(string_split(regexp_extract(sql, '\(([^()]*)\)\s*;?\s*$', 1), ',')), ' "')
+            FROM duckdb_indexes()
+            WHERE database_name = current_database() AND "#});
+        self.write_catalog_table_condition(out, "schema_name", "table_name", table);
+        out.push_str("\nORDER BY index_name;");
+    }
 }
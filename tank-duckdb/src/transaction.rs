@@ -1,7 +1,8 @@
 use crate::{DuckDBConnection, DuckDBDriver};
+use libduckdb_sys::{duckdb_query, duckdb_state_DuckDBSuccess};
+use std::{ffi::CString, ptr};
 use tank_core::{
-    Driver, Executor, Result, SqlWriter, Transaction, future::TryFutureExt,
-    impl_executor_transaction,
+    Driver, Executor, Result, SqlWriter, Transaction, TransactionOptions, impl_executor_transaction,
 };
 
 pub struct DuckDBTransaction<'c> {
@@ -9,15 +10,19 @@ pub struct DuckDBTransaction<'c> {
 }
 
 impl<'c> DuckDBTransaction<'c> {
-    pub async fn new(connection: &'c mut DuckDBConnection) -> Result<Self> {
+    pub async fn new(
+        connection: &'c mut DuckDBConnection,
+        options: TransactionOptions,
+    ) -> Result<Self> {
         let result = Self { connection };
         let mut sql = String::new();
         result
             .connection
             .driver()
             .sql_writer()
-            .write_transaction_begin(&mut sql);
+            .write_transaction_begin_with(&mut sql, &options);
         result.connection.execute(sql).await?;
+        result.connection.transaction = true;
         Ok(result)
     }
 }
@@ -29,7 +34,11 @@ impl<'c> Transaction<'c> for DuckDBTransaction<'c> {
         self.driver()
             .sql_writer()
             .write_transaction_commit(&mut sql);
-        self.connection.execute(sql).map_ok(|_| ())
+        async move {
+            self.connection.execute(sql).await?;
+            self.connection.transaction = false;
+            Ok(())
+        }
     }
 
     fn rollback(self) -> impl Future<Output = Result<()>> {
@@ -37,6 +46,32 @@ impl<'c> Transaction<'c> for DuckDBTransaction<'c> {
         self.driver()
             .sql_writer()
             .write_transaction_rollback(&mut sql);
-        self.connection.execute(sql).map_ok(|_| ())
+        async move {
+            self.connection.execute(sql).await?;
+            self.connection.transaction = false;
+            Ok(())
+        }
+    }
+}
+
+impl Drop for DuckDBTransaction<'_> {
+    /// Rolls back a transaction neither committed nor rolled back, like the other drivers do.
+    fn drop(&mut self) {
+        if !self.connection.transaction {
+            return;
+        }
+        self.connection.transaction = false;
+        let mut sql = String::new();
+        self.driver()
+            .sql_writer()
+            .write_transaction_rollback(&mut sql);
+        let Ok(sql) = CString::new(sql) else {
+            return;
+        };
+        let state =
+            unsafe { duckdb_query(*self.connection.connection, sql.as_ptr(), ptr::null_mut()) };
+        if state != duckdb_state_DuckDBSuccess {
+            log::error!("Failed to roll back a dropped transaction");
+        }
     }
 }
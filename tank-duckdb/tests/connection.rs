@@ -2,7 +2,7 @@
 mod tests {
     use std::{path::Path, sync::Mutex};
     use tank_core::{
-        AsValue, Connection, Executor, QueryResult, indoc::indoc, stream::TryStreamExt,
+        AsValue, Connection, Executor, QueryResult, Transaction, indoc::indoc, stream::TryStreamExt,
     };
     use tank_duckdb::DuckDBConnection;
     use tank_tests::{init_logs, silent_logs};
@@ -99,4 +99,41 @@ mod tests {
         .expect("Could not extract string");
         assert_eq!(max_memory, "800.0 MiB");
     }
+
+    #[tokio::test]
+    async fn dropped_transaction() {
+        init_logs();
+        const DB_PATH: &'static str = "../target/debug/dropped_transaction.duckdb";
+        if Path::new(DB_PATH).exists() {
+            fs::remove_file(DB_PATH)
+                .await
+                .expect("Failed to remove the test database file");
+        }
+        let mut connection = DuckDBConnection::connect(format!("duckdb://{}", DB_PATH).into())
+            .await
+            .expect("Could not open the database");
+        connection
+            .execute("CREATE TABLE orders (id INTEGER);")
+            .await
+            .expect("Failed to create the table");
+        let mut transaction = connection.begin().await.expect("Failed to begin");
+        transaction
+            .execute("INSERT INTO orders VALUES (1);")
+            .await
+            .expect("Failed to insert");
+        drop(transaction);
+        assert!(!connection.in_transaction());
+        let rows = connection
+            .fetch("SELECT * FROM orders;")
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("Failed to select");
+        assert!(
+            rows.is_empty(),
+            "The dropped transaction must be rolled back"
+        );
+        let transaction = connection.begin().await.expect("Failed to begin again");
+        transaction.commit().await.expect("Failed to commit");
+        assert!(!connection.in_transaction());
+    }
 }
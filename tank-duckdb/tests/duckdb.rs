@@ -2,8 +2,8 @@
 mod tests {
     use std::path::Path;
     use std::sync::Mutex;
-    use tank_core::Driver;
-    use tank_duckdb::DuckDBDriver;
+    use tank_core::Connection;
+    use tank_duckdb::DuckDBConnection;
     use tank_tests::{execute_tests, init_logs};
     use tokio::fs;
 
@@ -23,9 +23,7 @@ mod tests {
             !Path::new(DB_PATH).exists(),
             "Database file should not exist before test"
         );
-        let driver = DuckDBDriver::new();
-        let connection = driver
-            .connect(format!("duckdb://{}?mode=rw", DB_PATH).into())
+        let connection = DuckDBConnection::connect(format!("duckdb://{}?mode=rw", DB_PATH).into())
             .await
             .expect("Could not open the database");
         assert!(
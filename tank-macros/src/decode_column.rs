@@ -3,19 +3,22 @@ use proc_macro2::TokenStream;
 use quote::ToTokens;
 use std::{collections::BTreeMap, fmt::Debug};
 use syn::{
-    Expr, ExprCall, ExprLit, ExprMethodCall, Field, Ident, Lit, LitStr, Path, Result, Type,
-    custom_keyword, parenthesized,
-    parse::{Parse, ParseStream},
+    Expr, ExprAssign, ExprCall, ExprLit, ExprMethodCall, ExprUnary, Field, Ident, Lit, LitStr,
+    Path, Result, Type, TypePath, UnOp, custom_keyword, parenthesized,
+    parse::{Parse, ParseStream, Parser},
     parse2,
-    token::{Comma, Eq},
+    punctuated::Punctuated,
+    token::{Comma, Eq, Paren},
 };
 use tank_core::{
-    Action, CheckPassive, PrimaryKeyType, TypeDecoded, Value, decode_type, future::Either,
+    Action, CheckPassive, PrimaryKeyType, TypeDecoded, Validation, Value, decode_type,
+    future::Either,
 };
 
 pub(crate) struct ColumnMetadata {
     pub(crate) ident: Ident,
     pub(crate) ignored: bool,
+    pub(crate) optional: bool,
     pub(crate) ty: Type,
     pub(crate) name: String,
     pub(crate) column_type: BTreeMap<String, String>,
@@ -28,7 +31,12 @@ pub(crate) struct ColumnMetadata {
     pub(crate) on_update: Option<Action>,
     pub(crate) unique: bool,
     pub(crate) passive: bool,
+    pub(crate) auto_increment: bool,
+    pub(crate) generated: Option<TokenStream>,
+    pub(crate) stored: bool,
     pub(crate) check_passive: Option<CheckPassive>,
+    pub(crate) with: Option<TokenStream>,
+    pub(crate) validate: Vec<Validation>,
     pub(crate) comment: String,
 }
 
@@ -48,7 +56,12 @@ impl Debug for ColumnMetadata {
             .field("on_update", &self.on_update)
             .field("unique", &self.unique)
             .field("passive", &self.passive)
+            .field("auto_increment", &self.auto_increment)
+            .field("generated", &self.generated)
+            .field("stored", &self.stored)
             .field("check_passive", &"..")
+            .field("with", &self.with)
+            .field("validate", &self.validate)
             .field("comment", &self.comment)
             .finish()
     }
@@ -68,6 +81,10 @@ impl Parse for Entry {
             input
                 .parse::<TokenStream>()
                 .expect("There must be some value after `=`")
+        } else if input.peek(Paren) {
+            let content;
+            parenthesized!(content in input);
+            content.parse::<TokenStream>()?
         } else {
             TokenStream::new()
         };
@@ -125,6 +142,100 @@ impl Parse for TypeEntry {
     }
 }
 
+pub(crate) fn is_option(ty: &Type) -> bool {
+    let Type::Path(TypePath { path, .. }) = ty else {
+        return false;
+    };
+    path.segments.last().is_some_and(|v| v.ident == "Option")
+}
+
+fn decode_number(expr: &Expr) -> Option<f64> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(v), ..
+        }) => v.base10_parse::<f64>().ok(),
+        Expr::Lit(ExprLit {
+            lit: Lit::Float(v), ..
+        }) => v.base10_parse::<f64>().ok(),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(..),
+            expr,
+            ..
+        }) => decode_number(expr).map(|v| -v),
+        _ => None,
+    }
+}
+
+fn decode_validate(value: TokenStream) -> Vec<Validation> {
+    let example = "Cannot parse `validate`, example: `#[tank(validate(length(min = 1, max = 64), range(min = 0, max = 100), regex = \"^[a-z]+$\"))]`";
+    let rules = Punctuated::<Expr, Comma>::parse_terminated
+        .parse2(value)
+        .expect(example);
+    rules
+        .into_iter()
+        .map(|rule| match rule {
+            Expr::Call(ExprCall { func, args, .. }) => {
+                let name = func.to_token_stream().to_string();
+                let mut min = None;
+                let mut max = None;
+                for arg in args {
+                    let Expr::Assign(ExprAssign { left, right, .. }) = arg else {
+                        panic!("{}", example);
+                    };
+                    let bound = left.to_token_stream().to_string();
+                    let Some(number) = decode_number(&right) else {
+                        panic!("Expected a number for `{}` in `{}`", bound, name);
+                    };
+                    match bound.as_str() {
+                        "min" => min = Some(number),
+                        "max" => max = Some(number),
+                        _ => panic!(
+                            "Unknown bound `{}` in `{}`, use `min` or `max`",
+                            bound, name
+                        ),
+                    }
+                }
+                match name.as_str() {
+                    "length" => {
+                        let to_length = |v: f64| {
+                            if v < 0.0 || v.fract() != 0.0 {
+                                panic!("The bounds of `length` must be non negative integers");
+                            }
+                            v as usize
+                        };
+                        Validation::Length {
+                            min: min.map(to_length),
+                            max: max.map(to_length),
+                        }
+                    }
+                    "range" => Validation::Range { min, max },
+                    _ => panic!(
+                        "Unknown validation `{}`, use `length`, `range` or `regex`",
+                        name
+                    ),
+                }
+            }
+            Expr::Assign(ExprAssign { left, right, .. })
+                if left.to_token_stream().to_string() == "regex" =>
+            {
+                let Expr::Lit(ExprLit {
+                    lit: Lit::Str(pattern),
+                    ..
+                }) = *right
+                else {
+                    panic!("{}", example);
+                };
+                let pattern = pattern.value();
+                if let Err(error) = regex::Regex::new(&pattern) {
+                    panic!("Invalid regular expression `{}`: {}", pattern, error);
+                }
+                Validation::Regex(pattern.into())
+            }
+            _ => panic!("{}", example),
+        })
+        .collect()
+}
+
 pub fn decode_column(field: &Field) -> ColumnMetadata {
     let ident = field
         .ident
@@ -134,6 +245,7 @@ pub fn decode_column(field: &Field) -> ColumnMetadata {
     let mut metadata = ColumnMetadata {
         ident,
         ignored: false,
+        optional: false,
         ty: field.ty.clone(),
         name,
         column_type: Default::default(),
@@ -146,7 +258,12 @@ pub fn decode_column(field: &Field) -> ColumnMetadata {
         on_update: None,
         unique: false,
         passive: false,
+        auto_increment: false,
+        generated: None,
+        stored: false,
         check_passive: None,
+        with: None,
+        validate: Vec::new(),
         comment: String::new(),
     };
     if metadata.name.starts_with('_') {
@@ -163,6 +280,8 @@ pub fn decode_column(field: &Field) -> ColumnMetadata {
                 let (name, value) = (entry.name, entry.value);
                 if name == "ignore" {
                     metadata.ignored = true;
+                } else if name == "optional" {
+                    metadata.optional = true;
                 } else if name == "default" {
                     metadata.default = Some(expr(value.to_token_stream().into()).into());
                 } else if name == "name" {
@@ -231,6 +350,26 @@ pub fn decode_column(field: &Field) -> ColumnMetadata {
                     }
                 } else if name == "unique" {
                     metadata.unique = true;
+                } else if name == "validate" {
+                    metadata.validate.extend(decode_validate(value.clone()));
+                } else if name == "auto_increment" {
+                    metadata.auto_increment = true;
+                } else if name == "generated" {
+                    if value.is_empty() {
+                        panic!(
+                            "Cannot parse `generated`, example: `#[tank(generated = price * quantity, stored)]`"
+                        );
+                    }
+                    metadata.generated = Some(expr(value.to_token_stream().into()).into());
+                } else if name == "stored" {
+                    metadata.stored = true;
+                } else if name == "with" {
+                    let Ok(v) = parse2::<Path>(value.clone()) else {
+                        panic!(
+                            "Cannot parse `with`, example: `#[tank(with = crate::codecs::url)]`"
+                        );
+                    };
+                    metadata.with = Some(v.to_token_stream());
                 } else {
                     panic!("Unknown attribute `{}` inside tank macro", name);
                 }
@@ -249,7 +388,37 @@ pub fn decode_column(field: &Field) -> ColumnMetadata {
             metadata.comment.push_str(v.value().trim());
         }
     }
-    if !metadata.ignored {
+    if metadata.stored && metadata.generated.is_none() {
+        panic!(
+            "Column `{}` is marked `stored` but it is not `generated`",
+            metadata.name
+        );
+    }
+    if metadata.auto_increment && metadata.generated.is_some() {
+        panic!(
+            "Column `{}` cannot be both `auto_increment` and `generated`",
+            metadata.name
+        );
+    }
+    if metadata.with.is_some() && metadata.auto_increment {
+        panic!(
+            "Column `{}` cannot be both `auto_increment` and use a `with` codec",
+            metadata.name
+        );
+    }
+    if metadata.optional && !is_option(&field.ty) {
+        panic!(
+            "Column `{}` is marked `optional` but its type is not an `Option`",
+            metadata.name
+        );
+    }
+    if metadata.ignored {
+        return metadata;
+    }
+    if metadata.with.is_some() {
+        // The codec provides the value, the field type is opaque to Tank
+        metadata.nullable = is_option(&field.ty);
+    } else {
         let (
             TypeDecoded {
                 value,
@@ -64,6 +64,13 @@ pub fn decode_expression(expr: &Expr) -> TokenStream {
                                         BinOp::Ne(..) => quote! { ::tank::BinaryOpType::NotGlob },
                                         _ => unreachable!(),
                                     }
+                                } else if identifier == "IN" {
+                                    rhs = &cast.expr;
+                                    result = match op {
+                                        BinOp::Eq(..) => quote! { ::tank::BinaryOpType::In },
+                                        BinOp::Ne(..) => quote! { ::tank::BinaryOpType::NotIn },
+                                        _ => unreachable!(),
+                                    }
                                 }
                             }
                         }
@@ -118,7 +125,7 @@ pub fn decode_expression(expr: &Expr) -> TokenStream {
                     path: path.clone(),
                 })),
                 _ => panic!(
-                    "Unexpected cast type, cast can only be a type or the special keyworkds: `IS`, `LIKE`, `REGEXP`, `GLOB`"
+                    "Unexpected cast type, cast can only be a type or the special keyworkds: `IS`, `LIKE`, `REGEXP`, `GLOB`, `IN`"
                 ),
             };
             quote! {
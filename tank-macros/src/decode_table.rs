@@ -15,6 +15,27 @@ pub(crate) struct TableMetadata {
     pub(crate) item: ItemStruct,
     pub(crate) schema: String,
     pub(crate) unique: Vec<Vec<usize>>,
+    pub(crate) hooks: bool,
+    pub(crate) view: Option<ViewMetadata>,
+}
+
+/// Query defining the view backing the entity.
+pub(crate) enum ViewMetadata {
+    Sql(LitStr),
+    /// Columns, data set and optional condition of the SELECT.
+    Select(Vec<Expr>),
+}
+
+fn decode_view(value: Expr) -> Option<ViewMetadata> {
+    Some(match value {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(v), ..
+        }) => ViewMetadata::Sql(v),
+        Expr::Tuple(tuple) if (2..=3).contains(&tuple.elems.len()) => {
+            ViewMetadata::Select(tuple.elems.into_iter().collect())
+        }
+        _ => return None,
+    })
 }
 
 fn decode_set_columns<'a, I: Iterator<Item = &'a ColumnMetadata> + Clone>(
@@ -80,6 +101,8 @@ pub fn decode_table(item: ItemStruct) -> TableMetadata {
     let mut schema = String::new();
     let mut primary_key = vec![];
     let mut unique = vec![];
+    let mut hooks = false;
+    let mut view = None;
     if name.starts_with('_') {
         name.remove(0);
     }
@@ -129,6 +152,17 @@ pub fn decode_table(item: ItemStruct) -> TableMetadata {
                         panic!("Error while parsing `unique`, use it like: `#[tank(unique = (\"k1\", \"k2\", ..))]`, you can specify more than one");
                     };
                     unique.push(value);
+                } else if arg.path.is_ident("hooks") {
+                    hooks = true;
+                } else if arg.path.is_ident("view") {
+                    let Some(value) = arg
+                        .value()
+                        .and_then(ParseBuffer::parse::<Expr>)
+                        .ok()
+                        .and_then(decode_view) else {
+                        panic!("Error while parsing `view`, use it like: `#[tank(view = \"SELECT ...\")]` or `#[tank(view = (cols!(..), MyTable::table(), expr!(..)))]`");
+                    };
+                    view = Some(value);
                 } else {
                     panic!("Unknown attribute `{}` inside tank macro", arg.path.to_token_stream().to_string());
                 }
@@ -146,11 +180,16 @@ pub fn decode_table(item: ItemStruct) -> TableMetadata {
             columns[*pk].primary_key = pk_type;
         }
     }
+    if columns.iter().filter(|c| c.auto_increment).count() > 1 {
+        panic!("A table can have at most one `auto_increment` column");
+    }
     TableMetadata {
         columns,
         name,
         item,
         schema,
         unique,
+        hooks,
+        view,
     }
 }
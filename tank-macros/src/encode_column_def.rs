@@ -1,16 +1,25 @@
 use crate::decode_column::ColumnMetadata;
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{ToTokens, quote};
 use tank_core::{future::Either, quote_btree_map, quote_option};
 
 pub fn encode_column_def(metadata: &ColumnMetadata, column_ref: TokenStream) -> TokenStream {
     let column_type = quote_btree_map(&metadata.column_type);
-    let value = &metadata.value;
+    let value = match &metadata.with {
+        Some(with) => quote!(#with::as_empty_value()),
+        None => metadata.value.to_token_stream(),
+    };
     let nullable = &metadata.nullable;
     let default = metadata
         .default
         .as_ref()
         .map_or(quote!(None), |v| quote!(Some(Box::new(#v))));
+    let generated = metadata
+        .generated
+        .as_ref()
+        .map_or(quote!(None), |v| quote!(Some(Box::new(#v))));
+    let stored = &metadata.stored;
+    let auto_increment = &metadata.auto_increment;
     let primary_key = &metadata.primary_key;
     let references = if let Some(Either::Left(tokens)) = &metadata.references {
         let tokens = tokens.clone();
@@ -34,6 +43,7 @@ pub fn encode_column_def(metadata: &ColumnMetadata, column_ref: TokenStream) ->
     let on_update = quote_option(&metadata.on_update);
     let unique = &metadata.unique;
     let passive = &metadata.passive;
+    let validate = &metadata.validate;
     let comment = &metadata.comment;
     quote! {
         ::tank::ColumnDef {
@@ -48,6 +58,10 @@ pub fn encode_column_def(metadata: &ColumnMetadata, column_ref: TokenStream) ->
             on_update: #on_update,
             unique: #unique,
             passive: #passive,
+            auto_increment: #auto_increment,
+            generated: #generated,
+            stored: #stored,
+            validate: vec![#(#validate),*],
             comment: #comment,
         }
     }
@@ -1,7 +1,7 @@
 use crate::TableMetadata;
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{Ident, Type, spanned::Spanned};
+use syn::{Ident, spanned::Spanned};
 
 pub(crate) fn from_row_trait(table: &TableMetadata) -> (Ident, TokenStream) {
     let item = &table.item;
@@ -15,15 +15,22 @@ pub(crate) fn from_row_trait(table: &TableMetadata) -> (Ident, TokenStream) {
             let mut #ident: Option<#ty> = None;
         }
     });
-    type AssignmentFn = dyn Fn(&Ident, &Type) -> TokenStream;
+    type AssignmentFn = dyn Fn(&Ident, &TokenStream) -> TokenStream;
     type ProducerFn = Box<dyn Fn(&AssignmentFn) -> TokenStream>;
     let field_assignment = table
         .columns
         .iter()
-        .map(|c| (c.ident.clone(), c.name.to_string(), c.ty.clone()))
-        .map(|(ident, name, ty)| {
+        .map(|c| {
+            let ty = &c.ty;
+            let decode = match &c.with {
+                Some(with) => quote!(#with::from_value(__v__)?),
+                None => quote!(<#ty as ::tank::AsValue>::try_from_value(__v__)?),
+            };
+            (c.ident.clone(), c.name.to_string(), decode)
+        })
+        .map(|(ident, name, decode)| {
             Box::new(move |assign: &AssignmentFn| {
-                let assign = assign(&ident, &ty);
+                let assign = assign(&ident, &decode);
                 quote! {
                     if __n__ == #name {
                         #assign;
@@ -42,8 +49,15 @@ pub(crate) fn from_row_trait(table: &TableMetadata) -> (Ident, TokenStream) {
     let create_result = table.columns.iter().map(|c| {
         let column = &c.name;
         let ident = &c.ident;
-        quote! {
-            #ident: #ident.ok_or(__make_error__(#column))?
+        if c.optional {
+            // Fields marked `optional` are left empty when the column is missing
+            quote! {
+                #ident: #ident.unwrap_or_default()
+            }
+        } else {
+            quote! {
+                #ident: #ident.ok_or(__make_error__(#column))?
+            }
         }
     });
     let remaining = item
@@ -66,12 +80,9 @@ pub(crate) fn from_row_trait(table: &TableMetadata) -> (Ident, TokenStream) {
             #(#remaining,)*
         }
     };
-    let field_assignment_default = field_assignment(
-        &|field, ty| quote!(result.#field = <#ty as ::tank::AsValue>::try_from_value(__v__)?),
-    );
-    let field_assignment_holder = field_assignment(
-        &|field, ty| quote!(#field = Some(<#ty as ::tank::AsValue>::try_from_value(__v__)?)),
-    );
+    let field_assignment_default =
+        field_assignment(&|field, decode| quote!(result.#field = #decode));
+    let field_assignment_holder = field_assignment(&|field, decode| quote!(#field = Some(#decode)));
     (
         factory_name.clone(),
         quote! {
@@ -12,7 +12,7 @@ mod from_row_trait;
 use crate::{
     cols::ColList,
     decode_column::ColumnMetadata,
-    decode_table::{TableMetadata, decode_table},
+    decode_table::{TableMetadata, ViewMetadata, decode_table},
     encode_column_def::encode_column_def,
     from_row_trait::from_row_trait,
 };
@@ -30,6 +30,43 @@ use syn::{
 };
 use tank_core::PrimaryKeyType;
 
+/// Implements `FromRow` for a plain struct, decoding each field from the column with the same name.
+///
+/// Fields accept `#[tank(name = "...")]` to read a differently named column and `#[tank(ignore)]`
+/// to skip them. `Option` fields marked `#[tank(optional)]` become `None` when their column is
+/// missing from the row, other missing fields keep their default value if the struct implements
+/// `Default` or produce an error. No table, DDL or CRUD methods are generated.
+///
+/// *Example:*
+/// ```ignore
+/// #[derive(FromRow)]
+/// struct Callsigns {
+///     #[tank(name = "callsign")]
+///     name: String,
+///     messages: i64,
+///     #[tank(optional)]
+///     last_message: Option<String>,
+/// }
+/// let rows = executor
+///     .fetch("SELECT callsign, COUNT(*) AS messages FROM radio_log GROUP BY callsign")
+///     .and_then(|row| async { Callsigns::from_row(row) });
+/// ```
+#[proc_macro_derive(FromRow, attributes(tank))]
+pub fn derive_from_row(input: TokenStream) -> TokenStream {
+    let table = decode_table(parse_macro_input!(input as ItemStruct));
+    let ident = &table.item.ident;
+    let (from_row_factory, from_row) = from_row_trait(&table);
+    quote! {
+        #from_row
+        impl ::tank::FromRow for #ident {
+            fn from_row(row: ::tank::RowLabeled) -> ::tank::Result<Self> {
+                #from_row_factory::<Self>::from_row(row)
+            }
+        }
+    }
+    .into()
+}
+
 #[proc_macro_derive(Entity, attributes(tank))]
 pub fn derive_entity(input: TokenStream) -> TokenStream {
     let table = decode_table(parse_macro_input!(input as ItemStruct));
@@ -40,7 +77,9 @@ pub fn derive_entity(input: TokenStream) -> TokenStream {
         .columns
         .iter()
         .map(|metadata| {
-            let filter_passive = if let Some(ref filter_passive) = metadata.check_passive {
+            let filter_passive = if metadata.generated.is_some() {
+                quote!(false)
+            } else if let Some(ref filter_passive) = metadata.check_passive {
                 let field = &metadata.ident;
                 filter_passive(quote!(self.#field))
             } else {
@@ -64,6 +103,12 @@ pub fn derive_entity(input: TokenStream) -> TokenStream {
             }
         })
         .collect();
+    if let Some((_, column)) = primary_keys.iter().find(|(_, c)| c.with.is_some()) {
+        panic!(
+            "Column `{}` uses a `with` codec, it cannot be part of the primary key",
+            column.name
+        );
+    }
     let primary_key = primary_keys
         .iter()
         .map(|(_i, c)| c.ident.clone())
@@ -84,18 +129,34 @@ pub fn derive_entity(input: TokenStream) -> TokenStream {
     let unique_defs = quote!(vec![#(#unique_defs),*].into_boxed_slice());
     let primary_key_types = primary_keys.iter().map(|(_, c)| c.ty.clone());
     let column = column_trait(&table);
+    let encode_value = |column: &ColumnMetadata| {
+        let field = &column.ident;
+        match &column.with {
+            Some(with) => quote!(#with::to_value(&self.#field)),
+            None => quote!(::tank::AsValue::as_value(self.#field.clone())),
+        }
+    };
     let label_value_and_filter = metadata_and_filter.iter().map(|(column, filter)| {
         let name = &column.name;
-        let field = &column.ident;
-        quote!((#name.into(), ::tank::AsValue::as_value(self.#field.clone()), #filter))
+        let value = encode_value(column);
+        quote!((#name.into(), #value, #filter))
     });
-    let row_full = metadata_and_filter.iter().map(
-        |(ColumnMetadata { ident, .. }, _)| quote!(::tank::AsValue::as_value(self.#ident.clone())),
-    );
+    let row_full = metadata_and_filter
+        .iter()
+        .map(|(column, _)| encode_value(column));
     let columns = metadata_and_filter.iter().map(|(c, _)| {
         let field = &c.ident;
         encode_column_def(&c, quote!(#ident::#field))
     });
+    let set_auto_increment = table.columns.iter().find(|c| c.auto_increment).map(|c| {
+        let field = &c.ident;
+        quote! {
+            fn set_auto_increment(&mut self, value: ::tank::Value) -> ::tank::Result<()> {
+                self.#field = ::tank::AsValue::try_from_value(value)?;
+                Ok(())
+            }
+        }
+    });
     let primary_key_condition = primary_keys.iter().enumerate().map(|(i, (_, c))| {
         (
             &c.ident,
@@ -111,9 +172,130 @@ pub fn derive_entity(input: TokenStream) -> TokenStream {
         .clone()
         .map(|(field, _i, pk)| quote!(#ident::#field == # #pk))
         .collect::<Punctuated<_, AndAnd>>();
+    let (hooks, as_hooks) = if table.hooks {
+        (
+            quote!(),
+            quote! {
+                fn as_hooks(&self) -> Option<&(dyn ::tank::EntityHooks + Sync)> {
+                    Some(self)
+                }
+            },
+        )
+    } else {
+        (quote!(impl ::tank::EntityHooks for #ident {}), quote!())
+    };
+    let insert_many = if table.hooks {
+        quote! {
+            let entities = entities.into_iter().collect::<Vec<_>>();
+            for entity in &entities {
+                if let Err(error) = ::tank::EntityHooks::before_insert(*entity) {
+                    return ::tank::future::Either::Left(::std::future::ready(Err(error)));
+                }
+            }
+            let future = executor.append(entities.clone());
+            ::tank::future::Either::Right(async move {
+                let result = future.await?;
+                for entity in entities {
+                    ::tank::EntityHooks::after_insert(entity, &result)?;
+                }
+                Ok(result)
+            })
+        }
+    } else {
+        quote!(executor.append(entities))
+    };
+    let (write_create, write_drop) = if table.view.is_some() {
+        (quote!(write_create_view), quote!(write_drop_view))
+    } else {
+        (quote!(write_create_table), quote!(write_drop_table))
+    };
+    let view = table.view.as_ref().map(|view| {
+        let write_query = match view {
+            ViewMetadata::Sql(query) => quote!(out.push_str(#query);),
+            ViewMetadata::Select(select) => {
+                let (columns, from) = (&select[0], &select[1]);
+                let condition = select.get(2).map(|v| quote!(#v)).unwrap_or(quote!(true));
+                quote! {
+                    writer.write_select(out, #columns, &#from, &#condition, None);
+                }
+            }
+        };
+        quote! {
+            fn is_view() -> bool {
+                true
+            }
+
+            fn write_view_query<W: ::tank::SqlWriter>(writer: &W, out: &mut String) {
+                let _ = writer;
+                #write_query
+            }
+        }
+    });
+    // Views are read-only, every write is rejected by `Entity::writable`
+    let read_only = quote! {
+        ::std::future::ready(
+            <Self as ::tank::Entity>::writable().map(|_| ::tank::RowsAffected::default()),
+        )
+    };
+    let (insert_one, insert_many, delete_one, delete_many) = if table.view.is_some() {
+        (
+            quote!(let _ = (executor, entity); #read_only),
+            quote!(let _ = (executor, entities); #read_only),
+            quote!(let _ = (executor, primary_key); #read_only),
+            quote!(let _ = (executor, condition); #read_only),
+        )
+    } else {
+        (
+            quote! {
+                if let Err(error) = ::tank::Entity::validate(entity)
+                    .and_then(|_| ::tank::EntityHooks::before_insert(entity))
+                {
+                    return ::tank::future::Either::Left(::std::future::ready(Err(error)));
+                }
+                let mut query = String::with_capacity(128);
+                ::tank::SqlWriter::write_insert(
+                    &::tank::Driver::sql_writer(executor.driver()),
+                    &mut query,
+                    [entity],
+                    false,
+                );
+                let hooks = ::tank::Entity::as_hooks(entity);
+                let future = executor.execute(query);
+                ::tank::future::Either::Right(async move {
+                    let result = future.await?;
+                    if let Some(hooks) = hooks {
+                        ::tank::EntityHooks::after_insert(hooks, &result)?;
+                    }
+                    Ok(result)
+                })
+            },
+            insert_many,
+            quote! {
+                #primary_key_condition_declaration
+                let condition = ::tank::expr!(#primary_key_condition_expression);
+                let mut query = String::with_capacity(128);
+                ::tank::SqlWriter::write_delete::<Self>(
+                    &::tank::Driver::sql_writer(executor.driver()),
+                    &mut query,
+                    &condition,
+                );
+                executor.execute(query)
+            },
+            quote! {
+                let mut query = String::with_capacity(128);
+                ::tank::SqlWriter::write_delete::<Self>(
+                    &::tank::Driver::sql_writer(executor.driver()),
+                    &mut query,
+                    condition,
+                );
+                executor.execute(query)
+            },
+        )
+    };
     quote! {
         #from_row
         #column
+        #hooks
         impl ::tank::Entity for #ident {
             type PrimaryKey<'a> = (#(&'a #primary_key_types,)*);
 
@@ -167,23 +349,31 @@ pub fn derive_entity(input: TokenStream) -> TokenStream {
             }
 
             fn from_row(row: ::tank::RowLabeled) -> ::tank::Result<Self> {
-                #from_row_factory::<Self>::from_row(row)
+                let mut entity = #from_row_factory::<Self>::from_row(row)?;
+                ::tank::EntityHooks::after_load(&mut entity)?;
+                Ok(entity)
             }
 
+            #set_auto_increment
+
+            #view
+
+            #as_hooks
+
             async fn create_table(
                 executor: &mut impl ::tank::Executor,
                 if_not_exists: bool,
                 create_schema: bool,
             ) -> ::tank::Result<()> {
                 let mut query = String::with_capacity(2048);
-                if create_schema && !#schema.is_empty() {
+                if create_schema {
                     ::tank::SqlWriter::write_create_schema::<#ident>(
                         &::tank::Driver::sql_writer(executor.driver()),
                         &mut query,
                         true,
                     );
                 }
-                ::tank::SqlWriter::write_create_table::<#ident>(
+                ::tank::SqlWriter::#write_create::<#ident>(
                     &::tank::Driver::sql_writer(executor.driver()),
                     &mut query,
                     if_not_exists,
@@ -200,12 +390,12 @@ pub fn derive_entity(input: TokenStream) -> TokenStream {
                 drop_schema: bool,
             ) -> ::tank::Result<()> {
                 let mut query = String::with_capacity(256);
-                ::tank::SqlWriter::write_drop_table::<#ident>(
+                ::tank::SqlWriter::#write_drop::<#ident>(
                     &::tank::Driver::sql_writer(executor.driver()),
                     &mut query,
                     if_exists,
                 );
-                if drop_schema && !#schema.is_empty() {
+                if drop_schema {
                     ::tank::SqlWriter::write_drop_schema::<#ident>(
                         &::tank::Driver::sql_writer(executor.driver()),
                         &mut query,
@@ -222,14 +412,7 @@ pub fn derive_entity(input: TokenStream) -> TokenStream {
                 executor: &mut impl ::tank::Executor,
                 entity: &impl ::tank::Entity,
             ) -> impl ::std::future::Future<Output = ::tank::Result<::tank::RowsAffected>> + Send {
-                let mut query = String::with_capacity(128);
-                ::tank::SqlWriter::write_insert(
-                    &::tank::Driver::sql_writer(executor.driver()),
-                    &mut query,
-                    [entity],
-                    false,
-                );
-                executor.execute(query)
+                #insert_one
             }
 
             fn insert_many<'a, It>(
@@ -240,7 +423,7 @@ pub fn derive_entity(input: TokenStream) -> TokenStream {
                 Self: 'a,
                 It: IntoIterator<Item = &'a Self> + Send,
             {
-                executor.append(entities)
+                #insert_many
             }
 
             fn find_pk(
@@ -294,15 +477,7 @@ pub fn derive_entity(input: TokenStream) -> TokenStream {
             where
                 Self: Sized
             {
-                #primary_key_condition_declaration
-                let condition = ::tank::expr!(#primary_key_condition_expression);
-                let mut query = String::with_capacity(128);
-                ::tank::SqlWriter::write_delete::<Self>(
-                    &::tank::Driver::sql_writer(executor.driver()),
-                    &mut query,
-                    &condition,
-                );
-                executor.execute(query)
+                #delete_one
             }
 
             fn delete_many(
@@ -312,13 +487,7 @@ pub fn derive_entity(input: TokenStream) -> TokenStream {
             where
                 Self: Sized
             {
-                let mut query = String::with_capacity(128);
-                ::tank::SqlWriter::write_delete::<Self>(
-                    &::tank::Driver::sql_writer(executor.driver()),
-                    &mut query,
-                    condition,
-                );
-                executor.execute(query)
+                #delete_many
             }
         }
     }
@@ -375,6 +544,7 @@ pub fn join(input: TokenStream) -> TokenStream {
 /// - `PI` identifiers
 /// - `value != "ab%" as LIKE` pattern matching, it becomes `value NOT LIKE 'ab%'`,
 ///   it also supports `REGEXP` and `GLOB` (actual supports depends on the driver)
+/// - `Book::author == [1, 2, 3] as IN`, `Book::author != #ids as IN` membership test, it becomes `author IN (1, 2, 3)`
 /// - `-(-PI) + 2 * (5 % (2 + 1)) == 7 && !(4 < 2)` combination of the previous
 ///
 /// Parentheses obey standard Rust precedence.
@@ -2,7 +2,8 @@ use crate::{MySQLDriver, MySQLQueryable, MySQLTransaction};
 use mysql_async::{Conn, Opts};
 use std::borrow::Cow;
 use tank_core::{
-    Connection, Driver, Error, ErrorContext, Result, impl_executor_transaction, truncate_long,
+    Connection, Driver, Error, ErrorContext, Result, TransactionOptions, impl_executor_transaction,
+    truncate_long,
 };
 use url::Url;
 
@@ -37,6 +38,21 @@ impl Connection for MySQLConnection {
 
     #[allow(refining_impl_trait)]
     fn begin(&mut self) -> impl Future<Output = Result<MySQLTransaction<'_>>> {
-        MySQLTransaction::new(self)
+        MySQLTransaction::new(self, TransactionOptions::default())
+    }
+
+    #[allow(refining_impl_trait)]
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> impl Future<Output = Result<MySQLTransaction<'_>>> {
+        MySQLTransaction::new(self, options)
+    }
+
+    fn begin_driver_transaction(
+        &mut self,
+        options: TransactionOptions,
+    ) -> impl Future<Output = Result<MySQLTransaction<'_>>> {
+        MySQLTransaction::new(self, options)
     }
 }
@@ -1,5 +1,6 @@
 use crate::{MySQLConnection, MySQLPrepared, MySQLSqlWriter, MySQLTransaction};
-use tank_core::Driver;
+use std::{future::Future, time::Duration};
+use tank_core::{Driver, Error};
 
 #[derive(Debug, Clone, Copy, Default)]
 pub struct MySQLDriver;
@@ -16,7 +17,24 @@ impl Driver for MySQLDriver {
     type Transaction<'c> = MySQLTransaction<'c>;
 
     const NAME: &'static str = "mysql";
+    /// Below the 4 MiB `max_allowed_packet` default of older servers
+    const MAX_INSERT_LENGTH: usize = 4_000_000;
+    /// DDL statements commit the current transaction implicitly
+    const TRANSACTIONAL_DDL: bool = false;
+
     fn sql_writer(&self) -> Self::SqlWriter {
         MySQLSqlWriter::default()
     }
+
+    /// Deadlock found when trying to get lock (1213).
+    fn is_retryable(&self, error: &Error) -> bool {
+        error
+            .chain()
+            .filter_map(|e| e.downcast_ref::<mysql_async::Error>())
+            .any(|e| matches!(e, mysql_async::Error::Server(e) if e.code == 1213))
+    }
+
+    fn sleep(duration: Duration) -> impl Future<Output = ()> + Send {
+        tokio::time::sleep(duration)
+    }
 }
@@ -3,8 +3,8 @@ use std::{
     fmt::Write,
 };
 use tank_core::{
-    ColumnDef, Context, Entity, Fragment, Interval, PrimaryKeyType, SqlWriter, Value,
-    future::Either, print_timer, separated_by,
+    ColumnDef, ConflictAction, Context, Entity, Fragment, Interval, OnConflict, PrimaryKeyType,
+    SqlWriter, TableName, TableRef, Value, future::Either, indoc::indoc, print_timer, separated_by,
 };
 
 #[derive(Default)]
@@ -12,6 +12,15 @@ pub struct MySQLSqlWriter {}
 
 impl MySQLSqlWriter {
     const DEFAULT_PK_VARCHAR_TYPE: &'static str = "VARCHAR(63)";
+
+    /// MySQL changes the type and nullability by restating the whole column definition.
+    fn write_modify_column(&self, out: &mut String, table: &TableRef, column: &ColumnDef) {
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        self.write_alter_table_prefix(&mut context, out, table);
+        out.push_str("MODIFY COLUMN ");
+        self.write_create_table_column_fragment(&mut context, out, column);
+        out.push(';');
+    }
 }
 
 impl SqlWriter for MySQLSqlWriter {
@@ -44,6 +53,7 @@ impl SqlWriter for MySQLSqlWriter {
             .or_else(|| {
                 if matches!(column.value, Value::Varchar(..))
                     && column.primary_key != PrimaryKeyType::None
+                    && column.max_length().is_none()
                 {
                     Some(Self::DEFAULT_PK_VARCHAR_TYPE)
                 } else {
@@ -172,6 +182,17 @@ impl SqlWriter for MySQLSqlWriter {
         }
     }
 
+    fn write_column_auto_increment(
+        &self,
+        _context: &mut Context,
+        out: &mut String,
+        _column: &ColumnDef,
+    ) where
+        Self: Sized,
+    {
+        out.push_str(" AUTO_INCREMENT");
+    }
+
     fn write_column_comment_inline(
         &self,
         mut context: &mut Context,
@@ -191,23 +212,124 @@ impl SqlWriter for MySQLSqlWriter {
     {
     }
 
+    /// MySQL sets the characteristics of the next transaction before it begins, `deferrable` is
+    /// ignored.
+    fn write_alter_table_alter_type(&self, out: &mut String, table: &TableRef, column: &ColumnDef)
+    where
+        Self: Sized,
+    {
+        self.write_modify_column(out, table, column);
+    }
+
+    fn write_alter_table_set_not_null(&self, out: &mut String, table: &TableRef, column: &ColumnDef)
+    where
+        Self: Sized,
+    {
+        self.write_modify_column(out, table, column);
+    }
+
+    fn write_alter_table_drop_not_null(
+        &self,
+        out: &mut String,
+        table: &TableRef,
+        column: &ColumnDef,
+    ) where
+        Self: Sized,
+    {
+        self.write_modify_column(out, table, column);
+    }
+
+    fn write_alter_table_rename_table(&self, out: &mut String, table: &TableName, new_name: &str)
+    where
+        Self: Sized,
+    {
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("ALTER TABLE ");
+        self.write_table_name(&mut context, out, table);
+        // An unqualified name would move the table to the current database
+        out.push_str(" RENAME TO ");
+        self.write_table_name(
+            &mut context,
+            out,
+            &TableName::new(table.schema.as_str(), new_name),
+        );
+        out.push(';');
+    }
+
+    fn write_create_view<E>(&self, out: &mut String, if_not_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        let mut query = String::with_capacity(256);
+        E::write_view_query(self, &mut query);
+        out.reserve(32 + E::table().name.len() + query.len());
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        // CREATE VIEW IF NOT EXISTS is not supported
+        out.push_str(if if_not_exists {
+            "CREATE OR REPLACE VIEW "
+        } else {
+            "CREATE VIEW "
+        });
+        let mut context = Context::new(Fragment::SqlCreateView, false);
+        self.write_table_ref(&mut context, out, E::table());
+        out.push_str(" AS\n");
+        out.push_str(query.trim().trim_end_matches(';'));
+        out.push(';');
+    }
+
+    fn write_insert_returning<E>(&self, out: &mut String, entity: &E, _returning: &ColumnDef)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        // MySQL has no RETURNING, the id is reported by LAST_INSERT_ID()
+        self.write_insert(out, [entity], false);
+    }
+
     fn write_insert_update_fragment<'a, E>(
         &self,
         context: &mut Context,
         out: &mut String,
         columns: impl Iterator<Item = &'a ColumnDef>,
+        on_conflict: &OnConflict,
     ) where
         Self: Sized,
         E: Entity,
     {
-        let pk = E::primary_key_def();
-        if pk.len() == 0 {
+        // MySQL has no conflict target, any unique key violation triggers the update
+        let target = on_conflict.target_names::<E>();
+        let do_nothing = on_conflict.action == ConflictAction::Nothing;
+        if target.is_empty() && !do_nothing {
             return;
         }
-        out.push_str("\nON DUPLICATE KEY UPDATE");
+        let columns = columns.collect::<Vec<_>>();
+        let update = on_conflict.update_columns::<E>(columns.iter().copied());
+        if update.is_empty() {
+            // Assigning a key column to itself leaves the row untouched, unlike INSERT IGNORE it
+            // does not turn the other errors into warnings
+            let Some(name) = target
+                .first()
+                .copied()
+                .or_else(|| columns.first().map(|c| c.name()))
+            else {
+                return;
+            };
+            out.push_str("\nON DUPLICATE KEY UPDATE ");
+            self.write_identifier_quoted(context, out, name);
+            out.push_str(" = ");
+            self.write_identifier_quoted(context, out, name);
+            return;
+        }
+        out.push_str("\nON DUPLICATE KEY UPDATE\n");
         separated_by(
             out,
-            columns.filter(|c| c.primary_key == PrimaryKeyType::None),
+            update,
             |out, v| {
                 self.write_identifier_quoted(context, out, v.name());
                 out.push_str(" = VALUES(");
@@ -217,4 +339,66 @@ impl SqlWriter for MySQLSqlWriter {
             ",\n",
         );
     }
+
+    fn write_current_schema(&self, out: &mut String) {
+        out.push_str("DATABASE()");
+    }
+
+    fn write_select_tables(&self, out: &mut String, schema: Option<&str>) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(
+            "SELECT table_schema, table_name\nFROM information_schema.tables\nWHERE table_type = 'BASE TABLE' AND ",
+        );
+        match schema {
+            Some(schema) => {
+                out.push_str("table_schema = ");
+                let mut context = Context::new(Fragment::SqlSelectWhere, false);
+                self.write_value_string(&mut context, out, schema);
+            }
+            None => out.push_str(
+                "table_schema NOT IN ('information_schema', 'mysql', 'performance_schema', 'sys')",
+            ),
+        }
+        out.push_str("\nORDER BY table_schema, table_name;");
+    }
+
+    fn write_select_table_columns(&self, out: &mut String, table: &TableName) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(
+            "SELECT column_name, column_type, is_nullable = 'YES', column_default, column_comment\nFROM information_schema.columns\nWHERE ",
+        );
+        self.write_catalog_table_condition(out, "table_schema", "table_name", table);
+        out.push_str("\nORDER BY ordinal_position;");
+    }
+
+    fn write_select_table_constraints(&self, out: &mut String, table: &TableName) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        // The referenced columns are in key_column_usage, unique constraint names (PRIMARY) are
+        // not unique in the schema
+        out.push_str(indoc! {"
+            SELECT tc.constraint_name, tc.constraint_type, kcu.column_name, kcu.ordinal_position, kcu.referenced_table_schema, kcu.referenced_table_name, kcu.referenced_column_name, rc.update_rule, rc.delete_rule
+            FROM information_schema.table_constraints tc
+            JOIN information_schema.key_column_usage kcu ON kcu.constraint_schema = tc.constraint_schema AND kcu.constraint_name = tc.constraint_name AND kcu.table_schema = tc.table_schema AND kcu.table_name = tc.table_name
+            LEFT JOIN information_schema.referential_constraints rc ON rc.constraint_schema = tc.constraint_schema AND rc.constraint_name = tc.constraint_name AND rc.table_name = tc.table_name
+            WHERE tc.constraint_type IN ('PRIMARY KEY', 'UNIQUE', 'FOREIGN KEY') AND "});
+        self.write_catalog_table_condition(out, "tc.table_schema", "tc.table_name", table);
+        out.push_str("\nORDER BY tc.constraint_name, kcu.ordinal_position;");
+    }
+
+    fn write_select_table_indexes(&self, out: &mut String, table: &TableName) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("SELECT s.index_name, s.non_unique = 0, s.column_name\nFROM information_schema.statistics s\nWHERE ");
+        self.write_catalog_table_condition(out, "s.table_schema", "s.table_name", table);
+        out.push_str(indoc! {"
+             AND NOT EXISTS (SELECT 1 FROM information_schema.table_constraints tc WHERE tc.table_schema = s.table_schema AND tc.table_name = s.table_name AND tc.constraint_name = s.index_name)
+            ORDER BY s.index_name, s.seq_in_index;"});
+    }
 }
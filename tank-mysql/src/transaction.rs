@@ -1,19 +1,34 @@
 use crate::{MySQLConnection, MySQLDriver, MySQLQueryable};
 use mysql_async::TxOpts;
-use tank_core::{Result, Transaction, impl_executor_transaction};
+use tank_core::{
+    IsolationLevel, Result, Transaction, TransactionOptions, impl_executor_transaction,
+};
 
 pub struct MySQLTransaction<'c> {
     pub(crate) transaction: MySQLQueryable<mysql_async::Transaction<'c>>,
 }
 
 impl<'c> MySQLTransaction<'c> {
-    pub async fn new(connection: &'c mut MySQLConnection) -> Result<Self> {
+    pub async fn new(
+        connection: &'c mut MySQLConnection,
+        options: TransactionOptions,
+    ) -> Result<Self> {
+        let mut opts = TxOpts::new();
+        opts.with_isolation_level(options.isolation.map(|v| match v {
+            IsolationLevel::ReadUncommitted => mysql_async::IsolationLevel::ReadUncommitted,
+            IsolationLevel::ReadCommitted => mysql_async::IsolationLevel::ReadCommitted,
+            IsolationLevel::RepeatableRead => mysql_async::IsolationLevel::RepeatableRead,
+            IsolationLevel::Serializable => mysql_async::IsolationLevel::Serializable,
+        }));
+        if options.read_only {
+            opts.with_readonly(true);
+        }
         Ok(Self {
             transaction: MySQLQueryable {
                 executor: connection
                     .conn
                     .executor
-                    .start_transaction(TxOpts::new())
+                    .start_transaction(opts)
                     .await
                     .map_err(|e| {
                         log::error!("{:#}", e);
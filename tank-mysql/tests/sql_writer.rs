@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests {
+    use tank::{Entity, OnConflict, SqlWriter, indoc::indoc};
+    use tank_mysql::MySQLSqlWriter;
+
+    #[derive(Entity)]
+    #[tank(name = "enlistments", primary_key = (Self::operator, Self::squadron))]
+    struct Enlistment {
+        operator: i64,
+        squadron: i64,
+    }
+
+    #[derive(Entity)]
+    #[tank(name = "assignments", unique = (Self::squadron, Self::slot))]
+    struct Assignment {
+        #[tank(primary_key)]
+        id: i64,
+        squadron: i64,
+        slot: i64,
+        operator: i64,
+    }
+
+    const WRITER: MySQLSqlWriter = MySQLSqlWriter {};
+
+    #[test]
+    fn test_upsert_key_only() {
+        let entity = Enlistment {
+            operator: 1,
+            squadron: 2,
+        };
+        for on_conflict in [OnConflict::default(), OnConflict::default().do_nothing()] {
+            let mut query = String::new();
+            WRITER.write_insert_on_conflict(&mut query, [&entity], Some(&on_conflict));
+            assert_eq!(
+                query,
+                indoc! {"
+                    INSERT INTO `enlistments` (`operator`, `squadron`) VALUES
+                    (1, 2)
+                    ON DUPLICATE KEY UPDATE `operator` = `operator`;
+                "}
+                .trim()
+            );
+        }
+    }
+
+    #[test]
+    fn test_upsert_unique_target() {
+        let mut query = String::new();
+        WRITER.write_insert_on_conflict(
+            &mut query,
+            [&Assignment {
+                id: 5,
+                squadron: 1,
+                slot: 2,
+                operator: 3,
+            }],
+            Some(&OnConflict::unique::<Assignment>(0)),
+        );
+        assert_eq!(
+            query,
+            indoc! {"
+                INSERT INTO `assignments` (`id`, `squadron`, `slot`, `operator`) VALUES
+                (5, 1, 2, 3)
+                ON DUPLICATE KEY UPDATE
+                `operator` = VALUES(`operator`);
+            "}
+            .trim()
+        );
+    }
+}
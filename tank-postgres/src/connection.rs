@@ -8,7 +8,7 @@ use postgres_openssl::MakeTlsConnector;
 use std::{borrow::Cow, env, mem, path::PathBuf, pin::pin, str::FromStr, sync::Arc};
 use tank_core::{
     AsQuery, Connection, Driver, Error, ErrorContext, Executor, Query, QueryResult, Result,
-    Transaction,
+    Transaction, TransactionOptions,
     future::Either,
     stream::{Stream, StreamExt, TryStreamExt},
     truncate_long,
@@ -228,7 +228,22 @@ impl Connection for PostgresConnection {
 
     #[allow(refining_impl_trait)]
     fn begin(&mut self) -> impl Future<Output = Result<PostgresTransaction<'_>>> {
-        PostgresTransaction::new(self)
+        PostgresTransaction::new(self, TransactionOptions::default())
+    }
+
+    #[allow(refining_impl_trait)]
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> impl Future<Output = Result<PostgresTransaction<'_>>> {
+        PostgresTransaction::new(self, options)
+    }
+
+    fn begin_driver_transaction(
+        &mut self,
+        options: TransactionOptions,
+    ) -> impl Future<Output = Result<PostgresTransaction<'_>>> {
+        PostgresTransaction::new(self, options)
     }
 
     #[allow(refining_impl_trait)]
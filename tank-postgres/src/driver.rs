@@ -1,7 +1,9 @@
 use crate::{PostgresConnection, PostgresPrepared, PostgresSqlWriter, PostgresTransaction};
-use tank_core::Driver;
+use std::{future::Future, time::Duration};
+use tank_core::{Driver, Error};
+use tokio_postgres::error::SqlState;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, Default)]
 pub struct PostgresDriver {}
 
 impl PostgresDriver {
@@ -20,4 +22,19 @@ impl Driver for PostgresDriver {
     fn sql_writer(&self) -> PostgresSqlWriter {
         PostgresSqlWriter {}
     }
+
+    /// Serialization failure (40001) or deadlock detected (40P01).
+    fn is_retryable(&self, error: &Error) -> bool {
+        error
+            .chain()
+            .filter_map(|e| e.downcast_ref::<tokio_postgres::Error>())
+            .filter_map(|e| e.code())
+            .any(|v| {
+                *v == SqlState::T_R_SERIALIZATION_FAILURE || *v == SqlState::T_R_DEADLOCK_DETECTED
+            })
+    }
+
+    fn sleep(duration: Duration) -> impl Future<Output = ()> + Send {
+        tokio::time::sleep(duration)
+    }
 }
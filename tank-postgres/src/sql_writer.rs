@@ -1,5 +1,8 @@
 use std::{collections::BTreeMap, fmt::Write};
-use tank_core::{ColumnDef, Context, SqlWriter, Value, future::Either, separated_by};
+use tank_core::{
+    ColumnDef, Context, Entity, Fragment, SqlWriter, TableName, Value, future::Either,
+    indoc::indoc, separated_by,
+};
 use time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
 
 pub struct PostgresSqlWriter {}
@@ -72,6 +75,40 @@ impl SqlWriter for PostgresSqlWriter {
         };
     }
 
+    fn write_column_generated(&self, context: &mut Context, out: &mut String, column: &ColumnDef) {
+        let Some(generated) = &column.generated else {
+            return;
+        };
+        // Virtual generated columns are not available before PostgreSQL 18
+        out.push_str(" GENERATED ALWAYS AS (");
+        generated.write_query(self, context, out);
+        out.push_str(") STORED");
+    }
+
+    fn write_create_view<E>(&self, out: &mut String, if_not_exists: bool)
+    where
+        Self: Sized,
+        E: Entity,
+    {
+        let mut query = String::with_capacity(256);
+        E::write_view_query(self, &mut query);
+        out.reserve(32 + E::table().name.len() + query.len());
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        // CREATE VIEW IF NOT EXISTS is not supported
+        out.push_str(if if_not_exists {
+            "CREATE OR REPLACE VIEW "
+        } else {
+            "CREATE VIEW "
+        });
+        let mut context = Context::new(Fragment::SqlCreateView, false);
+        self.write_table_ref(&mut context, out, E::table());
+        out.push_str(" AS\n");
+        out.push_str(query.trim().trim_end_matches(';'));
+        out.push(';');
+    }
+
     fn write_value_blob(&self, _context: &mut Context, out: &mut String, value: &[u8]) {
         out.push_str("'\\x");
         for b in value {
@@ -200,4 +237,76 @@ impl SqlWriter for PostgresSqlWriter {
         context.counter += 1;
         let _ = write!(out, "${}", context.counter);
     }
+
+    fn write_select_tables(&self, out: &mut String, schema: Option<&str>) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        // Catalog columns are domains over name, not decoded by the driver
+        out.push_str(
+            "SELECT table_schema::TEXT, table_name::TEXT\nFROM information_schema.tables\nWHERE table_type = 'BASE TABLE' AND ",
+        );
+        match schema {
+            Some(schema) => {
+                out.push_str("table_schema = ");
+                let mut context = Context::new(Fragment::SqlSelectWhere, false);
+                self.write_value_string(&mut context, out, schema);
+            }
+            None => out.push_str("table_schema NOT IN ('information_schema', 'pg_catalog')"),
+        }
+        out.push_str("\nORDER BY table_schema, table_name;");
+    }
+
+    fn write_select_table_columns(&self, out: &mut String, table: &TableName) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(indoc! {"
+            SELECT a.attname::TEXT, format_type(a.atttypid, a.atttypmod), NOT a.attnotnull, pg_get_expr(d.adbin, d.adrelid), col_description(a.attrelid, a.attnum)
+            FROM pg_attribute a
+            JOIN pg_class c ON c.oid = a.attrelid
+            JOIN pg_namespace n ON n.oid = c.relnamespace
+            LEFT JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum
+            WHERE a.attnum > 0 AND NOT a.attisdropped AND "});
+        self.write_catalog_table_condition(out, "n.nspname", "c.relname", table);
+        out.push_str("\nORDER BY a.attnum;");
+    }
+
+    fn write_select_table_constraints(&self, out: &mut String, table: &TableName) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(indoc! {"
+            SELECT con.conname::TEXT, CASE con.contype WHEN 'p' THEN 'PRIMARY KEY' WHEN 'u' THEN 'UNIQUE' ELSE 'FOREIGN KEY' END, a.attname::TEXT, k.position::INTEGER, fn.nspname::TEXT, ft.relname::TEXT, fa.attname::TEXT,
+            CASE con.confupdtype WHEN 'a' THEN 'NO ACTION' WHEN 'r' THEN 'RESTRICT' WHEN 'c' THEN 'CASCADE' WHEN 'n' THEN 'SET NULL' WHEN 'd' THEN 'SET DEFAULT' END,
+            CASE con.confdeltype WHEN 'a' THEN 'NO ACTION' WHEN 'r' THEN 'RESTRICT' WHEN 'c' THEN 'CASCADE' WHEN 'n' THEN 'SET NULL' WHEN 'd' THEN 'SET DEFAULT' END
+            FROM pg_constraint con
+            JOIN pg_class t ON t.oid = con.conrelid
+            JOIN pg_namespace n ON n.oid = t.relnamespace
+            JOIN LATERAL unnest(con.conkey) WITH ORDINALITY AS k(attnum, position) ON TRUE
+            JOIN pg_attribute a ON a.attrelid = t.oid AND a.attnum = k.attnum
+            LEFT JOIN pg_class ft ON ft.oid = con.confrelid
+            LEFT JOIN pg_namespace fn ON fn.oid = ft.relnamespace
+            LEFT JOIN pg_attribute fa ON fa.attrelid = con.confrelid AND fa.attnum = con.confkey[k.position]
+            WHERE con.contype IN ('p', 'u', 'f') AND "});
+        self.write_catalog_table_condition(out, "n.nspname", "t.relname", table);
+        out.push_str("\nORDER BY con.conname, k.position;");
+    }
+
+    fn write_select_table_indexes(&self, out: &mut String, table: &TableName) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(indoc! {"
+            SELECT i.relname::TEXT, ix.indisunique, a.attname::TEXT
+            FROM pg_index ix
+            JOIN pg_class t ON t.oid = ix.indrelid
+            JOIN pg_namespace n ON n.oid = t.relnamespace
+            JOIN pg_class i ON i.oid = ix.indexrelid
+            JOIN LATERAL unnest(ix.indkey::SMALLINT[]) WITH ORDINALITY AS k(attnum, position) ON TRUE
+            JOIN pg_attribute a ON a.attrelid = t.oid AND a.attnum = k.attnum
+            WHERE NOT EXISTS (SELECT 1 FROM pg_constraint WHERE conindid = ix.indexrelid) AND "});
+        self.write_catalog_table_condition(out, "n.nspname", "t.relname", table);
+        out.push_str("\nORDER BY i.relname, k.position;");
+    }
 }
@@ -5,7 +5,8 @@ use crate::{
     util::stream_postgres_row_to_tank_row,
 };
 use tank_core::{
-    AsQuery, Error, Executor, Query, QueryResult, Result, Transaction,
+    AsQuery, Error, Executor, IsolationLevel, Query, QueryResult, Result, Transaction,
+    TransactionOptions,
     future::{Either, TryFutureExt},
     stream::{Stream, TryStreamExt},
 };
@@ -13,8 +14,26 @@ use tank_core::{
 pub struct PostgresTransaction<'c>(pub(crate) tokio_postgres::Transaction<'c>);
 
 impl<'c> PostgresTransaction<'c> {
-    pub async fn new(client: &'c mut PostgresConnection) -> Result<Self> {
-        Ok(Self(client.client.transaction().await.map_err(|e| {
+    pub async fn new(
+        client: &'c mut PostgresConnection,
+        options: TransactionOptions,
+    ) -> Result<Self> {
+        let mut builder = client.client.build_transaction();
+        if let Some(isolation) = options.isolation {
+            builder = builder.isolation_level(match isolation {
+                IsolationLevel::ReadUncommitted => tokio_postgres::IsolationLevel::ReadUncommitted,
+                IsolationLevel::ReadCommitted => tokio_postgres::IsolationLevel::ReadCommitted,
+                IsolationLevel::RepeatableRead => tokio_postgres::IsolationLevel::RepeatableRead,
+                IsolationLevel::Serializable => tokio_postgres::IsolationLevel::Serializable,
+            });
+        }
+        if options.read_only {
+            builder = builder.read_only(true);
+        }
+        if options.deferrable {
+            builder = builder.deferrable(true);
+        }
+        Ok(Self(builder.start().await.map_err(|e| {
             log::error!("{:#}", e);
             e
         })?))
@@ -26,6 +45,9 @@ impl<'c> Executor for PostgresTransaction<'c> {
     fn driver(&self) -> &Self::Driver {
         &PostgresDriver {}
     }
+    fn in_transaction(&self) -> bool {
+        true
+    }
     async fn prepare(&mut self, query: String) -> Result<Query<Self::Driver>> {
         Ok(
             PostgresPrepared::new(self.0.prepare(&query).await.map_err(|e| {
@@ -15,13 +15,14 @@ mod tests {
     async fn postgres() {
         init_logs();
         let _guard = MUTEX.lock().unwrap();
-        let driver = PostgresDriver::new();
 
         // Unencrypted
         let (url, container) = init(false).await;
         let container = container.expect("Could not launch container");
         let error_msg = format!("Could not connect to `{url}`");
-        let connection = driver.connect(url.into()).await.expect(&error_msg);
+        let connection = PostgresConnection::connect(url.into())
+            .await
+            .expect(&error_msg);
         execute_tests(connection).await;
         drop(container);
 
@@ -29,7 +30,9 @@ mod tests {
         let (url, container) = init(true).await;
         let container = container.expect("Could not launch container");
         let error_msg = format!("Could not connect to `{url}`");
-        let connection = driver.connect(url.into()).await.expect(&error_msg);
+        let connection = PostgresConnection::connect(url.into())
+            .await
+            .expect(&error_msg);
         execute_tests(connection).await;
         drop(container);
     }
@@ -16,7 +16,7 @@ use std::{
 };
 use tank_core::{
     AsQuery, Connection, Driver, Error, ErrorContext, Executor, Query, QueryResult, Result,
-    RowLabeled, RowsAffected, send_value, stream::Stream, truncate_long,
+    RowLabeled, RowsAffected, TransactionOptions, send_value, stream::Stream, truncate_long,
 };
 use tokio::task::spawn_blocking;
 
@@ -55,7 +55,12 @@ impl SQLiteConnection {
             };
             loop {
                 match sqlite3_step(statement) {
-                    SQLITE_BUSY => {
+                    // Waiting for a lock inside a transaction can deadlock with another one,
+                    // the write is reported so that the transaction can be rolled back
+                    SQLITE_BUSY
+                        if sqlite3_get_autocommit(connection) != 0
+                            || sqlite3_stmt_readonly(statement) != 0 =>
+                    {
                         continue;
                     }
                     SQLITE_DONE => {
@@ -161,6 +166,11 @@ impl Executor for SQLiteConnection {
         &SQLiteDriver {}
     }
 
+    /// Also true after a `BEGIN` executed as a plain query.
+    fn in_transaction(&self) -> bool {
+        unsafe { sqlite3_get_autocommit(*self.connection) == 0 }
+    }
+
     async fn prepare(&mut self, sql: String) -> Result<Query<Self::Driver>> {
         let connection = AtomicPtr::new(*self.connection);
         let context = format!(
@@ -284,6 +294,21 @@ impl Connection for SQLiteConnection {
 
     #[allow(refining_impl_trait)]
     fn begin(&mut self) -> impl Future<Output = Result<SQLiteTransaction<'_>>> {
-        SQLiteTransaction::new(self)
+        SQLiteTransaction::new(self, TransactionOptions::default())
+    }
+
+    #[allow(refining_impl_trait)]
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> impl Future<Output = Result<SQLiteTransaction<'_>>> {
+        SQLiteTransaction::new(self, options)
+    }
+
+    fn begin_driver_transaction(
+        &mut self,
+        options: TransactionOptions,
+    ) -> impl Future<Output = Result<SQLiteTransaction<'_>>> {
+        SQLiteTransaction::new(self, options)
     }
 }
@@ -1,7 +1,9 @@
 use crate::{SQLiteConnection, SQLitePrepared, SQLiteTransaction, sql_writer::SQLiteSqlWriter};
-use tank_core::Driver;
+use libsqlite3_sys::{SQLITE_BUSY, sqlite3_errstr};
+use std::{ffi::CStr, future::Future, time::Duration};
+use tank_core::{Driver, Error};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, Default)]
 pub struct SQLiteDriver {}
 
 impl SQLiteDriver {
@@ -17,7 +19,22 @@ impl Driver for SQLiteDriver {
     type Transaction<'c> = SQLiteTransaction<'c>;
 
     const NAME: &'static str = "sqlite";
+    const MAX_INSERT_ROWS: usize = 500;
+    /// Default `SQLITE_MAX_SQL_LENGTH`
+    const MAX_INSERT_LENGTH: usize = 1_000_000;
+    const ALTER_COLUMN: bool = false;
+
     fn sql_writer(&self) -> SQLiteSqlWriter {
         SQLiteSqlWriter {}
     }
+
+    /// The database is locked (`SQLITE_BUSY`), reported for writes inside a transaction.
+    fn is_retryable(&self, error: &Error) -> bool {
+        let busy = unsafe { CStr::from_ptr(sqlite3_errstr(SQLITE_BUSY)) }.to_string_lossy();
+        error.chain().any(|e| e.to_string().contains(&*busy))
+    }
+
+    fn sleep(duration: Duration) -> impl Future<Output = ()> + Send {
+        tokio::time::sleep(duration)
+    }
 }
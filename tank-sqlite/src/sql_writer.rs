@@ -1,8 +1,29 @@
 use std::{collections::BTreeMap, fmt::Write};
-use tank_core::{ColumnDef, ColumnRef, Context, Entity, SqlWriter, TableRef, Value};
+use tank_core::{
+    ColumnDef, ColumnRef, Context, Entity, Fragment, IsolationLevel, SqlWriter, TableName,
+    TableRef, TransactionOptions, Value, indoc::indoc,
+};
 
 pub struct SQLiteSqlWriter {}
 
+impl SQLiteSqlWriter {
+    /// Write the schema and the table name out of a catalog name column, SQLite has no schema
+    /// and tables are named "schema.table".
+    fn write_split_table_name(&self, out: &mut String, column: &str) {
+        let _ = write!(
+            out,
+            "CASE WHEN instr({column}, '.') > 0 THEN substr({column}, 1, instr({column}, '.') - 1) ELSE '' END, \
+            CASE WHEN instr({column}, '.') > 0 THEN substr({column}, instr({column}, '.') + 1) ELSE {column} END",
+        );
+    }
+
+    /// Write `table` as the string literal argument of a pragma function.
+    fn write_pragma_table(&self, out: &mut String, table: &TableName) {
+        let mut context = Context::new(Fragment::SqlSelectFrom, false);
+        self.write_value_string(&mut context, out, &table.to_string());
+    }
+}
+
 impl SqlWriter for SQLiteSqlWriter {
     fn as_dyn(&self) -> &dyn SqlWriter {
         self
@@ -26,8 +47,9 @@ impl SqlWriter for SQLiteSqlWriter {
     fn write_column_ref(&self, context: &mut Context, out: &mut String, value: &ColumnRef) {
         if context.qualify_columns && !value.table.is_empty() {
             out.push('"');
-            if !value.schema.is_empty() {
-                self.write_escaped(context, out, &value.schema, '"', "\"\"");
+            let schema = value.effective_schema();
+            if !schema.is_empty() {
+                self.write_escaped(context, out, &schema, '"', "\"\"");
                 out.push('.');
             }
             self.write_escaped(context, out, &value.table, '"', "\"\"");
@@ -39,8 +61,9 @@ impl SqlWriter for SQLiteSqlWriter {
     fn write_table_ref(&self, context: &mut Context, out: &mut String, value: &TableRef) {
         if self.alias_declaration(context) || value.alias.is_empty() {
             out.push('"');
-            if !value.schema.is_empty() {
-                self.write_escaped(context, out, &value.schema, '"', "\"\"");
+            let schema = value.effective_schema();
+            if !schema.is_empty() {
+                self.write_escaped(context, out, &schema, '"', "\"\"");
                 out.push('.');
             }
             self.write_escaped(context, out, &value.name, '"', "\"\"");
@@ -51,6 +74,16 @@ impl SqlWriter for SQLiteSqlWriter {
         }
     }
 
+    fn write_table_name(&self, context: &mut Context, out: &mut String, value: &TableName) {
+        out.push('"');
+        if !value.schema.is_empty() {
+            self.write_escaped(context, out, &value.schema, '"', "\"\"");
+            out.push('.');
+        }
+        self.write_escaped(context, out, &value.name, '"', "\"\"");
+        out.push('"');
+    }
+
     fn write_column_type(&self, _context: &mut Context, out: &mut String, value: &Value) {
         match value {
             Value::Boolean(..) => out.push_str("INTEGER"),
@@ -105,6 +138,36 @@ impl SqlWriter for SQLiteSqlWriter {
         out.push('\'');
     }
 
+    fn write_column_auto_increment(
+        &self,
+        _context: &mut Context,
+        _out: &mut String,
+        _column: &ColumnDef,
+    ) where
+        Self: Sized,
+    {
+        // A INTEGER PRIMARY KEY column is already an alias of the rowid
+    }
+
+    /// SQLite transactions are always serializable, the options choose when the database is
+    /// locked: `DEFERRED` (first access) for read only or deferrable transactions and for the
+    /// read committed and uncommitted levels, `IMMEDIATE` (write lock at the start) for repeatable
+    /// read, `EXCLUSIVE` for serializable.
+    fn write_transaction_begin_with(&self, out: &mut String, options: &TransactionOptions) {
+        out.push_str(if options.read_only || options.deferrable {
+            "BEGIN DEFERRED;"
+        } else {
+            match options.isolation {
+                None => "BEGIN;",
+                Some(IsolationLevel::ReadUncommitted | IsolationLevel::ReadCommitted) => {
+                    "BEGIN DEFERRED;"
+                }
+                Some(IsolationLevel::RepeatableRead) => "BEGIN IMMEDIATE;",
+                Some(IsolationLevel::Serializable) => "BEGIN EXCLUSIVE;",
+            }
+        });
+    }
+
     fn write_create_schema<E>(&self, _buff: &mut String, _if_not_exists: bool)
     where
         Self: Sized,
@@ -121,10 +184,114 @@ impl SqlWriter for SQLiteSqlWriter {
         // SQLite does not support schema
     }
 
+    fn write_alter_table_rename_table(&self, out: &mut String, table: &TableName, new_name: &str)
+    where
+        Self: Sized,
+    {
+        let mut context = Context::new(Fragment::SqlAlterTable, false);
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("ALTER TABLE ");
+        self.write_table_name(&mut context, out, table);
+        // The schema is part of the table name, see write_table_ref
+        out.push_str(" RENAME TO ");
+        self.write_table_name(
+            &mut context,
+            out,
+            &TableName::new(table.schema.as_str(), new_name),
+        );
+        out.push(';');
+    }
+
+    fn write_foreign_keys_enforced(&self, out: &mut String) {
+        out.push_str("PRAGMA foreign_keys;");
+    }
+
+    fn write_set_foreign_keys_enforced(&self, out: &mut String, enforced: bool) {
+        out.push_str(if enforced {
+            "PRAGMA foreign_keys = ON;"
+        } else {
+            "PRAGMA foreign_keys = OFF;"
+        });
+    }
+
+    fn write_foreign_key_check(&self, out: &mut String) {
+        out.push_str("PRAGMA foreign_key_check;");
+    }
+
     fn write_column_comments_statements<E>(&self, _context: &mut Context, _buff: &mut String)
     where
         Self: Sized,
         E: Entity,
     {
     }
+
+    fn write_select_tables(&self, out: &mut String, schema: Option<&str>) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("SELECT ");
+        self.write_split_table_name(out, "name");
+        out.push_str(
+            "\nFROM sqlite_master\nWHERE type = 'table' AND name NOT LIKE 'sqlite\\_%' ESCAPE '\\'",
+        );
+        if let Some(schema) = schema {
+            let mut context = Context::new(Fragment::SqlSelectWhere, false);
+            if schema.is_empty() {
+                out.push_str(" AND instr(name, '.') = 0");
+            } else {
+                out.push_str(" AND substr(name, 1, instr(name, '.') - 1) = ");
+                self.write_value_string(&mut context, out, schema);
+            }
+        }
+        out.push_str("\nORDER BY name;");
+    }
+
+    fn write_select_table_columns(&self, out: &mut String, table: &TableName) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(
+            "SELECT name, type, \"notnull\" = 0 AND pk = 0, dflt_value, NULL\nFROM pragma_table_info(",
+        );
+        self.write_pragma_table(out, table);
+        out.push_str(")\nORDER BY cid;");
+    }
+
+    fn write_select_table_constraints(&self, out: &mut String, table: &TableName) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("SELECT 'PRIMARY KEY', 'PRIMARY KEY', name, pk, NULL, NULL, NULL, NULL, NULL\nFROM pragma_table_info(");
+        self.write_pragma_table(out, table);
+        out.push_str(indoc! {"
+            )
+            WHERE pk > 0
+            UNION ALL
+            SELECT il.name, 'UNIQUE', ii.name, ii.seqno, NULL, NULL, NULL, NULL, NULL
+            FROM pragma_index_list("});
+        self.write_pragma_table(out, table);
+        out.push_str(indoc! {"
+            ) il, pragma_index_info(il.name) ii
+            WHERE il.origin = 'u'
+            UNION ALL
+            SELECT 'fk_' || fk.id, 'FOREIGN KEY', fk.\"from\", fk.seq, "});
+        self.write_split_table_name(out, "fk.\"table\"");
+        out.push_str(", fk.\"to\", fk.on_update, fk.on_delete\nFROM pragma_foreign_key_list(");
+        self.write_pragma_table(out, table);
+        out.push_str(") fk;");
+    }
+
+    fn write_select_table_indexes(&self, out: &mut String, table: &TableName) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str("SELECT il.name, il.\"unique\", ii.name\nFROM pragma_index_list(");
+        self.write_pragma_table(out, table);
+        out.push_str(indoc! {"
+            ) il, pragma_index_info(il.name) ii
+            WHERE il.origin = 'c'
+            ORDER BY il.name, ii.seqno;"});
+    }
 }
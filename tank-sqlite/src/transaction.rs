@@ -1,6 +1,6 @@
 use crate::{SQLiteConnection, SQLiteDriver};
 use tank_core::{
-    Driver, Executor, Result, SqlWriter, Transaction, future::TryFutureExt,
+    Driver, Executor, Result, SqlWriter, Transaction, TransactionOptions, future::TryFutureExt,
     impl_executor_transaction,
 };
 
@@ -9,14 +9,17 @@ pub struct SQLiteTransaction<'c> {
 }
 
 impl<'c> SQLiteTransaction<'c> {
-    pub async fn new(connection: &'c mut SQLiteConnection) -> Result<Self> {
+    pub async fn new(
+        connection: &'c mut SQLiteConnection,
+        options: TransactionOptions,
+    ) -> Result<Self> {
         let result = Self { connection };
         let mut sql = String::new();
         result
             .connection
             .driver()
             .sql_writer()
-            .write_transaction_begin(&mut sql);
+            .write_transaction_begin_with(&mut sql, &options);
         result.connection.execute(sql).await?;
         Ok(result)
     }
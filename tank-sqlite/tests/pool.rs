@@ -0,0 +1,183 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        path::Path,
+        sync::{
+            Arc, Mutex,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::Duration,
+    };
+    use tank::{Connection, Entity, Executor, Pool, Transaction};
+    use tank_sqlite::SQLiteDriver;
+    use tank_tests::{execute_tests, init_logs, silent_logs};
+    use tokio::{fs, time::sleep};
+
+    #[derive(Entity)]
+    #[tank(name = "pool_sorties")]
+    struct Sortie {
+        #[tank(primary_key)]
+        id: i32,
+        pilot: String,
+    }
+
+    static MUTEX: Mutex<()> = Mutex::new(());
+
+    #[tokio::test]
+    async fn pool() {
+        init_logs();
+        const DB_PATH: &'static str = "../target/debug/pool.sqlite";
+        let _guard = MUTEX.lock().unwrap();
+        if Path::new(DB_PATH).exists() {
+            fs::remove_file(DB_PATH).await.expect(
+                format!("Failed to remove existing test database file {}", DB_PATH).as_str(),
+            );
+        }
+        let acquired = Arc::new(AtomicUsize::new(0));
+        let counter = acquired.clone();
+        let pool = Pool::<SQLiteDriver>::builder(format!("sqlite://{}?mode=rwc", DB_PATH))
+            .min_size(1)
+            .max_size(2)
+            .acquire_timeout(Some(Duration::from_millis(100)))
+            .idle_timeout(Some(Duration::from_millis(50)))
+            .health_check(true)
+            .on_acquire(move |_| {
+                counter.fetch_add(1, Ordering::Relaxed);
+                Box::pin(async { Ok(()) })
+            })
+            .build()
+            .await
+            .expect("Could not create the pool");
+        assert_eq!(pool.idle(), 1);
+        assert_eq!(pool.size(), 1);
+
+        // Entities and transactions on the pooled connection
+        let mut connection = pool
+            .acquire()
+            .await
+            .expect("Could not acquire a connection");
+        Sortie::create_table(&mut connection, true, false)
+            .await
+            .expect("Failed to create Sortie table");
+        let mut transaction = connection
+            .begin()
+            .await
+            .expect("Could not begin a transaction");
+        Sortie {
+            id: 1,
+            pilot: "Maverick".into(),
+        }
+        .save(&mut transaction)
+        .await
+        .expect("Failed to save the sortie");
+        transaction
+            .commit()
+            .await
+            .expect("Could not commit the transaction");
+        assert_eq!(
+            Sortie::count(&mut connection, &true)
+                .await
+                .expect("Failed to count the sorties"),
+            1
+        );
+
+        // Maximum size
+        let other = pool
+            .acquire()
+            .await
+            .expect("Could not acquire a connection");
+        assert_eq!(pool.idle(), 0);
+        assert_eq!(pool.size(), 2);
+        silent_logs! {
+            assert!(pool.acquire().await.is_err(), "The pool is exhausted");
+        }
+        drop(other);
+        assert_eq!(pool.idle(), 1);
+        let mut other = pool
+            .acquire()
+            .await
+            .expect("Could not acquire a connection");
+        assert_eq!(
+            Sortie::count(&mut other, &true)
+                .await
+                .expect("Failed to count the sorties"),
+            1
+        );
+        drop(other);
+        drop(connection);
+        assert_eq!(pool.idle(), 2);
+        assert_eq!(acquired.load(Ordering::Relaxed), 3);
+
+        // Idle eviction keeps the minimum size
+        sleep(Duration::from_millis(60)).await;
+        pool.evict_idle();
+        assert_eq!(pool.idle(), 1);
+        assert_eq!(pool.size(), 1);
+
+        // Connections left inside a transaction are closed, rolling it back
+        let mut connection = pool
+            .acquire()
+            .await
+            .expect("Could not acquire a connection");
+        let mut transaction = connection
+            .begin()
+            .await
+            .expect("Could not begin a transaction");
+        Sortie {
+            id: 2,
+            pilot: "Goose".into(),
+        }
+        .save(&mut transaction)
+        .await
+        .expect("Failed to save the sortie");
+        drop(transaction);
+        assert!(connection.in_transaction());
+        drop(connection);
+        assert_eq!(pool.idle(), 0);
+        assert_eq!(pool.size(), 0);
+        let mut connection = pool
+            .acquire()
+            .await
+            .expect("Could not acquire a connection");
+        assert!(!connection.in_transaction());
+        assert_eq!(
+            Sortie::count(&mut connection, &true)
+                .await
+                .expect("Failed to count the sorties"),
+            1
+        );
+        drop(connection);
+
+        // Disconnected connections do not return to the pool
+        let connection = pool
+            .acquire()
+            .await
+            .expect("Could not acquire a connection");
+        connection
+            .disconnect()
+            .await
+            .expect("Could not disconnect the connection");
+        assert_eq!(pool.size(), 0);
+    }
+
+    #[tokio::test]
+    async fn pooled_connection() {
+        init_logs();
+        const DB_PATH: &'static str = "../target/debug/pooled.sqlite";
+        let _guard = MUTEX.lock().unwrap();
+        if Path::new(DB_PATH).exists() {
+            fs::remove_file(DB_PATH).await.expect(
+                format!("Failed to remove existing test database file {}", DB_PATH).as_str(),
+            );
+        }
+        let pool = Pool::<SQLiteDriver>::connect(format!("sqlite://{}?mode=rwc", DB_PATH))
+            .await
+            .expect("Could not create the pool");
+        execute_tests(
+            pool.acquire()
+                .await
+                .expect("Could not acquire a connection"),
+        )
+        .await;
+    }
+}
@@ -0,0 +1,211 @@
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, sync::Mutex};
+    use tank::{AsValue, Connection, Entity, Executor, Transaction, stream::TryStreamExt};
+    use tank_sqlite::SQLiteConnection;
+    use tank_tests::{init_logs, silent_logs};
+    use tokio::fs;
+
+    #[derive(Entity)]
+    #[tank(name = "units")]
+    struct UnitV1 {
+        #[tank(primary_key)]
+        id: i32,
+        name: String,
+    }
+
+    #[derive(Entity)]
+    #[tank(name = "units")]
+    struct UnitV2 {
+        #[tank(primary_key)]
+        id: i32,
+        name: Option<String>,
+    }
+
+    #[derive(Entity)]
+    #[tank(name = "pilots")]
+    struct Pilot {
+        #[tank(primary_key)]
+        id: i32,
+        #[tank(references = UnitV1::id, on_delete = cascade)]
+        unit: i32,
+    }
+
+    static MUTEX: Mutex<()> = Mutex::new(());
+
+    async fn foreign_keys(connection: &mut SQLiteConnection) -> bool {
+        let rows = connection
+            .fetch("PRAGMA foreign_keys;")
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("Failed to read the foreign keys state");
+        bool::try_from_value(rows[0].values[0].clone()).expect("Unexpected foreign keys state")
+    }
+
+    #[tokio::test]
+    async fn rebuild_foreign_keys() {
+        init_logs();
+        const DB_PATH: &'static str = "../target/debug/rebuild.sqlite";
+        let _guard = MUTEX.lock().unwrap();
+        if Path::new(DB_PATH).exists() {
+            fs::remove_file(DB_PATH).await.expect(
+                format!("Failed to remove existing test database file {}", DB_PATH).as_str(),
+            );
+        }
+        let mut connection =
+            SQLiteConnection::connect(format!("sqlite://{}?mode=rwc", DB_PATH).into())
+                .await
+                .expect("Could not open the database");
+        connection
+            .execute("PRAGMA foreign_keys = ON;")
+            .await
+            .expect("Failed to enable the foreign keys");
+        UnitV1::create_table(&mut connection, false, false)
+            .await
+            .expect("Failed to create the units");
+        Pilot::create_table(&mut connection, false, false)
+            .await
+            .expect("Failed to create the pilots");
+        UnitV1 {
+            id: 1,
+            name: "Red".into(),
+        }
+        .save(&mut connection)
+        .await
+        .expect("Failed to save the unit");
+        Pilot { id: 1, unit: 1 }
+            .save(&mut connection)
+            .await
+            .expect("Failed to save the pilot");
+
+        // Dropping the referenced table with the foreign keys enforced would delete the pilots
+        UnitV2::migrate_table(&mut connection, false)
+            .await
+            .expect("Failed to rebuild the units");
+        assert!(foreign_keys(&mut connection).await);
+        assert_eq!(
+            Pilot::find_many(&mut connection, &true, None)
+                .try_collect::<Vec<_>>()
+                .await
+                .expect("Failed to read the pilots")
+                .len(),
+            1
+        );
+        let live = connection
+            .describe_table(&UnitV2::table().into())
+            .await
+            .expect("Failed to describe the units")
+            .expect("The units must exist");
+        assert!(live.column("name").unwrap().nullable);
+
+        // A violation found once rebuilt rolls back the whole rebuild
+        connection
+            .execute("PRAGMA foreign_keys = OFF;")
+            .await
+            .expect("Failed to disable the foreign keys");
+        Pilot { id: 2, unit: 2 }
+            .save(&mut connection)
+            .await
+            .expect("Failed to save the orphan pilot");
+        connection
+            .execute("PRAGMA foreign_keys = ON;")
+            .await
+            .expect("Failed to enable the foreign keys");
+        silent_logs! {
+            assert!(UnitV1::migrate_table(&mut connection, true).await.is_err());
+        }
+        assert!(foreign_keys(&mut connection).await);
+        let live = connection
+            .describe_table(&UnitV1::table().into())
+            .await
+            .expect("Failed to describe the units")
+            .expect("The units must still exist");
+        assert!(live.column("name").unwrap().nullable);
+        let tables = connection
+            .tables(None)
+            .await
+            .expect("Failed to list the tables");
+        assert_eq!(tables.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn rebuild_inside_transaction() {
+        init_logs();
+        const DB_PATH: &'static str = "../target/debug/rebuild_transaction.sqlite";
+        let _guard = MUTEX.lock().unwrap();
+        if Path::new(DB_PATH).exists() {
+            fs::remove_file(DB_PATH).await.expect(
+                format!("Failed to remove existing test database file {}", DB_PATH).as_str(),
+            );
+        }
+        let mut connection =
+            SQLiteConnection::connect(format!("sqlite://{}?mode=rwc", DB_PATH).into())
+                .await
+                .expect("Could not open the database");
+        UnitV1::create_table(&mut connection, false, false)
+            .await
+            .expect("Failed to create the units");
+        Pilot::create_table(&mut connection, false, false)
+            .await
+            .expect("Failed to create the pilots");
+
+        // The foreign keys cannot be disabled inside a transaction
+        connection
+            .execute("PRAGMA foreign_keys = ON;")
+            .await
+            .expect("Failed to enable the foreign keys");
+        let mut transaction = connection
+            .begin()
+            .await
+            .expect("Failed to begin the transaction");
+        silent_logs! {
+            assert!(UnitV2::migrate_table(&mut transaction, false).await.is_err());
+        }
+        transaction
+            .rollback()
+            .await
+            .expect("Failed to roll back the transaction");
+
+        // Without them the rebuild runs in a savepoint and belongs to the transaction
+        connection
+            .execute("PRAGMA foreign_keys = OFF;")
+            .await
+            .expect("Failed to disable the foreign keys");
+        let mut transaction = connection
+            .begin()
+            .await
+            .expect("Failed to begin the transaction");
+        UnitV2::migrate_table(&mut transaction, false)
+            .await
+            .expect("Failed to rebuild the units inside the transaction");
+        transaction
+            .rollback()
+            .await
+            .expect("Failed to roll back the transaction");
+        let live = connection
+            .describe_table(&UnitV1::table().into())
+            .await
+            .expect("Failed to describe the units")
+            .expect("The units must exist");
+        assert!(!live.column("name").unwrap().nullable);
+
+        let mut transaction = connection
+            .begin()
+            .await
+            .expect("Failed to begin the transaction");
+        UnitV2::migrate_table(&mut transaction, false)
+            .await
+            .expect("Failed to rebuild the units inside the transaction");
+        transaction
+            .commit()
+            .await
+            .expect("Failed to commit the transaction");
+        let live = connection
+            .describe_table(&UnitV2::table().into())
+            .await
+            .expect("Failed to describe the units")
+            .expect("The units must exist");
+        assert!(live.column("name").unwrap().nullable);
+        assert!(!connection.in_transaction());
+    }
+}
@@ -0,0 +1,167 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        path::Path,
+        sync::{
+            Mutex,
+            atomic::{AtomicU32, Ordering},
+        },
+        time::Duration,
+    };
+    use tank::{
+        Connection, Driver, Entity, Error, IsolationLevel, RetryPolicy, SqlWriter, Transaction,
+        TransactionOptions,
+    };
+    use tank_sqlite::{SQLiteConnection, SQLiteDriver};
+    use tank_tests::{init_logs, silent_logs};
+    use tokio::{fs, time::sleep};
+
+    #[derive(Entity)]
+    #[tank(name = "retried_orders")]
+    struct Order {
+        #[tank(primary_key)]
+        id: i32,
+        quantity: i32,
+    }
+
+    static MUTEX: Mutex<()> = Mutex::new(());
+
+    #[tokio::test]
+    async fn transaction_retry() {
+        init_logs();
+        const DB_PATH: &'static str = "../target/debug/retry.sqlite";
+        let _guard = MUTEX.lock().unwrap();
+        if Path::new(DB_PATH).exists() {
+            fs::remove_file(DB_PATH).await.expect(
+                format!("Failed to remove existing test database file {}", DB_PATH).as_str(),
+            );
+        }
+        let url = format!("sqlite://{}?mode=rwc", DB_PATH);
+        let mut first = SQLiteConnection::connect(url.clone().into())
+            .await
+            .expect("Could not open the first connection");
+        let mut second = SQLiteConnection::connect(url.into())
+            .await
+            .expect("Could not open the second connection");
+        Order::create_table(&mut first, true, false)
+            .await
+            .expect("Failed to create Order table");
+
+        // The first connection holds the write lock until it commits
+        let mut transaction = first.begin().await.expect("Could not begin a transaction");
+        Order { id: 1, quantity: 3 }
+            .save(&mut transaction)
+            .await
+            .expect("Failed to save the first order");
+        let attempts = AtomicU32::new(0);
+        let (committed, result) = tokio::join!(
+            async {
+                sleep(Duration::from_millis(50)).await;
+                transaction.commit().await
+            },
+            second.transaction_with(
+                TransactionOptions::default(),
+                RetryPolicy {
+                    max_attempts: 20,
+                    initial_backoff: Duration::from_millis(5),
+                    max_backoff: Duration::from_millis(20),
+                },
+                |tx| {
+                    attempts.fetch_add(1, Ordering::Relaxed);
+                    Box::pin(async move {
+                        Order { id: 2, quantity: 7 }.save(tx).await?;
+                        Order::count(tx, &true).await
+                    })
+                },
+            )
+        );
+        committed.expect("Failed to commit the first transaction");
+        assert_eq!(result.expect("The second transaction must succeed"), 2);
+        assert!(
+            attempts.load(Ordering::Relaxed) > 1,
+            "The lock must be retried"
+        );
+
+        // Without retries the error is returned
+        let mut transaction = first.begin().await.expect("Could not begin a transaction");
+        Order { id: 3, quantity: 1 }
+            .save(&mut transaction)
+            .await
+            .expect("Failed to save the third order");
+        let error;
+        silent_logs! {
+            error = second
+                .transaction_with(TransactionOptions::default(), RetryPolicy::none(), |tx| {
+                    Box::pin(async move { Order { id: 4, quantity: 1 }.save(tx).await })
+                })
+                .await
+                .expect_err("The database is locked");
+        }
+        assert!(SQLiteDriver::new().is_retryable(&error), "{:#}", error);
+        assert!(!SQLiteDriver::new().is_retryable(&Error::msg("Insufficient funds")));
+        transaction
+            .commit()
+            .await
+            .expect("Failed to commit the third order");
+        assert_eq!(
+            Order::count(&mut second, &true)
+                .await
+                .expect("Failed to count the orders"),
+            3
+        );
+    }
+
+    #[test]
+    fn retry_policy_backoff() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(50),
+        };
+        assert_eq!(policy.backoff(1), Duration::from_millis(10));
+        assert_eq!(policy.backoff(2), Duration::from_millis(20));
+        assert_eq!(policy.backoff(3), Duration::from_millis(40));
+        assert_eq!(policy.backoff(4), Duration::from_millis(50));
+        assert_eq!(policy.backoff(100), Duration::from_millis(50));
+    }
+
+    #[test]
+    fn begin_with_locking_mode() {
+        let begin = |isolation, read_only| {
+            let mut out = String::new();
+            SQLiteDriver::new()
+                .sql_writer()
+                .write_transaction_begin_with(
+                    &mut out,
+                    &TransactionOptions {
+                        isolation,
+                        read_only,
+                        deferrable: false,
+                    },
+                );
+            out
+        };
+        assert_eq!(begin(None, false), "BEGIN;");
+        assert_eq!(begin(None, true), "BEGIN DEFERRED;");
+        assert_eq!(
+            begin(Some(IsolationLevel::ReadUncommitted), false),
+            "BEGIN DEFERRED;"
+        );
+        assert_eq!(
+            begin(Some(IsolationLevel::ReadCommitted), false),
+            "BEGIN DEFERRED;"
+        );
+        assert_eq!(
+            begin(Some(IsolationLevel::RepeatableRead), false),
+            "BEGIN IMMEDIATE;"
+        );
+        assert_eq!(
+            begin(Some(IsolationLevel::Serializable), false),
+            "BEGIN EXCLUSIVE;"
+        );
+        assert_eq!(
+            begin(Some(IsolationLevel::Serializable), true),
+            "BEGIN DEFERRED;"
+        );
+    }
+}
@@ -90,6 +90,28 @@ pub async fn aggregates<E: Executor>(executor: &mut E) {
         );
     }
 
+    // Entity::count, Entity::exists
+    {
+        let count = Values::count(executor, &true)
+            .await
+            .expect("Failed to count the values");
+        assert_eq!(count, COUNT as u64);
+        let count = Values::count(executor, &expr!(Values::value > #EXPECTED_AVG))
+            .await
+            .expect("Failed to count the values above average");
+        assert_eq!(count, COUNT as u64 / 2);
+        assert!(
+            Values::exists(executor, &expr!(Values::value == 42))
+                .await
+                .expect("Failed to check if 42 exists")
+        );
+        assert!(
+            !Values::exists(executor, &expr!(Values::value > #COUNT))
+                .await
+                .expect("Failed to check if values above the count exist")
+        );
+    }
+
     // SELECT *
     {
         {
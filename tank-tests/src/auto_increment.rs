@@ -0,0 +1,75 @@
+use std::sync::LazyLock;
+use tank::{Entity, Executor, Passive, expr, stream::TryStreamExt};
+use tokio::sync::Mutex;
+
+#[derive(Debug, Entity)]
+#[tank(schema = "testing", name = "radio_operators")]
+struct RadioOperator {
+    #[tank(primary_key, auto_increment)]
+    id: Passive<i64>,
+    #[tank(column_type = (mysql = "VARCHAR(64)"))]
+    callsign: String,
+}
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+pub async fn auto_increment<E: Executor>(executor: &mut E) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    RadioOperator::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop RadioOperator table");
+    RadioOperator::create_table(executor, false, true)
+        .await
+        .expect("Failed to create RadioOperator table");
+
+    // Insert with id write back
+    let mut viper = RadioOperator {
+        id: Passive::NotSet,
+        callsign: "Viper".into(),
+    };
+    let result = viper
+        .insert(executor)
+        .await
+        .expect("Failed to insert Viper");
+    assert_eq!(result.rows_affected, 1);
+    let Passive::Set(viper_id) = viper.id else {
+        panic!("The id of Viper was not written back");
+    };
+    let mut ghost = RadioOperator {
+        id: Passive::NotSet,
+        callsign: "Ghost".into(),
+    };
+    ghost
+        .insert(executor)
+        .await
+        .expect("Failed to insert Ghost");
+    let Passive::Set(ghost_id) = ghost.id else {
+        panic!("The id of Ghost was not written back");
+    };
+    assert!(ghost_id > viper_id);
+
+    // Explicit id
+    let mut hammer = RadioOperator {
+        id: Passive::Set(1000),
+        callsign: "Hammer".into(),
+    };
+    hammer
+        .insert(executor)
+        .await
+        .expect("Failed to insert Hammer");
+    assert_eq!(hammer.id, Passive::Set(1000));
+
+    // Read back
+    let operator = RadioOperator::find_pk(executor, &ghost.primary_key())
+        .await
+        .expect("Failed to query Ghost")
+        .expect("Ghost must exist");
+    assert_eq!(operator.callsign, "Ghost");
+    let operators = RadioOperator::find_many(executor, &expr!(RadioOperator::id < 1000), None)
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("Failed to query the operators");
+    assert_eq!(operators.len(), 2);
+}
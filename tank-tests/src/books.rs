@@ -2,8 +2,8 @@
 use std::{collections::HashSet, pin::pin, sync::LazyLock};
 #[allow(unused_imports)]
 use tank::{
-    AsValue, DataSet, Driver, Entity, Executor, Passive, Query, QueryResult, RowLabeled, SqlWriter,
-    Value, cols, expr, join,
+    AsValue, DataSet, Driver, Entity, EntityRegistry, Executor, Passive, Query, QueryResult,
+    Related, RowLabeled, SqlWriter, Value, cols, expr, join,
     stream::{StreamExt, TryStreamExt},
 };
 use tokio::sync::Mutex;
@@ -38,18 +38,17 @@ pub async fn books<E: Executor>(executor: &mut E) {
     let _lock = MUTEX.lock().await;
 
     // Setup
-    Book::drop_table(executor, true, false)
+    let registry = EntityRegistry::<E::Driver>::new()
+        .entity::<Book>()
+        .entity::<Author>();
+    registry
+        .drop_all(executor, true)
         .await
-        .expect("Failed to drop Book table");
-    Author::drop_table(executor, true, false)
+        .expect("Failed to drop the Book and Author tables");
+    registry
+        .create_all(executor, false)
         .await
-        .expect("Failed to drop Author table");
-    Author::create_table(executor, false, true)
-        .await
-        .expect("Failed to create Author table");
-    Book::create_table(executor, false, true)
-        .await
-        .expect("Failed to create Book table");
+        .expect("Failed to create the Book and Author tables");
 
     // Author objects
     let authors = vec![
@@ -320,6 +319,173 @@ pub async fn books<E: Executor>(executor: &mut E) {
         ])
     );
 
+    // Belongs to
+    let book = Book::find_one(executor, &expr!(Book::title == "Hogwarts 2033"))
+        .await
+        .expect("Failed to query book by title")
+        .expect("The book must exist");
+    let author: Option<Author> = book
+        .belongs_to(executor, Book::author)
+        .await
+        .expect("Failed to load the author of the book");
+    assert_eq!(author.map(|v| v.name), Some("J.K. Rowling".into()));
+    let co_author: Option<Author> = book
+        .belongs_to(executor, Book::co_author)
+        .await
+        .expect("Failed to load the co-author of the book");
+    assert_eq!(
+        co_author.map(|v| v.name),
+        Some("Dmitrij Gluchovskij".into())
+    );
+
+    // Has many
+    let authors = Author::find_many(executor, &true, None)
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("Failed to query the authors");
+    let rowling = authors
+        .iter()
+        .find(|v| v.name == "J.K. Rowling")
+        .expect("Rowling must exist");
+    let titles = rowling
+        .has_many::<Book>(executor, Book::author)
+        .map_ok(|v| v.title)
+        .try_collect::<HashSet<_>>()
+        .await
+        .expect("Failed to load the books of the author");
+    assert_eq!(
+        titles,
+        HashSet::from_iter([
+            "Harry Potter and the Philosopher's Stone".to_string(),
+            "Harry Potter and the Deathly Hallows".to_string(),
+            "Hogwarts 2033".to_string(),
+        ])
+    );
+    assert!(
+        rowling
+            .belongs_to::<Author>(executor, Book::author)
+            .await
+            .is_err(),
+        "Author has no column referencing authors"
+    );
+
+    // Eager loading
+    let written = Author::load_has_many::<Book>(executor, &authors, Book::author)
+        .await
+        .expect("Failed to eager load the books of the authors");
+    let written = authors
+        .iter()
+        .zip(written)
+        .map(|(author, books)| (author.name.as_str(), books.len()))
+        .collect::<HashSet<_>>();
+    assert_eq!(
+        written,
+        HashSet::from_iter([
+            ("J.K. Rowling", 3),
+            ("J.R.R. Tolkien", 1),
+            ("Dmitrij Gluchovskij", 1),
+            ("Linus Torvalds", 0),
+        ])
+    );
+    let all_books = Book::find_many(executor, &true, None)
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("Failed to query the books");
+    let co_authors = Book::load_belongs_to::<Author>(executor, &all_books, Book::co_author)
+        .await
+        .expect("Failed to eager load the co-authors of the books");
+    assert_eq!(co_authors.len(), all_books.len());
+    let co_authors = all_books
+        .iter()
+        .zip(co_authors)
+        .filter_map(|(book, author)| Some((book.title.as_str(), author?.name)))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        co_authors,
+        vec![("Hogwarts 2033", "Dmitrij Gluchovskij".to_string())]
+    );
+    let rowling = authors
+        .iter()
+        .find(|v| v.name == "J.K. Rowling")
+        .expect("Rowling must exist");
+    let written =
+        Author::load_has_many::<Book>(executor, &[rowling.clone(), rowling.clone()], Book::author)
+            .await
+            .expect("Failed to eager load the books of the same author twice");
+    assert_eq!(
+        written.iter().map(Vec::len).collect::<Vec<_>>(),
+        [3, 3],
+        "Every parent sharing a key must get the children"
+    );
+
+    // Eager loading with a foreign key narrower than the key it references
+    #[derive(Entity, Debug, Clone, PartialEq)]
+    #[tank(schema = "testing", name = "depots")]
+    struct Depot {
+        #[tank(primary_key)]
+        id: i64,
+        name: String,
+    }
+    #[derive(Entity, Debug, Clone, PartialEq)]
+    #[tank(schema = "testing", name = "shipments")]
+    struct Shipment {
+        #[tank(primary_key)]
+        id: i32,
+        #[tank(references = Depot::id, column_type = (mysql = "BIGINT", duckdb = "BIGINT"))]
+        depot: i32,
+    }
+    Shipment::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop Shipment table");
+    Depot::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop Depot table");
+    Depot::create_table(executor, false, true)
+        .await
+        .expect("Failed to create Depot table");
+    Shipment::create_table(executor, false, true)
+        .await
+        .expect("Failed to create Shipment table");
+    let depots = [
+        Depot {
+            id: 1,
+            name: "North".into(),
+        },
+        Depot {
+            id: 2,
+            name: "South".into(),
+        },
+    ];
+    Depot::insert_many(executor, &depots)
+        .await
+        .expect("Failed to insert the depots");
+    let shipments = [
+        Shipment { id: 1, depot: 1 },
+        Shipment { id: 2, depot: 1 },
+        Shipment { id: 3, depot: 2 },
+    ];
+    Shipment::insert_many(executor, &shipments)
+        .await
+        .expect("Failed to insert the shipments");
+    let loaded = Depot::load_has_many::<Shipment>(executor, &depots, Shipment::depot)
+        .await
+        .expect("Failed to eager load the shipments of the depots");
+    assert_eq!(loaded.iter().map(Vec::len).collect::<Vec<_>>(), [2, 1]);
+    let loaded = Shipment::load_belongs_to::<Depot>(executor, &shipments, Shipment::depot)
+        .await
+        .expect("Failed to eager load the depots of the shipments");
+    assert_eq!(
+        loaded
+            .into_iter()
+            .map(|v| v.map(|v| v.name))
+            .collect::<Vec<_>>(),
+        [
+            Some("North".to_string()),
+            Some("North".to_string()),
+            Some("South".to_string())
+        ]
+    );
+
     #[cfg(not(feature = "disable-references"))]
     {
         // Insert book violating referential integrity
@@ -0,0 +1,72 @@
+use crate::silent_logs;
+use std::sync::LazyLock;
+use tank::{Connection, Entity};
+use tokio::sync::Mutex;
+
+#[derive(Debug, Entity)]
+#[tank(schema = "testing", name = "telemetry")]
+struct Reading {
+    #[tank(primary_key)]
+    id: i32,
+    sensor: String,
+    value: f64,
+}
+
+impl Reading {
+    fn sample(id: i32) -> Self {
+        Self {
+            id,
+            sensor: format!("sensor_{:04}", id % 97),
+            value: id as f64 * 0.25,
+        }
+    }
+}
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+pub async fn chunks<C: Connection>(connection: &mut C) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    Reading::drop_table(connection, true, false)
+        .await
+        .expect("Failed to drop Reading table");
+    Reading::create_table(connection, false, true)
+        .await
+        .expect("Failed to create Reading table");
+
+    // Larger than a single chunk of any driver
+    const COUNT: i32 = 2_500;
+    let readings = (0..COUNT).map(Reading::sample).collect::<Vec<_>>();
+    let result = Reading::insert_many(connection, &readings)
+        .await
+        .expect("Failed to insert the readings");
+    assert_eq!(result.rows_affected, COUNT as u64);
+    assert_eq!(
+        Reading::count(connection, &true)
+            .await
+            .expect("Failed to count the readings"),
+        COUNT as u64
+    );
+
+    // All or nothing
+    #[cfg(not(feature = "disable-transactions"))]
+    {
+        let readings = (COUNT..COUNT * 2)
+            .chain([0])
+            .map(Reading::sample)
+            .collect::<Vec<_>>();
+        let result;
+        silent_logs! {
+            result = connection.append_atomic(&readings).await;
+        }
+        assert!(result.is_err(), "The duplicated primary key must fail");
+        assert_eq!(
+            Reading::count(connection, &true)
+                .await
+                .expect("Failed to count the readings"),
+            COUNT as u64,
+            "No chunk must be inserted"
+        );
+    }
+}
@@ -0,0 +1,56 @@
+use std::sync::LazyLock;
+use tank::{Driver, Entity, Executor, TableName, write_entity};
+use tokio::sync::Mutex;
+
+#[derive(Debug, Entity)]
+#[tank(schema = "testing", name = "radio_channels")]
+struct RadioChannel {
+    #[tank(primary_key)]
+    id: i64,
+    /// Label shown on the radio
+    #[tank(unique, column_type = (mysql = "VARCHAR(64)"))]
+    label: String,
+    frequency: f64,
+    encrypted: Option<bool>,
+}
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+pub async fn entity_codegen<E: Executor>(executor: &mut E) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    RadioChannel::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop RadioChannel table");
+    RadioChannel::create_table(executor, false, true)
+        .await
+        .expect("Failed to create RadioChannel table");
+
+    // Generate
+    let table = executor
+        .describe_table(&TableName::new("testing", "radio_channels"))
+        .await
+        .expect("Failed to describe the radio channels")
+        .expect("Radio channels table must exist");
+    let mut source = String::new();
+    write_entity(&mut source, &table, <E::Driver as Driver>::NAME);
+    assert!(source.contains("pub struct RadioChannels {"), "{}", source);
+    assert!(
+        source.contains("#[tank(primary_key)]\n    pub id: i64,"),
+        "{}",
+        source
+    );
+    assert!(source.contains("unique"), "{}", source);
+    assert!(source.contains("pub label: "), "{}", source);
+    assert!(source.contains("pub frequency: f64,"), "{}", source);
+    assert!(source.contains("pub encrypted: Option<"), "{}", source);
+    if <E::Driver as Driver>::NAME != "sqlite" {
+        // SQLite does not store column comments
+        assert!(
+            source.contains("/// Label shown on the radio\n"),
+            "{}",
+            source
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use std::sync::LazyLock;
+use tank::{Entity, Executor, Tracked, expr, stream::TryStreamExt};
+use tokio::sync::Mutex;
+
+#[derive(Debug, Entity)]
+#[tank(schema = "testing", name = "order_lines")]
+struct OrderLine {
+    #[tank(primary_key)]
+    id: i32,
+    quantity: i32,
+    unit_price: i64,
+    #[tank(generated = quantity * unit_price, stored)]
+    total: i64,
+    #[tank(generated = unit_price * 2)]
+    double_price: i64,
+}
+
+impl OrderLine {
+    fn new(id: i32, quantity: i32, unit_price: i64) -> Self {
+        Self {
+            id,
+            quantity,
+            unit_price,
+            total: 0,
+            double_price: 0,
+        }
+    }
+}
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+pub async fn generated<E: Executor>(executor: &mut E) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    OrderLine::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop OrderLine table");
+    OrderLine::create_table(executor, false, true)
+        .await
+        .expect("Failed to create OrderLine table");
+
+    // Insert
+    OrderLine::insert_one(executor, &OrderLine::new(1, 3, 40))
+        .await
+        .expect("Failed to insert a order line");
+    OrderLine::insert_many(
+        executor,
+        &[OrderLine::new(2, 10, 5), OrderLine::new(3, 1, 999)],
+    )
+    .await
+    .expect("Failed to insert multiple order lines");
+    let lines = OrderLine::find_many(executor, &true, None)
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("Failed to query the order lines");
+    assert_eq!(lines.len(), 3);
+    for line in &lines {
+        assert_eq!(line.total, line.quantity as i64 * line.unit_price);
+        assert_eq!(line.double_price, line.unit_price * 2);
+    }
+
+    // Save
+    let mut line = OrderLine::find_one(executor, &expr!(OrderLine::id == 1))
+        .await
+        .expect("Failed to query the order line")
+        .expect("Order line 1 must exist");
+    assert_eq!(line.total, 120);
+    line.quantity = 4;
+    line.save(executor)
+        .await
+        .expect("Failed to save the order line");
+    let line = OrderLine::find_pk(executor, &line.primary_key())
+        .await
+        .expect("Failed to query the order line")
+        .expect("Order line 1 must exist");
+    assert_eq!(line.total, 160);
+
+    // Tracked update
+    let mut line = Tracked::new(line);
+    line.unit_price = 50;
+    assert_eq!(line.changed_columns().len(), 1);
+    line.update(executor)
+        .await
+        .expect("Failed to update the order line");
+    let line = OrderLine::find_pk(executor, &line.primary_key())
+        .await
+        .expect("Failed to query the order line")
+        .expect("Order line 1 must exist");
+    assert_eq!(line.total, 200);
+    assert_eq!(line.double_price, 100);
+}
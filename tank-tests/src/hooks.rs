@@ -0,0 +1,170 @@
+use std::sync::{
+    LazyLock,
+    atomic::{AtomicU64, Ordering},
+};
+use tank::{Entity, EntityHooks, Error, Executor, Result, RowsAffected, expr};
+use tokio::sync::Mutex;
+
+#[derive(Debug, Entity)]
+#[tank(schema = "testing", name = "call_signs", hooks)]
+struct CallSign {
+    #[tank(primary_key)]
+    id: i32,
+    #[tank(column_type = (mysql = "VARCHAR(64)"))]
+    name: String,
+    locked: bool,
+}
+
+static INSERTED: AtomicU64 = AtomicU64::new(0);
+
+impl EntityHooks for CallSign {
+    fn before_insert(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(Error::msg("The call sign name cannot be empty"));
+        }
+        Ok(())
+    }
+    fn after_insert(&self, result: &RowsAffected) -> Result<()> {
+        assert!(result.rows_affected > 0);
+        INSERTED.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+    fn before_save(&self) -> Result<()> {
+        self.before_insert()
+    }
+    fn before_delete(&self) -> Result<()> {
+        if self.locked {
+            return Err(Error::msg("The call sign is locked"));
+        }
+        Ok(())
+    }
+    fn after_load(&mut self) -> Result<()> {
+        self.name = self.name.to_uppercase();
+        Ok(())
+    }
+}
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+pub async fn hooks<E: Executor>(executor: &mut E) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    CallSign::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop CallSign table");
+    CallSign::create_table(executor, false, true)
+        .await
+        .expect("Failed to create CallSign table");
+    INSERTED.store(0, Ordering::Relaxed);
+
+    // Insert
+    let mut viper = CallSign {
+        id: 1,
+        name: "viper".into(),
+        locked: false,
+    };
+    viper
+        .insert(executor)
+        .await
+        .expect("Failed to insert viper");
+    assert!(
+        CallSign::insert_one(
+            executor,
+            &CallSign {
+                id: 6,
+                name: String::new(),
+                locked: false,
+            },
+        )
+        .await
+        .is_err(),
+        "Blank name must be rejected by insert_one"
+    );
+    CallSign::insert_many(
+        executor,
+        &[
+            CallSign {
+                id: 2,
+                name: "ghost".into(),
+                locked: true,
+            },
+            CallSign {
+                id: 3,
+                name: "hammer".into(),
+                locked: false,
+            },
+        ],
+    )
+    .await
+    .expect("Failed to insert ghost and hammer");
+    assert_eq!(INSERTED.load(Ordering::Relaxed), 3);
+    CallSign::insert_one(
+        executor,
+        &CallSign {
+            id: 7,
+            name: "maverick".into(),
+            locked: false,
+        },
+    )
+    .await
+    .expect("Failed to insert maverick");
+    assert_eq!(INSERTED.load(Ordering::Relaxed), 4);
+
+    // Rejected before any query
+    let result = CallSign::insert_many(
+        executor,
+        &[
+            CallSign {
+                id: 4,
+                name: "rook".into(),
+                locked: false,
+            },
+            CallSign {
+                id: 5,
+                name: " ".into(),
+                locked: false,
+            },
+        ],
+    )
+    .await;
+    assert!(result.is_err(), "Blank name must be rejected");
+    let rook = CallSign::find_pk(executor, &(&4,))
+        .await
+        .expect("Failed to query rook");
+    assert!(rook.is_none(), "No row must be inserted when a hook fails");
+    assert_eq!(INSERTED.load(Ordering::Relaxed), 4);
+
+    // Load
+    let mut viper = CallSign::find_pk(executor, &(&1,))
+        .await
+        .expect("Failed to query viper")
+        .expect("Viper must exist");
+    assert_eq!(viper.name, "VIPER");
+
+    // Save
+    viper.name = String::new();
+    assert!(viper.save(executor).await.is_err());
+    let viper = CallSign::find_pk(executor, &(&1,))
+        .await
+        .expect("Failed to query viper")
+        .expect("Viper must exist");
+    assert_eq!(viper.name, "VIPER");
+
+    // Delete
+    let ghost = CallSign::find_one(executor, &expr!(CallSign::locked == true))
+        .await
+        .expect("Failed to query ghost")
+        .expect("Ghost must exist");
+    assert!(ghost.delete(executor).await.is_err());
+    viper
+        .delete(executor)
+        .await
+        .expect("Failed to delete viper");
+    assert!(
+        CallSign::find_pk(executor, &(&2,))
+            .await
+            .expect("Failed to query ghost")
+            .is_some()
+    );
+}
@@ -0,0 +1,134 @@
+use std::sync::LazyLock;
+use tank::{Driver, Entity, Executor, SqlWriter, TableName, Value};
+use tokio::sync::Mutex;
+
+#[derive(Debug, Entity)]
+#[tank(schema = "testing", name = "squadrons")]
+struct Squadron {
+    #[tank(primary_key)]
+    id: i32,
+    #[tank(unique, column_type = (mysql = "VARCHAR(64)"))]
+    name: String,
+    base: Option<String>,
+}
+
+#[derive(Debug, Entity)]
+#[tank(schema = "testing", name = "crew", unique = (Self::squadron, Self::callsign))]
+struct Crew {
+    #[tank(primary_key)]
+    id: i64,
+    #[cfg_attr(
+        not(feature = "disable-referential-actions"),
+        tank(references = Squadron::id, on_delete = cascade)
+    )]
+    #[cfg_attr(
+        feature = "disable-referential-actions",
+        tank(references = Squadron::id)
+    )]
+    squadron: i32,
+    #[tank(column_type = (mysql = "VARCHAR(64)"))]
+    callsign: String,
+    #[tank(default = 1)]
+    rank: i16,
+}
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+pub async fn introspection<E: Executor>(executor: &mut E) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    Crew::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop Crew table");
+    Squadron::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop Squadron table");
+    Squadron::create_table(executor, false, true)
+        .await
+        .expect("Failed to create Squadron table");
+    Crew::create_table(executor, false, true)
+        .await
+        .expect("Failed to create Crew table");
+    let mut query = String::from("CREATE INDEX crew_rank_idx ON ");
+    let writer = executor.driver().sql_writer();
+    writer.write_table_ref(&mut Default::default(), &mut query, Crew::table());
+    query.push_str(" (");
+    writer.write_identifier_quoted(&mut Default::default(), &mut query, "rank");
+    query.push_str(");");
+    executor
+        .execute(query)
+        .await
+        .expect("Failed to create the rank index");
+
+    // Tables
+    let tables = executor
+        .tables(Some("testing"))
+        .await
+        .expect("Failed to list the tables");
+    assert!(tables.contains(&TableName::new("testing", "squadrons")));
+    assert!(tables.contains(&TableName::new("testing", "crew")));
+    assert!(
+        executor
+            .describe_table(&TableName::new("testing", "missing_table"))
+            .await
+            .expect("Failed to describe a missing table")
+            .is_none()
+    );
+
+    // Columns
+    let squadrons = executor
+        .describe_table(&TableName::new("testing", "squadrons"))
+        .await
+        .expect("Failed to describe the squadrons")
+        .expect("Squadrons table must exist");
+    assert_eq!(
+        squadrons
+            .columns
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect::<Vec<_>>(),
+        ["id", "name", "base"]
+    );
+    assert!(!squadrons.column("name").unwrap().nullable);
+    assert!(squadrons.column("base").unwrap().nullable);
+    assert!(matches!(
+        squadrons.column("name").unwrap().value,
+        Value::Varchar(..)
+    ));
+    assert_eq!(squadrons.primary_key, ["id"]);
+    assert_eq!(squadrons.unique.len(), 1);
+    assert_eq!(squadrons.unique[0].columns, ["name"]);
+
+    // Constraints and indexes
+    let crew = executor
+        .describe_table(&TableName::new("testing", "crew"))
+        .await
+        .expect("Failed to describe the crew")
+        .expect("Crew table must exist");
+    assert_eq!(crew.primary_key, ["id"]);
+    assert_eq!(crew.unique.len(), 1);
+    assert_eq!(crew.unique[0].columns, ["squadron", "callsign"]);
+    assert!(
+        crew.column("rank")
+            .unwrap()
+            .default
+            .as_ref()
+            .is_some_and(|v| v.contains('1'))
+    );
+    let [foreign_key] = crew.foreign_keys.as_slice() else {
+        panic!("Crew must have one foreign key: {:?}", crew.foreign_keys);
+    };
+    assert_eq!(foreign_key.columns, ["squadron"]);
+    assert_eq!(foreign_key.references.name, "squadrons");
+    assert_eq!(foreign_key.references_columns, ["id"]);
+    #[cfg(not(feature = "disable-referential-actions"))]
+    assert_eq!(foreign_key.on_delete, Some(tank::Action::Cascade));
+    let index = crew
+        .indexes
+        .iter()
+        .find(|v| v.name == "crew_rank_idx")
+        .expect("The rank index must be reported");
+    assert_eq!(index.columns, ["rank"]);
+    assert!(!index.unique);
+}
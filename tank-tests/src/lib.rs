@@ -1,31 +1,63 @@
 mod aggregates;
 mod arrays1;
 mod arrays2;
+mod auto_increment;
 mod books;
+mod chunks;
 mod complex;
+mod entity_codegen;
+mod generated;
+mod hooks;
 mod insane;
 mod interval;
+mod introspection;
 mod limits;
+mod migrations;
 mod multiple;
 mod operations;
 mod readme;
+mod registry;
+mod schema_diff;
 mod shopping;
 mod simple;
+mod tenants;
 mod trade;
 mod transaction1;
+mod transaction2;
+mod transaction3;
+mod transaction4;
+mod upsert;
 mod user;
+mod validation;
+mod views;
 
 use crate::{
+    auto_increment::auto_increment,
     books::books,
+    chunks::chunks,
     complex::complex,
+    entity_codegen::entity_codegen,
+    generated::generated,
+    hooks::hooks,
     insane::insane,
+    introspection::introspection,
     limits::limits,
+    migrations::migrations,
     operations::{advanced_operations, operations},
+    registry::registry,
+    schema_diff::schema_diff,
     shopping::shopping,
     simple::simple,
+    tenants::tenants,
     trade::{trade_multiple, trade_simple},
     transaction1::transaction1,
+    transaction2::transaction2,
+    transaction3::transaction3,
+    transaction4::transaction4,
+    upsert::upsert,
     user::users,
+    validation::validation,
+    views::views,
 };
 use aggregates::aggregates;
 
@@ -54,7 +86,10 @@ pub fn init_logs() {
     let _ = logger.try_init();
 }
 
-pub async fn execute_tests<C: Connection>(mut connection: C) {
+pub async fn execute_tests<C: Connection>(mut connection: C)
+where
+    C::Driver: Clone + Send,
+{
     simple(&mut connection).await;
     trade_simple(&mut connection).await;
     trade_multiple(&mut connection).await;
@@ -63,10 +98,29 @@ pub async fn execute_tests<C: Connection>(mut connection: C) {
     books(&mut connection).await;
     complex(&mut connection).await;
     insane(&mut connection).await;
+    generated(&mut connection).await;
+    auto_increment(&mut connection).await;
+    hooks(&mut connection).await;
+    validation(&mut connection).await;
+    tenants(&mut connection).await;
+    upsert(&mut connection).await;
+    chunks(&mut connection).await;
+    introspection(&mut connection).await;
+    schema_diff(&mut connection).await;
+    entity_codegen(&mut connection).await;
+    migrations(&mut connection).await;
+    views(&mut connection).await;
+    registry(&mut connection).await;
     limits(&mut connection).await;
     #[cfg(not(feature = "disable-multiple-statements"))]
     multiple(&mut connection).await;
     #[cfg(not(feature = "disable-transactions"))]
+    transaction2(&mut connection).await;
+    #[cfg(not(feature = "disable-transactions"))]
+    transaction3(&mut connection).await;
+    #[cfg(not(feature = "disable-transactions"))]
+    transaction4(&mut connection).await;
+    #[cfg(not(feature = "disable-transactions"))]
     transaction1(&mut connection).await;
     #[cfg(not(feature = "disable-intervals"))]
     interval(&mut connection).await;
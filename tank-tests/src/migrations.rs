@@ -0,0 +1,296 @@
+use crate::silent_logs;
+use std::{env, fs, process, sync::LazyLock};
+use tank::{
+    Connection, Driver, Entity, Migration, MigrationState, Migrator, SchemaChange, SqlWriter,
+    Value, expr, with_schema,
+};
+use time::macros::datetime;
+use tokio::sync::Mutex;
+
+#[derive(Debug, Entity)]
+#[tank(schema = "testing", name = "drones")]
+struct Drone {
+    #[tank(primary_key)]
+    id: i32,
+    #[tank(column_type = (mysql = "VARCHAR(64)"))]
+    model: String,
+}
+
+#[derive(Debug, Entity)]
+#[tank(schema = "testing", name = "drones")]
+struct DroneV2 {
+    #[tank(primary_key)]
+    id: i32,
+    #[tank(column_type = (mysql = "VARCHAR(64)"))]
+    model: String,
+    range: Option<i32>,
+}
+
+#[derive(Debug, Entity)]
+#[tank(name = "beacons")]
+struct Beacon {
+    #[tank(primary_key)]
+    id: i32,
+}
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+fn drone_migrations<C: Connection>(executor: &C) -> Migrator<C> {
+    let writer = executor.driver().sql_writer();
+    let range = &DroneV2::columns()[2];
+    let mut add_range = String::new();
+    writer.write_schema_change(
+        &mut add_range,
+        DroneV2::table(),
+        &SchemaChange::AddColumn(range),
+    );
+    let mut drop_range = String::new();
+    writer.write_schema_change(
+        &mut drop_range,
+        DroneV2::table(),
+        &SchemaChange::DropColumn(range.name().into()),
+    );
+    Migrator::new()
+        .migration(
+            Migration::new(1, "create_drones", |executor| {
+                Box::pin(Drone::create_table(executor, false, true))
+            })
+            .down(|executor| Box::pin(Drone::drop_table(executor, false, false))),
+        )
+        .migration(Migration::sql(2, "drones_range", add_range).down_sql(drop_range))
+}
+
+fn beacon_migrations<C: Connection>() -> Migrator<C> {
+    Migrator::new().migration(
+        Migration::new(1, "create_beacons", |executor| {
+            Box::pin(Beacon::create_table(executor, false, true))
+        })
+        .down(|executor| Box::pin(Beacon::drop_table(executor, false, false))),
+    )
+}
+
+pub async fn migrations<C: Connection>(executor: &mut C)
+where
+    C::Driver: Clone + Send,
+{
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    Drone::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop Drone table");
+    executor
+        .execute("DROP TABLE IF EXISTS tank_migrations;")
+        .await
+        .expect("Failed to drop the migrations history");
+    executor
+        .execute("DROP TABLE IF EXISTS tank_migrations_lock;")
+        .await
+        .expect("Failed to drop the migrations lock");
+
+    // Apply
+    let migrator = drone_migrations(executor);
+    let status = migrator
+        .status(executor)
+        .await
+        .expect("Failed to read the migrations status");
+    assert!(status.iter().all(|v| v.state == MigrationState::Pending));
+    assert_eq!(
+        migrator
+            .apply(executor)
+            .await
+            .expect("Failed to apply the migrations"),
+        [1, 2]
+    );
+    DroneV2::insert_one(
+        executor,
+        &DroneV2 {
+            id: 1,
+            model: "Reaper".into(),
+            range: Some(1850),
+        },
+    )
+    .await
+    .expect("Failed to insert the drone");
+    let status = migrator
+        .status(executor)
+        .await
+        .expect("Failed to read the migrations status");
+    assert_eq!(status.len(), 2);
+    assert!(
+        status
+            .iter()
+            .all(|v| v.state == MigrationState::Applied && v.applied_at.is_some())
+    );
+    let status = with_schema("tenant_migrations", migrator.status(executor))
+        .await
+        .expect("Failed to read the migrations status inside a tenant schema");
+    assert!(status.iter().all(|v| v.state == MigrationState::Applied));
+    assert!(
+        migrator
+            .apply(executor)
+            .await
+            .expect("Failed to apply the migrations")
+            .is_empty()
+    );
+
+    // A failing migration is not recorded and releases the lock
+    let failing =
+        drone_migrations(executor).migration(Migration::sql(3, "broken", "THIS IS NOT SQL;"));
+    silent_logs! {
+        assert!(failing.apply(executor).await.is_err());
+    }
+    let status = failing
+        .status(executor)
+        .await
+        .expect("Failed to read the migrations status");
+    assert_eq!(status[2].state, MigrationState::Pending);
+
+    // Modified migrations are rejected
+    let modified = Migrator::new().migration(Migration::sql(1, "create_drones", "SELECT 1;"));
+    let status = modified
+        .status(executor)
+        .await
+        .expect("Failed to read the migrations status");
+    assert_eq!(status[0].state, MigrationState::Modified);
+    assert_eq!(status[1].state, MigrationState::Unknown);
+    silent_logs! {
+        assert!(modified.apply(executor).await.is_err());
+    }
+
+    // Lock held by another instance
+    let mut take_lock = String::new();
+    let writer = executor.driver().sql_writer();
+    take_lock.push_str("INSERT INTO ");
+    writer.write_identifier_quoted(
+        &mut Default::default(),
+        &mut take_lock,
+        "tank_migrations_lock",
+    );
+    take_lock.push_str(" VALUES (1, ");
+    writer.write_value(
+        &mut Default::default(),
+        &mut take_lock,
+        &Value::Timestamp(Some(datetime!(2025-01-01 00:00))),
+    );
+    take_lock.push_str(");");
+    executor
+        .execute(take_lock.as_str())
+        .await
+        .expect("Failed to take the lock");
+    silent_logs! {
+        assert!(migrator.rollback(executor, 1).await.is_err());
+    }
+    migrator
+        .force_unlock(executor)
+        .await
+        .expect("Failed to release the lock");
+
+    // Rollback
+    assert_eq!(
+        migrator
+            .rollback(executor, 1)
+            .await
+            .expect("Failed to rollback the last migration"),
+        [2]
+    );
+    let drone = Drone::find_one(executor, &expr!(Drone::id == 1))
+        .await
+        .expect("Failed to query the drone")
+        .expect("The drone must still exist");
+    assert_eq!(drone.model, "Reaper");
+    let status = migrator
+        .status(executor)
+        .await
+        .expect("Failed to read the migrations status");
+    assert_eq!(status[0].state, MigrationState::Applied);
+    assert_eq!(status[1].state, MigrationState::Pending);
+    assert_eq!(
+        migrator
+            .rollback(executor, 5)
+            .await
+            .expect("Failed to rollback the migrations"),
+        [1]
+    );
+
+    // Migrations from a directory
+    let dir = env::temp_dir().join(format!(
+        "tank_migrations_{}_{}",
+        <C::Driver as Driver>::NAME,
+        process::id()
+    ));
+    fs::create_dir_all(&dir).expect("Failed to create the migrations directory");
+    let mut create = String::new();
+    writer.write_create_table::<Drone>(&mut create, false);
+    let mut drop = String::new();
+    writer.write_drop_table::<Drone>(&mut drop, false);
+    fs::write(dir.join("0001_create_drones.sql"), create).expect("Failed to write");
+    fs::write(dir.join("0001_create_drones.down.sql"), drop).expect("Failed to write");
+    fs::write(dir.join("README.md"), "Not a migration").expect("Failed to write");
+    let loaded = Migrator::<C>::new()
+        .load_dir(&dir)
+        .expect("Failed to load the migrations");
+    fs::remove_dir_all(&dir).expect("Failed to remove the migrations directory");
+    assert_eq!(
+        loaded
+            .apply(executor)
+            .await
+            .expect("Failed to apply the loaded migrations"),
+        [1]
+    );
+    assert_eq!(
+        loaded
+            .rollback(executor, 1)
+            .await
+            .expect("Failed to rollback the loaded migrations"),
+        [1]
+    );
+
+    // Inside a tenant schema the migrations move, the history and the lock stay shared
+    {
+        let mut tenant = executor.with_schema("tenant_migrations");
+        Beacon::drop_table(&mut tenant, true, false)
+            .await
+            .expect("Failed to drop Beacon table");
+        let migrator = beacon_migrations();
+        assert_eq!(
+            migrator
+                .apply(&mut tenant)
+                .await
+                .expect("Failed to apply the tenant migrations"),
+            [1]
+        );
+        Beacon::insert_one(&mut tenant, &Beacon { id: 1 })
+            .await
+            .expect("Failed to insert the beacon in the tenant schema");
+    }
+    let status = Migrator::<C>::new()
+        .status(executor)
+        .await
+        .expect("Failed to read the migrations status");
+    assert_eq!(status.len(), 1);
+    assert_eq!(status[0].name, "create_beacons");
+    assert_eq!(status[0].state, MigrationState::Unknown);
+    executor
+        .execute(take_lock.as_str())
+        .await
+        .expect("Failed to take the lock");
+    {
+        let mut tenant = executor.with_schema("tenant_migrations");
+        let migrator = beacon_migrations();
+        silent_logs! {
+            assert!(migrator.rollback(&mut tenant, 1).await.is_err());
+        }
+        migrator
+            .force_unlock(&mut tenant)
+            .await
+            .expect("Failed to release the lock");
+        assert_eq!(
+            migrator
+                .rollback(&mut tenant, 1)
+                .await
+                .expect("Failed to rollback the tenant migrations"),
+            [1]
+        );
+    }
+}
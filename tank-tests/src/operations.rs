@@ -1,6 +1,6 @@
 use std::{pin::pin, sync::LazyLock};
 use tank::{
-    DataSet, Driver, Entity, Executor, Prepared, Query, QueryResult, Result, RowsAffected,
+    DataSet, Driver, Entity, Executor, FromRow, Prepared, Query, QueryResult, Result, RowsAffected,
     SqlWriter, cols, expr, join,
     stream::{StreamExt, TryStreamExt},
 };
@@ -292,7 +292,7 @@ pub async fn advanced_operations<E: Executor>(executor: &mut E) -> Result<()> {
     )
     .map(|row| {
         row.and_then(|row| {
-            #[derive(Entity)]
+            #[derive(FromRow)]
             struct Row {
                 message: String,
                 callsign: String,
@@ -0,0 +1,149 @@
+use std::sync::LazyLock;
+use tank::{Entity, EntityRegistry, Executor, TableName};
+use tokio::sync::Mutex;
+
+#[derive(Debug, Entity)]
+#[tank(schema = "testing", name = "registry_units")]
+struct Unit {
+    #[tank(primary_key)]
+    id: i32,
+    #[tank(references = Unit::id)]
+    parent: Option<i32>,
+}
+
+#[derive(Debug, Entity)]
+#[tank(schema = "testing", name = "registry_soldiers")]
+struct Soldier {
+    #[tank(primary_key)]
+    id: i64,
+    #[tank(references = Unit::id)]
+    unit: i32,
+}
+
+#[derive(Debug, Entity)]
+#[tank(schema = "testing", name = "registry_missions")]
+struct Mission {
+    #[tank(primary_key)]
+    id: i64,
+    #[tank(references = Soldier::id)]
+    leader: i64,
+}
+
+#[derive(Debug, Entity)]
+#[tank(
+    schema = "testing",
+    name = "registry_unit_ids",
+    view = (tank::cols!(Unit::id), Unit::table())
+)]
+struct UnitId {
+    #[tank(primary_key)]
+    id: i32,
+}
+
+#[derive(Debug, Entity)]
+#[tank(schema = "testing", name = "registry_pilots")]
+struct Pilot {
+    #[tank(primary_key)]
+    id: i32,
+    #[tank(references = Aircraft::id)]
+    aircraft: i32,
+}
+
+#[derive(Debug, Entity)]
+#[tank(schema = "testing", name = "registry_aircraft")]
+struct Aircraft {
+    #[tank(primary_key)]
+    id: i32,
+    #[tank(references = Pilot::id)]
+    pilot: i32,
+}
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+pub async fn registry<E: Executor>(executor: &mut E) {
+    let _lock = MUTEX.lock().await;
+
+    let registry = EntityRegistry::<E::Driver>::new()
+        .entity::<UnitId>()
+        .entity::<Mission>()
+        .entity::<Soldier>()
+        .entity::<Unit>()
+        .entity::<Mission>();
+    assert_eq!(
+        registry.tables().expect("The entities must have an order"),
+        [
+            &TableName::from(Unit::table()),
+            &TableName::from(Soldier::table()),
+            &TableName::from(Mission::table()),
+            &TableName::from(UnitId::table()),
+        ]
+    );
+
+    // Setup
+    registry
+        .drop_all(executor, true)
+        .await
+        .expect("Failed to drop the registered entities");
+    registry
+        .create_all(executor, false)
+        .await
+        .expect("Failed to create the registered entities");
+    Unit {
+        id: 1,
+        parent: None,
+    }
+    .save(executor)
+    .await
+    .expect("Failed to save the unit");
+    Soldier { id: 1, unit: 1 }
+        .save(executor)
+        .await
+        .expect("Failed to save the soldier");
+    Mission { id: 1, leader: 1 }
+        .save(executor)
+        .await
+        .expect("Failed to save the mission");
+    assert_eq!(
+        UnitId::count(executor, &true)
+            .await
+            .expect("Failed to count the units"),
+        1
+    );
+    registry
+        .create_all(executor, true)
+        .await
+        .expect("Failed to create the registered entities a second time");
+
+    // Cycles
+    let cyclic = EntityRegistry::<E::Driver>::new()
+        .entity::<Unit>()
+        .entity::<Pilot>()
+        .entity::<Aircraft>();
+    let error = cyclic.tables().expect_err("The foreign keys form a cycle");
+    let message = error.to_string();
+    assert!(message.contains("registry_pilots"), "{}", message);
+    assert!(message.contains("registry_aircraft"), "{}", message);
+    assert!(!message.contains("registry_units"), "{}", message);
+    assert!(cyclic.create_all(executor, true).await.is_err());
+    assert!(
+        executor
+            .describe_table(&TableName::from(Pilot::table()))
+            .await
+            .expect("Failed to describe the pilots table")
+            .is_none(),
+        "Nothing must be created"
+    );
+
+    // Cleanup
+    registry
+        .drop_all(executor, false)
+        .await
+        .expect("Failed to drop the registered entities");
+    assert!(
+        executor
+            .describe_table(&TableName::from(Unit::table()))
+            .await
+            .expect("Failed to describe the units table")
+            .is_none()
+    );
+}
@@ -0,0 +1,198 @@
+use std::sync::LazyLock;
+use tank::{Driver, Entity, Executor, SchemaChange, SqlWriter, TableName, expr};
+use tokio::sync::Mutex;
+
+#[derive(Debug, Entity)]
+#[tank(schema = "testing", name = "roster")]
+struct RosterV1 {
+    #[tank(primary_key)]
+    id: i32,
+    callsign: String,
+    retired: Option<bool>,
+}
+
+#[derive(Debug, Entity)]
+#[tank(schema = "testing", name = "roster")]
+struct RosterV2 {
+    #[tank(primary_key)]
+    id: i32,
+    callsign: String,
+    nickname: Option<String>,
+}
+
+#[derive(Debug, Entity)]
+#[tank(schema = "testing", name = "roster")]
+struct RosterV3 {
+    #[tank(primary_key)]
+    id: i32,
+    callsign: Option<String>,
+    nickname: Option<String>,
+}
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+pub async fn schema_diff<E: Executor>(executor: &mut E) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    RosterV1::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop RosterV1 table");
+    assert!(
+        RosterV1::diff_table(executor)
+            .await
+            .expect("Failed to diff the missing table")
+            .is_none()
+    );
+    let diff = RosterV1::migrate_table(executor, false)
+        .await
+        .expect("Failed to create the table through migrate_table");
+    assert!(diff.is_empty());
+    RosterV1::insert_one(
+        executor,
+        &RosterV1 {
+            id: 1,
+            callsign: "Maverick".into(),
+            retired: Some(false),
+        },
+    )
+    .await
+    .expect("Failed to insert the roster entry");
+
+    // The table just created matches its entity
+    let diff = RosterV1::diff_table(executor)
+        .await
+        .expect("Failed to diff RosterV1")
+        .expect("Roster table must exist");
+    assert!(diff.is_empty(), "Unexpected changes: {:?}", diff.changes);
+
+    // New version of the entity
+    let diff = RosterV2::diff_table(executor)
+        .await
+        .expect("Failed to diff RosterV2")
+        .expect("Roster table must exist");
+    assert!(
+        matches!(
+            diff.changes.as_slice(),
+            [
+                SchemaChange::AddColumn(column),
+                SchemaChange::DropColumn(dropped),
+            ] if column.name() == "nickname" && dropped == "retired"
+        ),
+        "Unexpected changes: {:?}",
+        diff.changes
+    );
+
+    // Destructive changes are skipped by default
+    RosterV2::migrate_table(executor, false)
+        .await
+        .expect("Failed to migrate the roster table");
+    let live = executor
+        .describe_table(&TableName::new("testing", "roster"))
+        .await
+        .expect("Failed to describe the roster")
+        .expect("Roster table must exist");
+    assert!(live.column("nickname").is_some());
+    assert!(live.column("retired").is_some());
+    let entry = RosterV2::find_one(executor, &expr!(RosterV2::id == 1))
+        .await
+        .expect("Failed to query the roster entry")
+        .expect("Roster entry must exist");
+    assert_eq!(entry.callsign, "Maverick");
+    assert_eq!(entry.nickname, None);
+
+    // Opt into destructive changes
+    RosterV2::migrate_table(executor, true)
+        .await
+        .expect("Failed to migrate the roster table");
+    let live = executor
+        .describe_table(&TableName::new("testing", "roster"))
+        .await
+        .expect("Failed to describe the roster")
+        .expect("Roster table must exist");
+    assert!(live.column("retired").is_none());
+    let diff = RosterV2::diff_table(executor)
+        .await
+        .expect("Failed to diff RosterV2")
+        .expect("Roster table must exist");
+    assert!(diff.is_empty(), "Unexpected changes: {:?}", diff.changes);
+    assert_eq!(
+        RosterV2::count(executor, &true)
+            .await
+            .expect("Failed to count the roster"),
+        1
+    );
+
+    // Changes in place, the table is rebuilt where the driver cannot alter columns
+    let diff = RosterV3::migrate_table(executor, false)
+        .await
+        .expect("Failed to migrate the roster table");
+    assert!(
+        matches!(
+            diff.changes.as_slice(),
+            [SchemaChange::DropNotNull(column)] if column.name() == "callsign"
+        ),
+        "Unexpected changes: {:?}",
+        diff.changes
+    );
+    let diff = RosterV3::diff_table(executor)
+        .await
+        .expect("Failed to diff RosterV3")
+        .expect("Roster table must exist");
+    assert!(diff.is_empty(), "Unexpected changes: {:?}", diff.changes);
+    let entry = RosterV3::find_one(executor, &expr!(RosterV3::id == 1))
+        .await
+        .expect("Failed to query the roster entry")
+        .expect("Roster entry must exist");
+    assert_eq!(entry.callsign.as_deref(), Some("Maverick"));
+
+    // Renames
+    let writer = executor.driver().sql_writer();
+    let mut query = String::new();
+    writer.write_alter_table_rename_column(&mut query, RosterV3::table(), "nickname", "alias");
+    executor
+        .execute(query)
+        .await
+        .expect("Failed to rename the column");
+    let live = executor
+        .describe_table(&TableName::new("testing", "roster"))
+        .await
+        .expect("Failed to describe the roster")
+        .expect("Roster table must exist");
+    assert!(live.column("alias").is_some());
+    assert!(live.column("nickname").is_none());
+    let mut query = String::new();
+    writer.write_alter_table_rename_table(&mut query, &RosterV3::table().into(), "roster_renamed");
+    executor
+        .execute(query)
+        .await
+        .expect("Failed to rename the table");
+    let tables = executor
+        .tables(Some("testing"))
+        .await
+        .expect("Failed to list the tables");
+    assert!(tables.contains(&TableName::new("testing", "roster_renamed")));
+    assert!(!tables.contains(&TableName::new("testing", "roster")));
+    let mut query = String::new();
+    writer.write_alter_table_rename_table(
+        &mut query,
+        &TableName::new("testing", "roster_renamed"),
+        "roster",
+    );
+    executor
+        .execute(query)
+        .await
+        .expect("Failed to rename the table back");
+    let mut query = String::new();
+    writer.write_alter_table_rename_column(&mut query, RosterV3::table(), "alias", "nickname");
+    executor
+        .execute(query)
+        .await
+        .expect("Failed to rename the column back");
+    assert_eq!(
+        RosterV3::count(executor, &true)
+            .await
+            .expect("Failed to count the roster"),
+        1
+    );
+}
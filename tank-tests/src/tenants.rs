@@ -0,0 +1,144 @@
+use std::sync::LazyLock;
+use tank::{Entity, Executor, expr, stream::TryStreamExt, with_schema};
+use tokio::sync::Mutex;
+
+#[derive(Debug, Entity)]
+#[tank(name = "tenant_notes")]
+struct Note {
+    #[tank(primary_key)]
+    id: i32,
+    #[tank(column_type = (mysql = "VARCHAR(64)"))]
+    text: String,
+}
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+pub async fn tenants<E: Executor>(executor: &mut E)
+where
+    E::Driver: Clone + Send,
+{
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    for tenant in ["tenant_alpha", "tenant_bravo"] {
+        with_schema(tenant, async {
+            Note::drop_table(executor, true, false)
+                .await
+                .expect("Failed to drop Note table");
+            Note::create_table(executor, false, true)
+                .await
+                .expect("Failed to create Note table");
+        })
+        .await;
+    }
+
+    // Insert
+    with_schema("tenant_alpha", async {
+        Note::insert_many(
+            executor,
+            &[
+                Note {
+                    id: 1,
+                    text: "alpha one".into(),
+                },
+                Note {
+                    id: 2,
+                    text: "alpha two".into(),
+                },
+            ],
+        )
+        .await
+        .expect("Failed to insert the alpha notes");
+    })
+    .await;
+    with_schema("tenant_bravo", async {
+        Note::insert_one(
+            executor,
+            &Note {
+                id: 1,
+                text: "bravo one".into(),
+            },
+        )
+        .await
+        .expect("Failed to insert the bravo note");
+    })
+    .await;
+
+    // Each tenant sees its own rows
+    let alpha = with_schema("tenant_alpha", async {
+        Note::find_many(executor, &true, None)
+            .try_collect::<Vec<_>>()
+            .await
+            .expect("Failed to query the alpha notes")
+    })
+    .await;
+    assert_eq!(alpha.len(), 2);
+    let bravo = with_schema("tenant_bravo", async {
+        Note::find_one(executor, &expr!(Note::id == 1))
+            .await
+            .expect("Failed to query the bravo note")
+            .expect("The bravo note must exist")
+    })
+    .await;
+    assert_eq!(bravo.text, "bravo one");
+
+    // Delete
+    with_schema("tenant_alpha", async {
+        Note::delete_many(executor, &expr!(Note::id == 1))
+            .await
+            .expect("Failed to delete the alpha note");
+        assert!(
+            Note::find_pk(executor, &(&1,))
+                .await
+                .expect("Failed to query the alpha note")
+                .is_none()
+        );
+    })
+    .await;
+    let bravo = with_schema("tenant_bravo", async {
+        Note::find_pk(executor, &(&1,))
+            .await
+            .expect("Failed to query the bravo note")
+    })
+    .await;
+    assert!(bravo.is_some(), "Other tenants must not be affected");
+
+    // The executor override covers the SQL built when the operation is called
+    Note::insert_one(
+        &mut executor.with_schema("tenant_bravo"),
+        &Note {
+            id: 2,
+            text: "bravo two".into(),
+        },
+    )
+    .await
+    .expect("Failed to insert the bravo note");
+    let mut bravo = executor.with_schema("tenant_bravo");
+    Note::insert_many(
+        &mut bravo,
+        &[Note {
+            id: 3,
+            text: "bravo three".into(),
+        }],
+    )
+    .await
+    .expect("Failed to insert the bravo note");
+    Note::delete_one(&mut bravo, (&1,))
+        .await
+        .expect("Failed to delete the bravo note");
+    Note::delete_many(&mut bravo, &expr!(Note::id == 3))
+        .await
+        .expect("Failed to delete the bravo note");
+    let bravo = Note::find_many(&mut bravo, &true, None)
+        .map_ok(|v| v.id)
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("Failed to query the bravo notes");
+    assert_eq!(bravo, [2]);
+    let alpha = Note::find_many(&mut executor.with_schema("tenant_alpha"), &true, None)
+        .map_ok(|v| v.text)
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("Failed to query the alpha notes");
+    assert_eq!(alpha, ["alpha two"], "Other tenants must not be affected");
+}
@@ -0,0 +1,195 @@
+use crate::silent_logs;
+use std::sync::LazyLock;
+use tank::{Connection, Driver, Entity, Transaction, stream::TryStreamExt};
+use tokio::sync::Mutex;
+
+#[derive(Entity)]
+#[tank(name = "savepoint_ledger")]
+struct Ledger {
+    #[tank(primary_key)]
+    id: i32,
+    amount: i64,
+}
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+pub async fn transaction2<C: Connection>(connection: &mut C) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    Ledger::drop_table(connection, true, false)
+        .await
+        .expect("Failed to drop Ledger table");
+    Ledger::create_table(connection, true, true)
+        .await
+        .expect("Failed to create Ledger table");
+
+    let mut transaction = connection
+        .begin()
+        .await
+        .expect("Could not begin a transaction");
+    Ledger { id: 1, amount: 100 }
+        .save(&mut transaction)
+        .await
+        .expect("Failed to save the first entry");
+    if !<C::Driver as Driver>::SAVEPOINTS {
+        silent_logs! {
+            assert!(
+                transaction.savepoint().await.is_err(),
+                "The driver does not support savepoints"
+            );
+        }
+        transaction
+            .rollback()
+            .await
+            .expect("Failed to rollback the transaction");
+        return;
+    }
+
+    // Partial rollback
+    let mut savepoint = transaction
+        .savepoint()
+        .await
+        .expect("Could not set a savepoint");
+    Ledger { id: 2, amount: -50 }
+        .save(&mut savepoint)
+        .await
+        .expect("Failed to save the second entry");
+    assert_eq!(
+        Ledger::count(&mut savepoint, &true)
+            .await
+            .expect("Failed to count the entries"),
+        2
+    );
+    savepoint
+        .rollback()
+        .await
+        .expect("Failed to rollback the savepoint");
+    assert_eq!(
+        Ledger::count(&mut transaction, &true)
+            .await
+            .expect("Failed to count the entries"),
+        1
+    );
+
+    // Nested savepoints
+    let mut savepoint = transaction
+        .savepoint()
+        .await
+        .expect("Could not set a savepoint");
+    Ledger { id: 3, amount: 20 }
+        .save(&mut savepoint)
+        .await
+        .expect("Failed to save the third entry");
+    let mut nested = savepoint
+        .savepoint()
+        .await
+        .expect("Could not set a nested savepoint");
+    Ledger { id: 4, amount: 30 }
+        .save(&mut nested)
+        .await
+        .expect("Failed to save the fourth entry");
+    nested
+        .commit()
+        .await
+        .expect("Failed to release the nested savepoint");
+    let mut nested = savepoint
+        .savepoint()
+        .await
+        .expect("Could not set a nested savepoint");
+    Ledger { id: 5, amount: 40 }
+        .save(&mut nested)
+        .await
+        .expect("Failed to save the fifth entry");
+    nested
+        .rollback()
+        .await
+        .expect("Failed to rollback the nested savepoint");
+    savepoint
+        .commit()
+        .await
+        .expect("Failed to release the savepoint");
+    transaction
+        .commit()
+        .await
+        .expect("Failed to commit the transaction");
+
+    let mut ids = Ledger::find_many(connection, &true, None)
+        .map_ok(|v| v.id)
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("Could not select the entries");
+    ids.sort();
+    assert_eq!(ids, [1, 3, 4]);
+
+    // A rolled back transaction discards the released savepoints
+    let mut transaction = connection
+        .begin()
+        .await
+        .expect("Could not begin a transaction");
+    let mut savepoint = transaction
+        .savepoint()
+        .await
+        .expect("Could not set a savepoint");
+    Ledger { id: 6, amount: 10 }
+        .save(&mut savepoint)
+        .await
+        .expect("Failed to save the sixth entry");
+    savepoint
+        .commit()
+        .await
+        .expect("Failed to release the savepoint");
+    transaction
+        .rollback()
+        .await
+        .expect("Failed to rollback the transaction");
+    assert_eq!(
+        Ledger::count(connection, &true)
+            .await
+            .expect("Failed to count the entries"),
+        3
+    );
+
+    // A dropped savepoint leaves its changes in the enclosing transaction
+    let mut transaction = connection
+        .begin()
+        .await
+        .expect("Could not begin a transaction");
+    let mut savepoint = transaction
+        .savepoint()
+        .await
+        .expect("Could not set a savepoint");
+    Ledger { id: 7, amount: 70 }
+        .save(&mut savepoint)
+        .await
+        .expect("Failed to save the seventh entry");
+    drop(savepoint);
+    assert_eq!(
+        Ledger::count(&mut transaction, &true)
+            .await
+            .expect("Failed to count the entries"),
+        4
+    );
+    let mut savepoint = transaction
+        .savepoint()
+        .await
+        .expect("Could not set a savepoint after dropping one");
+    Ledger { id: 8, amount: 80 }
+        .save(&mut savepoint)
+        .await
+        .expect("Failed to save the eighth entry");
+    savepoint
+        .rollback()
+        .await
+        .expect("Failed to rollback the savepoint");
+    transaction
+        .commit()
+        .await
+        .expect("Failed to commit the transaction");
+    let mut ids = Ledger::find_many(connection, &true, None)
+        .map_ok(|v| v.id)
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("Could not select the entries");
+    ids.sort();
+    assert_eq!(ids, [1, 3, 4, 7]);
+}
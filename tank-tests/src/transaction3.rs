@@ -0,0 +1,86 @@
+use std::sync::LazyLock;
+use tank::{Connection, Entity, IsolationLevel, Transaction, TransactionOptions};
+use tokio::sync::Mutex;
+
+#[derive(Entity)]
+#[tank(name = "isolated_accounts")]
+struct Account {
+    #[tank(primary_key)]
+    id: i32,
+    balance: i64,
+}
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+pub async fn transaction3<C: Connection>(connection: &mut C) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    Account::drop_table(connection, true, false)
+        .await
+        .expect("Failed to drop Account table");
+    Account::create_table(connection, true, true)
+        .await
+        .expect("Failed to create Account table");
+
+    // Serializable
+    let mut transaction = connection
+        .begin_with(TransactionOptions {
+            isolation: Some(IsolationLevel::Serializable),
+            ..Default::default()
+        })
+        .await
+        .expect("Could not begin a serializable transaction");
+    Account {
+        id: 1,
+        balance: 500,
+    }
+    .save(&mut transaction)
+    .await
+    .expect("Failed to save the account");
+    transaction
+        .commit()
+        .await
+        .expect("Failed to commit the serializable transaction");
+
+    // Read committed
+    let mut transaction = connection
+        .begin_with(TransactionOptions {
+            isolation: Some(IsolationLevel::ReadCommitted),
+            ..Default::default()
+        })
+        .await
+        .expect("Could not begin a read committed transaction");
+    Account { id: 2, balance: 80 }
+        .save(&mut transaction)
+        .await
+        .expect("Failed to save the account");
+    transaction
+        .rollback()
+        .await
+        .expect("Failed to rollback the read committed transaction");
+
+    // Read only
+    let mut transaction = connection
+        .begin_with(TransactionOptions {
+            isolation: Some(IsolationLevel::Serializable),
+            read_only: true,
+            deferrable: true,
+        })
+        .await
+        .expect("Could not begin a read only transaction");
+    let account = Account::find_pk(&mut transaction, &(&1,))
+        .await
+        .expect("Failed to find the account")
+        .expect("The account must exist");
+    assert_eq!(account.balance, 500);
+    assert_eq!(
+        Account::count(&mut transaction, &true)
+            .await
+            .expect("Failed to count the accounts"),
+        1
+    );
+    transaction
+        .commit()
+        .await
+        .expect("Failed to commit the read only transaction");
+}
@@ -0,0 +1,85 @@
+use std::{
+    panic::AssertUnwindSafe,
+    sync::{
+        LazyLock,
+        atomic::{AtomicU32, Ordering},
+    },
+};
+use tank::{Connection, Entity, Error, future::FutureExt};
+use tokio::sync::Mutex;
+
+#[derive(Entity)]
+#[tank(name = "closure_transfers")]
+struct Transfer {
+    #[tank(primary_key)]
+    id: i32,
+    amount: i64,
+}
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+pub async fn transaction4<C: Connection>(connection: &mut C) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    Transfer::drop_table(connection, true, false)
+        .await
+        .expect("Failed to drop Transfer table");
+    Transfer::create_table(connection, true, true)
+        .await
+        .expect("Failed to create Transfer table");
+
+    // Commit on Ok
+    let count = connection
+        .transaction(|tx| {
+            Box::pin(async move {
+                Transfer { id: 1, amount: 250 }.save(tx).await?;
+                Transfer { id: 2, amount: -70 }.save(tx).await?;
+                Transfer::count(tx, &true).await
+            })
+        })
+        .await
+        .expect("The transaction must commit");
+    assert_eq!(count, 2);
+
+    // Rollback on Err, not retried
+    let attempts = AtomicU32::new(0);
+    let result = connection
+        .transaction(|tx| {
+            attempts.fetch_add(1, Ordering::Relaxed);
+            Box::pin(async move {
+                Transfer { id: 3, amount: 10 }.save(tx).await?;
+                Err::<(), _>(Error::msg("Insufficient funds"))
+            })
+        })
+        .await;
+    assert_eq!(
+        result.expect_err("The transaction must fail").to_string(),
+        "Insufficient funds"
+    );
+    assert_eq!(attempts.load(Ordering::Relaxed), 1);
+    assert_eq!(
+        Transfer::count(connection, &true)
+            .await
+            .expect("Failed to count the transfers"),
+        2
+    );
+
+    // Rollback on panic
+    let result = AssertUnwindSafe(connection.transaction(|tx| {
+        Box::pin(async move {
+            Transfer { id: 4, amount: 10 }.save(tx).await?;
+            panic!("The closure panics");
+            #[allow(unreachable_code)]
+            Ok(())
+        })
+    }))
+    .catch_unwind()
+    .await;
+    assert!(result.is_err(), "The panic must be propagated");
+    assert_eq!(
+        Transfer::count(connection, &true)
+            .await
+            .expect("Failed to count the transfers"),
+        2
+    );
+}
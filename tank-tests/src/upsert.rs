@@ -0,0 +1,161 @@
+use std::sync::LazyLock;
+use tank::{Entity, Executor, OnConflict, expr};
+use tokio::sync::Mutex;
+
+#[derive(Debug, Entity)]
+#[tank(schema = "testing", name = "callsigns")]
+struct Pilot {
+    #[tank(primary_key)]
+    id: i32,
+    #[tank(unique, column_type = (mysql = "VARCHAR(32)"))]
+    callsign: String,
+    missions: i32,
+    active: bool,
+}
+
+#[derive(Debug, Entity)]
+#[tank(schema = "testing", name = "wingmen", primary_key = (Self::pilot, Self::wingman))]
+struct Wingman {
+    pilot: i32,
+    wingman: i32,
+}
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+pub async fn upsert<E: Executor>(executor: &mut E) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    Pilot::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop Pilot table");
+    Pilot::create_table(executor, false, true)
+        .await
+        .expect("Failed to create Pilot table");
+    Pilot::insert_many(
+        executor,
+        &[
+            Pilot {
+                id: 1,
+                callsign: "Maverick".into(),
+                missions: 10,
+                active: true,
+            },
+            Pilot {
+                id: 2,
+                callsign: "Iceman".into(),
+                missions: 12,
+                active: true,
+            },
+        ],
+    )
+    .await
+    .expect("Failed to insert the pilots");
+
+    // Do nothing on the conflicting callsign
+    Pilot::upsert_many(
+        executor,
+        &[
+            Pilot {
+                id: 3,
+                callsign: "Maverick".into(),
+                missions: 0,
+                active: false,
+            },
+            Pilot {
+                id: 4,
+                callsign: "Goose".into(),
+                missions: 8,
+                active: true,
+            },
+        ],
+        &OnConflict::target([Pilot::callsign]).do_nothing(),
+    )
+    .await
+    .expect("Failed to upsert the pilots ignoring the conflicts");
+    assert_eq!(
+        Pilot::count(executor, &true)
+            .await
+            .expect("Failed to count the pilots"),
+        3
+    );
+    let maverick = Pilot::find_one(executor, &expr!(Pilot::callsign == "Maverick"))
+        .await
+        .expect("Failed to query Maverick")
+        .expect("Maverick must exist");
+    assert_eq!(maverick.id, 1);
+    assert_eq!(maverick.missions, 10);
+    assert!(maverick.active);
+
+    // Update just the selected columns
+    Pilot::upsert_many(
+        executor,
+        &[Pilot {
+            id: 2,
+            callsign: "Iceman".into(),
+            missions: 13,
+            active: false,
+        }],
+        &OnConflict::target([Pilot::callsign]).update([Pilot::missions]),
+    )
+    .await
+    .expect("Failed to upsert Iceman");
+    let iceman = Pilot::find_pk(executor, &(&2,))
+        .await
+        .expect("Failed to query Iceman")
+        .expect("Iceman must exist");
+    assert_eq!(iceman.missions, 13);
+    assert!(iceman.active, "Columns not listed must be left untouched");
+
+    // Default updates every column on the primary key conflict
+    Pilot::upsert_many(
+        executor,
+        &[Pilot {
+            id: 4,
+            callsign: "Goose".into(),
+            missions: 9,
+            active: false,
+        }],
+        &OnConflict::default(),
+    )
+    .await
+    .expect("Failed to upsert Goose");
+    let goose = Pilot::find_pk(executor, &(&4,))
+        .await
+        .expect("Failed to query Goose")
+        .expect("Goose must exist");
+    assert_eq!(goose.missions, 9);
+    assert!(!goose.active);
+
+    // Entity made of key columns only, there is nothing to update
+    Wingman::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop Wingman table");
+    Wingman::create_table(executor, false, true)
+        .await
+        .expect("Failed to create Wingman table");
+    let pairs = [
+        Wingman {
+            pilot: 1,
+            wingman: 2,
+        },
+        Wingman {
+            pilot: 4,
+            wingman: 1,
+        },
+    ];
+    Wingman::insert_many(executor, &pairs[..1])
+        .await
+        .expect("Failed to insert the wingmen");
+    for on_conflict in [OnConflict::default().do_nothing(), OnConflict::default()] {
+        Wingman::upsert_many(executor, &pairs, &on_conflict)
+            .await
+            .expect("Failed to upsert the wingmen");
+    }
+    assert_eq!(
+        Wingman::count(executor, &true)
+            .await
+            .expect("Failed to count the wingmen"),
+        2
+    );
+}
@@ -4,7 +4,7 @@ use std::{
     sync::LazyLock,
 };
 use tank::{
-    Entity, Executor, Passive, expr,
+    Entity, Executor, Passive, Tracked, expr,
     stream::{StreamExt, TryStreamExt},
 };
 use time::macros::datetime;
@@ -181,6 +181,40 @@ pub async fn users<E: Executor>(executor: &mut E) {
         .unwrap();
     assert_eq!(active_users_after_update.len(), 4);
 
+    // Tracked update of Alice
+    let alice = UserProfile::find_one(executor, &expr!(username == "alice"))
+        .await
+        .expect("Expected query to succeed")
+        .expect("Could not find alice");
+    let mut alice = Tracked::new(alice);
+    assert!(!alice.is_dirty());
+    alice.follower_count = 57;
+    assert_eq!(
+        alice
+            .changed_columns()
+            .into_iter()
+            .map(|c| c.name())
+            .collect::<Vec<_>>(),
+        ["follower_count"]
+    );
+    let result = alice
+        .update(executor)
+        .await
+        .expect("Failed to update alice");
+    assert_eq!(result.rows_affected, 1);
+    assert!(!alice.is_dirty());
+    let result = alice
+        .update(executor)
+        .await
+        .expect("Unchanged entity update must succeed");
+    assert_eq!(result.rows_affected, 0);
+    let updated_alice = UserProfile::find_pk(executor, &alice.primary_key())
+        .await
+        .expect("Expected query to succeed")
+        .expect("Could not find alice");
+    assert_eq!(updated_alice.follower_count, 57);
+    assert_eq!(updated_alice.full_name, Some("Alice Wonderland".into()));
+
     // Find eve user and delete it.
     let eve = UserProfile::find_one(executor, &expr!(username == "eve"))
         .await
@@ -0,0 +1,109 @@
+use crate::silent_logs;
+use std::sync::LazyLock;
+use tank::{Entity, Executor, ValidationError, expr, stream::TryStreamExt};
+use tokio::sync::Mutex;
+
+#[derive(Debug, Entity)]
+#[tank(schema = "testing", name = "recruits")]
+struct Recruit {
+    #[tank(primary_key)]
+    id: i32,
+    #[tank(validate(length(min = 2, max = 24), regex = "^[A-Za-z ]+$"))]
+    name: String,
+    #[tank(validate(range(min = 18, max = 60)))]
+    age: i32,
+}
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+pub async fn validation<E: Executor>(executor: &mut E) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    Recruit::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop Recruit table");
+    Recruit::create_table(executor, false, true)
+        .await
+        .expect("Failed to create Recruit table");
+
+    // Insert
+    Recruit::insert_one(
+        executor,
+        &Recruit {
+            id: 1,
+            name: "Sarah Connor".into(),
+            age: 29,
+        },
+    )
+    .await
+    .expect("Failed to insert a valid recruit");
+
+    // Rejected before any query
+    let result;
+    silent_logs! {
+        result = Recruit::insert_one(
+            executor,
+            &Recruit {
+                id: 2,
+                name: "R2".into(),
+                age: 70,
+            },
+        )
+        .await;
+    }
+    let error = result.expect_err("Invalid recruit must be rejected");
+    let error = error
+        .downcast_ref::<ValidationError>()
+        .expect("Must be a validation error");
+    assert_eq!(
+        error
+            .violations
+            .iter()
+            .map(|v| v.column)
+            .collect::<Vec<_>>(),
+        ["name", "age"]
+    );
+    let result;
+    silent_logs! {
+        result = Recruit::insert_many(
+            executor,
+            &[
+                Recruit {
+                    id: 3,
+                    name: "Kyle Reese".into(),
+                    age: 35,
+                },
+                Recruit {
+                    id: 4,
+                    name: "X".into(),
+                    age: 35,
+                },
+            ],
+        )
+        .await;
+    }
+    assert!(result.is_err(), "Short name must be rejected");
+    let recruits = Recruit::find_many(executor, &true, None)
+        .try_collect::<Vec<_>>()
+        .await
+        .expect("Failed to query the recruits");
+    assert_eq!(recruits.len(), 1, "No row must be inserted when invalid");
+
+    // Save
+    let mut recruit = Recruit::find_one(executor, &expr!(Recruit::id == 1))
+        .await
+        .expect("Failed to query the recruit")
+        .expect("Recruit 1 must exist");
+    recruit.age = 17;
+    let result;
+    silent_logs! {
+        result = recruit.save(executor).await;
+    }
+    assert!(result.is_err(), "Underage recruit must be rejected");
+    let recruit = Recruit::find_pk(executor, &recruit.primary_key())
+        .await
+        .expect("Failed to query the recruit")
+        .expect("Recruit 1 must exist");
+    assert_eq!(recruit.age, 29);
+}
@@ -0,0 +1,150 @@
+use std::{collections::BTreeMap, sync::LazyLock};
+use tank::{Entity, Executor, cols, expr, stream::TryStreamExt};
+use tokio::sync::Mutex;
+
+#[derive(Debug, Entity)]
+#[tank(name = "sorties")]
+struct Sortie {
+    #[tank(primary_key)]
+    id: i32,
+    #[tank(column_type = (mysql = "VARCHAR(32)"))]
+    pilot: String,
+    completed: bool,
+}
+
+#[derive(Debug, Entity)]
+#[tank(
+    name = "pilot_sorties",
+    view = "SELECT pilot, COUNT(*) AS sorties FROM sorties GROUP BY pilot"
+)]
+struct PilotSorties {
+    #[tank(primary_key)]
+    pilot: String,
+    sorties: i64,
+}
+
+#[derive(Debug, Entity)]
+#[tank(
+    name = "aborted_sorties",
+    view = (cols!(Sortie::id, Sortie::pilot), Sortie::table(), expr!(Sortie::completed == false))
+)]
+struct AbortedSortie {
+    #[tank(primary_key)]
+    id: i32,
+    pilot: String,
+}
+
+static MUTEX: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+pub async fn views<E: Executor>(executor: &mut E) {
+    let _lock = MUTEX.lock().await;
+
+    // Setup
+    PilotSorties::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop the PilotSorties view");
+    AbortedSortie::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop the AbortedSortie view");
+    Sortie::drop_table(executor, true, false)
+        .await
+        .expect("Failed to drop Sortie table");
+    Sortie::create_table(executor, false, true)
+        .await
+        .expect("Failed to create Sortie table");
+    PilotSorties::create_table(executor, false, true)
+        .await
+        .expect("Failed to create the PilotSorties view");
+    AbortedSortie::create_table(executor, true, true)
+        .await
+        .expect("Failed to create the AbortedSortie view");
+    Sortie::insert_many(
+        executor,
+        &[
+            Sortie {
+                id: 1,
+                pilot: "Maverick".into(),
+                completed: true,
+            },
+            Sortie {
+                id: 2,
+                pilot: "Maverick".into(),
+                completed: false,
+            },
+            Sortie {
+                id: 3,
+                pilot: "Iceman".into(),
+                completed: true,
+            },
+            Sortie {
+                id: 4,
+                pilot: "Maverick".into(),
+                completed: true,
+            },
+        ],
+    )
+    .await
+    .expect("Failed to insert the sorties");
+
+    // Typed reads
+    let sorties = PilotSorties::find_many(executor, &true, None)
+        .map_ok(|v| (v.pilot, v.sorties))
+        .try_collect::<BTreeMap<_, _>>()
+        .await
+        .expect("Failed to query the PilotSorties view");
+    assert_eq!(
+        sorties,
+        BTreeMap::from_iter([("Iceman".into(), 1), ("Maverick".into(), 3)])
+    );
+    let aborted = AbortedSortie::find_pk(executor, &(&2,))
+        .await
+        .expect("Failed to query the AbortedSortie view")
+        .expect("Sortie 2 must be aborted");
+    assert_eq!(aborted.pilot, "Maverick");
+    assert_eq!(
+        AbortedSortie::count(executor, &true)
+            .await
+            .expect("Failed to count the aborted sorties"),
+        1
+    );
+
+    // Writes are rejected
+    let row = PilotSorties {
+        pilot: "Goose".into(),
+        sorties: 1,
+    };
+    assert!(PilotSorties::insert_one(executor, &row).await.is_err());
+    assert!(PilotSorties::insert_many(executor, [&row]).await.is_err());
+    assert!(executor.append([&row]).await.is_err());
+    assert!(aborted.save(executor).await.is_err());
+    assert!(aborted.delete(executor).await.is_err());
+    assert!(AbortedSortie::delete_many(executor, &true).await.is_err());
+    assert_eq!(
+        Sortie::count(executor, &true)
+            .await
+            .expect("Failed to count the sorties"),
+        4
+    );
+
+    // Migrating recreates the view
+    AbortedSortie::migrate_table(executor, false)
+        .await
+        .expect("Failed to migrate the AbortedSortie view");
+    assert_eq!(
+        AbortedSortie::count(executor, &true)
+            .await
+            .expect("Failed to count the aborted sorties"),
+        1
+    );
+
+    // Cleanup
+    PilotSorties::drop_table(executor, false, false)
+        .await
+        .expect("Failed to drop the PilotSorties view");
+    AbortedSortie::drop_table(executor, false, false)
+        .await
+        .expect("Failed to drop the AbortedSortie view");
+    Sortie::drop_table(executor, false, false)
+        .await
+        .expect("Failed to drop Sortie table");
+}
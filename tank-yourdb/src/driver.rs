@@ -1,4 +1,5 @@
 use crate::{YourDBConnection, YourDBPrepared, YourDBSqlWriter, YourDBTransaction};
+use std::{future::Future, time::Duration};
 use tank_core::Driver;
 
 #[derive(Clone, Copy, Default)]
@@ -19,4 +20,8 @@ impl Driver for YourDBDriver {
     fn sql_writer(&self) -> Self::SqlWriter {
         YourDBSqlWriter::default()
     }
+
+    fn sleep(duration: Duration) -> impl Future<Output = ()> + Send {
+        tokio::time::sleep(duration)
+    }
 }
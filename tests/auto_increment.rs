@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use tank::{Entity, GenericSqlWriter, Passive, SqlWriter, Value};
+
+    #[derive(Entity, Debug)]
+    #[tank(schema = "army", name = "soldiers")]
+    struct Soldier {
+        #[tank(primary_key, auto_increment)]
+        id: Passive<i64>,
+        name: String,
+    }
+
+    const WRITER: GenericSqlWriter = GenericSqlWriter {};
+
+    #[test]
+    fn test_auto_increment_column() {
+        let column = Soldier::auto_increment_def().expect("Soldier::id must be auto increment");
+        assert_eq!(column.name(), "id");
+        assert!(!Soldier::columns()[1].auto_increment);
+        let mut soldier = Soldier {
+            id: Passive::NotSet,
+            name: "Rook".into(),
+        };
+        soldier
+            .set_auto_increment(Value::Int64(Some(7)))
+            .expect("Could not set the id");
+        assert_eq!(soldier.id, Passive::Set(7));
+        assert!(
+            soldier
+                .set_auto_increment(Value::Varchar(Some("seven".into())))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_auto_increment_create_table() {
+        let mut query = String::new();
+        WRITER.write_create_table::<Soldier>(&mut query, false);
+        assert_eq!(
+            query,
+            indoc! {r#"
+                CREATE TABLE "army"."soldiers" (
+                "id" BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+                "name" VARCHAR NOT NULL);
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_auto_increment_insert_returning() {
+        let mut query = String::new();
+        WRITER.write_insert_returning(
+            &mut query,
+            &Soldier {
+                id: Passive::NotSet,
+                name: "Rook".into(),
+            },
+            Soldier::auto_increment_def().unwrap(),
+        );
+        assert_eq!(
+            query,
+            indoc! {r#"
+                INSERT INTO "army"."soldiers" ("name") VALUES
+                ('Rook')
+                RETURNING "id";
+            "#}
+            .trim()
+        );
+    }
+}
@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use tank::{Entity, GenericSqlWriter, OnConflict, SqlWriter};
+
+    #[derive(Entity)]
+    #[tank(name = "readings")]
+    struct Reading {
+        #[tank(primary_key)]
+        id: i32,
+        value: f64,
+    }
+
+    const WRITER: GenericSqlWriter = GenericSqlWriter {};
+
+    fn readings(count: i32) -> Vec<Reading> {
+        (1..=count)
+            .map(|id| Reading {
+                id,
+                value: id as f64 / 2.0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_chunks_max_rows() {
+        let readings = readings(5);
+        let readings = readings.iter().collect::<Vec<_>>();
+        let mut queries = Vec::new();
+        WRITER.write_insert_chunks(&mut queries, &readings, None, 2, usize::MAX);
+        assert_eq!(
+            queries,
+            [
+                indoc! {r#"
+                    INSERT INTO "readings" ("id", "value") VALUES
+                    (1, 0.5),
+                    (2, 1.0);
+                "#}
+                .trim(),
+                indoc! {r#"
+                    INSERT INTO "readings" ("id", "value") VALUES
+                    (3, 1.5),
+                    (4, 2.0);
+                "#}
+                .trim(),
+                indoc! {r#"
+                    INSERT INTO "readings" ("id", "value") VALUES
+                    (5, 2.5);
+                "#}
+                .trim(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_chunks_max_length() {
+        let readings = readings(100);
+        let readings = readings.iter().collect::<Vec<_>>();
+        let mut queries = Vec::new();
+        WRITER.write_insert_chunks(
+            &mut queries,
+            &readings,
+            Some(&OnConflict::default()),
+            1000,
+            256,
+        );
+        assert!(queries.len() > 1);
+        assert!(queries.iter().all(|q| q.len() <= 256));
+        assert!(
+            queries
+                .iter()
+                .all(|q| q.ends_with(r#""value" = EXCLUDED."value";"#))
+        );
+        let rows = queries
+            .iter()
+            .flat_map(|q| q.lines().filter(|l| l.starts_with('(')))
+            .count();
+        assert_eq!(rows, 100);
+    }
+
+    #[test]
+    fn test_chunks_oversized_row() {
+        let readings = readings(3);
+        let readings = readings.iter().collect::<Vec<_>>();
+        let mut queries = Vec::new();
+        WRITER.write_insert_chunks(&mut queries, &readings, None, 1000, 1);
+        assert_eq!(queries.len(), 3, "A single row is never split");
+    }
+
+    #[test]
+    fn test_chunks_empty() {
+        let mut queries = Vec::new();
+        WRITER.write_insert_chunks::<Reading>(&mut queries, &[], None, 1000, usize::MAX);
+        assert!(queries.is_empty());
+    }
+}
@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use std::{net::Ipv4Addr, sync::Arc};
+    use tank::{Entity, GenericSqlWriter, RowLabeled, SqlWriter, Value};
+
+    mod ipv4 {
+        use std::net::Ipv4Addr;
+        use tank::{Error, Result, Value};
+
+        pub fn as_empty_value() -> Value {
+            Value::Varchar(None)
+        }
+        pub fn to_value(value: &Ipv4Addr) -> Value {
+            Value::Varchar(Some(value.to_string().into()))
+        }
+        pub fn from_value(value: Value) -> Result<Ipv4Addr> {
+            match value {
+                Value::Varchar(Some(v)) => v.parse().map_err(Error::new),
+                v => Err(Error::msg(format!("Cannot decode a Ipv4Addr from {:?}", v))),
+            }
+        }
+    }
+
+    mod ipv4_bits {
+        use std::net::Ipv4Addr;
+        use tank::{AsValue, Result, Value};
+
+        pub fn as_empty_value() -> Value {
+            Value::UInt32(None)
+        }
+        pub fn to_value(value: &Option<Ipv4Addr>) -> Value {
+            Value::UInt32(value.map(u32::from))
+        }
+        pub fn from_value(value: Value) -> Result<Option<Ipv4Addr>> {
+            Ok(Option::<u32>::try_from_value(value)?.map(Ipv4Addr::from))
+        }
+    }
+
+    #[derive(Entity, Debug, PartialEq)]
+    #[tank(name = "hosts")]
+    struct Host {
+        #[tank(primary_key)]
+        id: u32,
+        #[tank(with = ipv4)]
+        address: Ipv4Addr,
+        #[tank(with = ipv4_bits)]
+        gateway: Option<Ipv4Addr>,
+    }
+
+    const WRITER: GenericSqlWriter = GenericSqlWriter {};
+
+    #[test]
+    fn test_codec_columns() {
+        let columns = Host::columns();
+        assert!(matches!(columns[1].value, Value::Varchar(None)));
+        assert!(!columns[1].nullable);
+        assert!(matches!(columns[2].value, Value::UInt32(None)));
+        assert!(columns[2].nullable);
+    }
+
+    #[test]
+    fn test_codec_create_table() {
+        let mut query = String::new();
+        WRITER.write_create_table::<Host>(&mut query, false);
+        assert_eq!(
+            query,
+            indoc! {r#"
+                CREATE TABLE "hosts" (
+                "id" UINTEGER PRIMARY KEY,
+                "address" VARCHAR NOT NULL,
+                "gateway" UINTEGER);
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_codec_insert() {
+        let mut query = String::new();
+        let host = Host {
+            id: 1,
+            address: Ipv4Addr::new(10, 0, 0, 7),
+            gateway: Some(Ipv4Addr::new(10, 0, 0, 1)),
+        };
+        WRITER.write_insert(&mut query, [&host], false);
+        assert_eq!(
+            query,
+            indoc! {r#"
+                INSERT INTO "hosts" ("id", "address", "gateway") VALUES
+                (1, '10.0.0.7', 167772161);
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_codec_from_row() {
+        let row = RowLabeled::new(
+            Arc::from(
+                ["id", "address", "gateway"]
+                    .map(String::from)
+                    .into_iter()
+                    .collect::<Vec<_>>(),
+            ),
+            vec![
+                Value::UInt32(Some(2)),
+                Value::Varchar(Some("192.168.1.20".into())),
+                Value::UInt32(None),
+            ]
+            .into(),
+        );
+        assert_eq!(
+            Host::from_row(row).expect("Failed to decode the host"),
+            Host {
+                id: 2,
+                address: Ipv4Addr::new(192, 168, 1, 20),
+                gateway: None,
+            }
+        );
+        let row = RowLabeled::new(
+            Arc::from(["address".to_string()]),
+            vec![Value::Varchar(Some("not an address".into()))].into(),
+        );
+        assert!(Host::from_row(row).is_err());
+    }
+}
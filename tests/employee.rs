@@ -261,4 +261,34 @@ mod tests {
             .trim()
         );
     }
+
+    #[test]
+    fn test_sql_count() {
+        let mut query = String::new();
+        WRITER.write_count::<Employee>(&mut query, &expr!(Employee::salary > 50000.0));
+        assert_eq!(
+            query,
+            indoc! {r#"
+                SELECT COUNT(*)
+                FROM "company"."employee"
+                WHERE "salary" > 50000.0;
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_sql_exists() {
+        let mut query = String::new();
+        WRITER.write_exists::<Employee>(&mut query, &expr!(Employee::deleted == false));
+        assert_eq!(
+            query,
+            indoc! {r#"
+                SELECT EXISTS (SELECT 1
+                FROM "company"."employee"
+                WHERE "deleted" = false);
+            "#}
+            .trim()
+        );
+    }
 }
@@ -0,0 +1,286 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use tank::{
+        Action, ColumnSchema, Entity, ForeignKeySchema, TableName, TableSchema, UniqueSchema,
+        Value, sql_type_to_value, value_to_rust_type, write_entities,
+    };
+
+    // Output of `test_write_entity`, compiled
+    mod generated {
+        use tank::Entity;
+
+        #[derive(Debug, Clone, Entity)]
+        #[tank(schema = "army", name = "soldiers", unique = (Self::unit, Self::callsign))]
+        pub struct Soldiers {
+            #[tank(primary_key)]
+            pub id: i64,
+            #[tank(references = army.units(id), on_delete = cascade)]
+            pub unit: i32,
+            /// Radio callsign
+            /// As heard on the field
+            #[tank(column_type = (postgres = "character varying(32)"))]
+            pub callsign: Option<String>,
+            #[tank(name = "type", unique)]
+            pub type_: String,
+            #[tank(name = "signalStrength")]
+            pub signal_strength: tank::FixedDecimal<5, 2>,
+            #[tank(column_type = (postgres = "geography"))]
+            pub position: Option<String>,
+            pub enlisted: Option<time::Date>,
+        }
+
+        // Output of `test_write_entity_sqlite`, compiled
+        #[derive(Debug, Clone, Entity)]
+        #[tank(name = "beacons")]
+        pub struct Beacons {
+            #[tank(primary_key)]
+            pub id: i64,
+            pub frequency: f64,
+        }
+
+        // Output of `test_write_entities_clashing_names`, compiled
+        #[derive(Debug, Clone, Entity)]
+        #[tank(schema = "army", name = "radio_logs")]
+        pub struct RadioLogs {
+            #[tank(primary_key)]
+            pub id: i64,
+        }
+
+        #[derive(Debug, Clone, Entity)]
+        #[tank(schema = "navy", name = "radio_logs")]
+        pub struct RadioLogs2 {
+            #[tank(primary_key, name = "ID")]
+            pub id: i64,
+            #[tank(name = "signalStrength")]
+            pub signal_strength: Option<i32>,
+            #[tank(name = "signal_strength")]
+            pub signal_strength_2: Option<i32>,
+            #[tank(name = "Signal Strength")]
+            pub signal_strength_3: Option<i32>,
+        }
+    }
+
+    fn column(
+        name: &str,
+        column_type: &str,
+        nullable: bool,
+        comment: Option<&str>,
+    ) -> ColumnSchema {
+        ColumnSchema {
+            name: name.into(),
+            column_type: column_type.into(),
+            value: sql_type_to_value(column_type),
+            nullable,
+            default: None,
+            comment: comment.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn test_value_to_rust_type() {
+        let rust_type = |column_type: &str| value_to_rust_type(&sql_type_to_value(column_type));
+        assert_eq!(rust_type("BOOLEAN").as_deref(), Some("bool"));
+        assert_eq!(rust_type("INT UNSIGNED").as_deref(), Some("u32"));
+        assert_eq!(
+            rust_type("NUMERIC").as_deref(),
+            Some("rust_decimal::Decimal")
+        );
+        assert_eq!(
+            rust_type("DECIMAL(10,2)").as_deref(),
+            Some("tank::FixedDecimal<10, 2>")
+        );
+        assert_eq!(rust_type("BYTEA").as_deref(), Some("Box<[u8]>"));
+        assert_eq!(
+            rust_type("TIMESTAMP WITH TIME ZONE").as_deref(),
+            Some("time::OffsetDateTime")
+        );
+        assert_eq!(rust_type("INTEGER[]").as_deref(), Some("Vec<i32>"));
+        assert_eq!(rust_type("DOUBLE[3]").as_deref(), Some("[f64; 3]"));
+        assert_eq!(
+            rust_type("MAP(VARCHAR, UUID)").as_deref(),
+            Some("std::collections::HashMap<String, uuid::Uuid>")
+        );
+        assert_eq!(rust_type("JSONB"), None);
+        assert_eq!(rust_type("geography"), None);
+        assert_eq!(value_to_rust_type(&Value::Unknown(None)), None);
+    }
+
+    #[test]
+    fn test_write_entity() {
+        let table = TableSchema {
+            table: TableName::new("army", "soldiers"),
+            columns: vec![
+                column("id", "bigint", false, None),
+                column("unit", "integer", false, None),
+                column(
+                    "callsign",
+                    "character varying(32)",
+                    true,
+                    Some("Radio callsign\nAs heard on the field"),
+                ),
+                column("type", "text", false, None),
+                column("signalStrength", "numeric(5,2)", false, None),
+                column("position", "geography", true, None),
+                column("enlisted", "date", true, None),
+            ],
+            primary_key: vec!["id".into()],
+            unique: vec![
+                UniqueSchema {
+                    name: "soldiers_unit_callsign_key".into(),
+                    columns: vec!["unit".into(), "callsign".into()],
+                },
+                UniqueSchema {
+                    name: "soldiers_type_key".into(),
+                    columns: vec!["type".into()],
+                },
+            ],
+            foreign_keys: vec![ForeignKeySchema {
+                name: "soldiers_unit_fkey".into(),
+                columns: vec!["unit".into()],
+                references: TableName::new("army", "units"),
+                references_columns: vec!["id".into()],
+                on_delete: Some(Action::Cascade),
+                on_update: Some(Action::NoAction),
+            }],
+            indexes: vec![],
+        };
+        let mut source = String::new();
+        write_entities(&mut source, &[table], "postgres");
+        assert_eq!(
+            source,
+            indoc! {r#"
+                use tank::Entity;
+
+                #[derive(Debug, Clone, Entity)]
+                #[tank(schema = "army", name = "soldiers", unique = (Self::unit, Self::callsign))]
+                pub struct Soldiers {
+                    #[tank(primary_key)]
+                    pub id: i64,
+                    #[tank(references = army.units(id), on_delete = cascade)]
+                    pub unit: i32,
+                    /// Radio callsign
+                    /// As heard on the field
+                    #[tank(column_type = (postgres = "character varying(32)"))]
+                    pub callsign: Option<String>,
+                    #[tank(name = "type", unique)]
+                    pub type_: String,
+                    #[tank(name = "signalStrength")]
+                    pub signal_strength: tank::FixedDecimal<5, 2>,
+                    #[tank(column_type = (postgres = "geography"))]
+                    pub position: Option<String>,
+                    pub enlisted: Option<time::Date>,
+                }
+            "#}
+        );
+
+        // The generated entity matches the table
+        let columns = generated::Soldiers::columns();
+        assert_eq!(generated::Soldiers::table().name, "soldiers");
+        assert_eq!(
+            columns.iter().map(|v| v.name()).collect::<Vec<_>>(),
+            [
+                "id",
+                "unit",
+                "callsign",
+                "type",
+                "signalStrength",
+                "position",
+                "enlisted"
+            ]
+        );
+        assert_eq!(columns[2].comment, "Radio callsign\nAs heard on the field");
+        assert!(columns[3].unique);
+    }
+
+    #[test]
+    fn test_write_entity_sqlite() {
+        // SQLite reports the declared type, `INTEGER` and `REAL` hold 64 bits
+        let table = TableSchema {
+            table: TableName::new("", "beacons"),
+            columns: vec![
+                column("id", "INTEGER", false, None),
+                column("frequency", "REAL", false, None),
+            ],
+            primary_key: vec!["id".into()],
+            unique: vec![],
+            foreign_keys: vec![],
+            indexes: vec![],
+        };
+        let mut source = String::new();
+        write_entities(&mut source, &[table], "sqlite");
+        assert_eq!(
+            source,
+            indoc! {r#"
+                use tank::Entity;
+
+                #[derive(Debug, Clone, Entity)]
+                #[tank(name = "beacons")]
+                pub struct Beacons {
+                    #[tank(primary_key)]
+                    pub id: i64,
+                    pub frequency: f64,
+                }
+            "#}
+        );
+        let columns = generated::Beacons::columns();
+        assert!(matches!(columns[0].value, Value::Int64(..)));
+        assert!(matches!(columns[1].value, Value::Float64(..)));
+    }
+
+    #[test]
+    fn test_write_entities_clashing_names() {
+        let army = TableSchema {
+            table: TableName::new("army", "radio_logs"),
+            columns: vec![column("id", "BIGINT", false, None)],
+            primary_key: vec!["id".into()],
+            unique: vec![],
+            foreign_keys: vec![],
+            indexes: vec![],
+        };
+        let navy = TableSchema {
+            table: TableName::new("navy", "radio_logs"),
+            columns: vec![
+                column("ID", "BIGINT", false, None),
+                column("signalStrength", "INTEGER", true, None),
+                column("signal_strength", "INTEGER", true, None),
+                column("Signal Strength", "INTEGER", true, None),
+            ],
+            primary_key: vec!["ID".into()],
+            unique: vec![],
+            foreign_keys: vec![],
+            indexes: vec![],
+        };
+        let mut source = String::new();
+        write_entities(&mut source, &[army, navy], "postgres");
+        assert_eq!(
+            source,
+            indoc! {r#"
+                use tank::Entity;
+
+                #[derive(Debug, Clone, Entity)]
+                #[tank(schema = "army", name = "radio_logs")]
+                pub struct RadioLogs {
+                    #[tank(primary_key)]
+                    pub id: i64,
+                }
+
+                #[derive(Debug, Clone, Entity)]
+                #[tank(schema = "navy", name = "radio_logs")]
+                pub struct RadioLogs2 {
+                    #[tank(primary_key, name = "ID")]
+                    pub id: i64,
+                    #[tank(name = "signalStrength")]
+                    pub signal_strength: Option<i32>,
+                    #[tank(name = "signal_strength")]
+                    pub signal_strength_2: Option<i32>,
+                    #[tank(name = "Signal Strength")]
+                    pub signal_strength_3: Option<i32>,
+                }
+            "#}
+        );
+        let columns = generated::RadioLogs2::columns();
+        assert_eq!(columns[2].name(), "signal_strength");
+        assert_eq!(columns[3].name(), "Signal Strength");
+    }
+}
@@ -175,6 +175,19 @@ mod tests {
         expr.write_query(&WRITER, &mut Context::new_qualify(false), &mut out);
         assert_eq!(out, "'log.txt' NOT GLOB 'src/**/log.{txt,csv}'");
 
+        let expr = expr!(2 == [1, 2, [3, 4]] as IN);
+        assert!(matches!(
+            expr,
+            BinaryOp {
+                op: BinaryOpType::In,
+                lhs: Operand::LitInt(2),
+                rhs: Operand::LitArray(..),
+            }
+        ));
+        let mut out = String::new();
+        expr.write_query(&WRITER, &mut Context::new_qualify(false), &mut out);
+        assert_eq!(out, "2 IN (1, 2, [3, 4])");
+
         let expr = expr!(CAST(true as i32));
         assert!(matches!(
             expr,
@@ -422,6 +435,26 @@ mod tests {
         let mut out = String::new();
         expr.write_query(&WRITER, &mut Context::new_qualify(false), &mut out);
         assert_eq!(out, "[-1,-2,-3,-4][2 + 1] + 60");
+
+        let ids = vec![10, 20, 30];
+        let expr = expr!(#one != #ids as IN);
+        assert!(matches!(
+            expr,
+            BinaryOp {
+                op: BinaryOpType::NotIn,
+                lhs: Operand::Variable(Value::Int32(Some(1))),
+                rhs: Operand::Variable(Value::List(Some(..), ..)),
+            }
+        ));
+        let mut out = String::new();
+        expr.write_query(&WRITER, &mut Context::new_qualify(false), &mut out);
+        assert_eq!(out, "1 NOT IN (10, 20, 30)");
+
+        let empty: Vec<i32> = vec![];
+        let expr = expr!(#three == #empty as IN);
+        let mut out = String::new();
+        expr.write_query(&WRITER, &mut Context::new_qualify(false), &mut out);
+        assert_eq!(out, "3 IN (NULL)");
     }
 
     #[test]
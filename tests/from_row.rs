@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use tank::{Entity, FromRow, RowLabeled, Value};
+
+    #[derive(FromRow, Debug, PartialEq)]
+    struct Callsign {
+        #[tank(name = "callsign")]
+        name: String,
+        messages: i64,
+        #[tank(optional)]
+        last_message: Option<String>,
+        #[tank(ignore)]
+        note: String,
+    }
+
+    #[derive(FromRow, Debug, PartialEq)]
+    struct Strict {
+        callsign: String,
+        last_message: Option<String>,
+    }
+
+    #[derive(Entity, Debug, PartialEq)]
+    struct Operator {
+        #[tank(primary_key)]
+        callsign: String,
+        last_message: Option<String>,
+    }
+
+    #[derive(FromRow, Debug, Default, PartialEq)]
+    struct Totals {
+        count: u32,
+        average: f64,
+    }
+
+    fn row(labels: &[&str], values: Vec<Value>) -> RowLabeled {
+        RowLabeled::new(
+            Arc::from(labels.iter().map(|v| v.to_string()).collect::<Vec<_>>()),
+            values.into(),
+        )
+    }
+
+    #[test]
+    fn test_from_row() {
+        let value = Callsign::from_row(row(
+            &["callsign", "messages", "last_message"],
+            vec![
+                Value::Varchar(Some("Viper".into())),
+                Value::Int64(Some(4)),
+                Value::Varchar(Some("All clear.".into())),
+            ],
+        ))
+        .expect("Could not decode the row");
+        assert_eq!(
+            value,
+            Callsign {
+                name: "Viper".into(),
+                messages: 4,
+                last_message: Some("All clear.".into()),
+                note: String::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_row_missing_columns() {
+        let value = Callsign::from_row(row(
+            &["messages", "callsign"],
+            vec![
+                Value::Int64(Some(12)),
+                Value::Varchar(Some("SteelHammer".into())),
+            ],
+        ))
+        .expect("Optional fields must not be required");
+        assert_eq!(
+            value,
+            Callsign {
+                name: "SteelHammer".into(),
+                messages: 12,
+                last_message: None,
+                note: String::new(),
+            }
+        );
+        assert!(
+            Callsign::from_row(row(
+                &["callsign"],
+                vec![Value::Varchar(Some("Viper".into()))]
+            ))
+            .is_err(),
+            "Missing mandatory column must be an error"
+        );
+        assert!(
+            Strict::from_row(row(
+                &["callsign"],
+                vec![Value::Varchar(Some("Viper".into()))]
+            ))
+            .is_err(),
+            "Missing column of an `Option` field not marked `optional` must be an error"
+        );
+        assert!(
+            Operator::from_row(row(
+                &["callsign"],
+                vec![Value::Varchar(Some("Viper".into()))]
+            ))
+            .is_err(),
+            "Missing column of an `Option` field of an entity must be an error"
+        );
+
+        let value = Totals::from_row(row(&["average"], vec![Value::Float64(Some(2.5))]))
+            .expect("Missing fields must be defaulted");
+        assert_eq!(
+            value,
+            Totals {
+                count: 0,
+                average: 2.5,
+            }
+        );
+        assert!(
+            Totals::from_row(row(&["count"], vec![Value::Varchar(Some("many".into()))])).is_err(),
+            "Type mismatch must be an error"
+        );
+    }
+}
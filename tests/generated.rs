@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use tank::{Entity, GenericSqlWriter, SqlWriter};
+
+    #[derive(Entity)]
+    #[tank(name = "order_lines")]
+    struct OrderLine {
+        #[tank(primary_key)]
+        id: u32,
+        quantity: i32,
+        unit_price: i64,
+        #[tank(generated = quantity * unit_price, stored)]
+        total: i64,
+        #[tank(generated = quantity >= 10)]
+        bulk: bool,
+    }
+
+    impl OrderLine {
+        fn new(id: u32, quantity: i32, unit_price: i64) -> Self {
+            Self {
+                id,
+                quantity,
+                unit_price,
+                total: 0,
+                bulk: false,
+            }
+        }
+    }
+
+    const WRITER: GenericSqlWriter = GenericSqlWriter {};
+
+    #[test]
+    fn test_generated_columns() {
+        let columns = OrderLine::columns();
+        assert!(!columns[2].is_generated());
+        assert!(columns[3].is_generated());
+        assert!(columns[3].stored);
+        assert!(columns[4].is_generated());
+        assert!(!columns[4].stored);
+        assert_eq!(
+            OrderLine::new(1, 2, 3)
+                .row_filtered()
+                .iter()
+                .map(|(name, _)| *name)
+                .collect::<Vec<_>>(),
+            ["id", "quantity", "unit_price"]
+        );
+    }
+
+    #[test]
+    fn test_generated_create_table() {
+        let mut query = String::new();
+        WRITER.write_create_table::<OrderLine>(&mut query, false);
+        assert_eq!(
+            query,
+            indoc! {r#"
+                CREATE TABLE "order_lines" (
+                "id" UINTEGER PRIMARY KEY,
+                "quantity" INTEGER NOT NULL,
+                "unit_price" BIGINT NOT NULL,
+                "total" BIGINT GENERATED ALWAYS AS (quantity * unit_price) STORED,
+                "bulk" BOOLEAN GENERATED ALWAYS AS (quantity >= 10) VIRTUAL);
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_generated_insert() {
+        let mut query = String::new();
+        WRITER.write_insert(&mut query, [&OrderLine::new(1, 4, 250)], true);
+        assert_eq!(
+            query,
+            indoc! {r#"
+                INSERT INTO "order_lines" ("id", "quantity", "unit_price") VALUES
+                (1, 4, 250)
+                ON CONFLICT ("id") DO UPDATE SET
+                "quantity" = EXCLUDED."quantity",
+                "unit_price" = EXCLUDED."unit_price";
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_generated_insert_multiple() {
+        let mut query = String::new();
+        WRITER.write_insert(
+            &mut query,
+            [OrderLine::new(1, 4, 250), OrderLine::new(2, 12, 90)].iter(),
+            true,
+        );
+        assert_eq!(
+            query,
+            indoc! {r#"
+                INSERT INTO "order_lines" ("id", "quantity", "unit_price") VALUES
+                (1, 4, 250),
+                (2, 12, 90)
+                ON CONFLICT ("id") DO UPDATE SET
+                "quantity" = EXCLUDED."quantity",
+                "unit_price" = EXCLUDED."unit_price";
+            "#}
+            .trim()
+        );
+    }
+}
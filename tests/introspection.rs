@@ -0,0 +1,283 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use std::sync::Arc;
+    use tank::{
+        Action, ColumnSchema, ForeignKeySchema, GenericSqlWriter, IndexSchema, RowLabeled,
+        SqlWriter, TableName, TableSchema, UniqueSchema, Value, sql_type_to_value,
+    };
+
+    const WRITER: GenericSqlWriter = GenericSqlWriter {};
+
+    fn rows(labels: &[&str], rows: Vec<Vec<Value>>) -> Vec<RowLabeled> {
+        let labels: Arc<[String]> = labels.iter().map(|v| v.to_string()).collect();
+        rows.into_iter()
+            .map(|v| RowLabeled::new(labels.clone(), v.into()))
+            .collect()
+    }
+
+    fn text(value: &str) -> Value {
+        Value::Varchar(Some(value.into()))
+    }
+
+    #[test]
+    fn test_sql_type_to_value() {
+        assert!(matches!(sql_type_to_value("INTEGER"), Value::Int32(None)));
+        assert!(matches!(
+            sql_type_to_value("int unsigned"),
+            Value::UInt32(None)
+        ));
+        assert!(matches!(
+            sql_type_to_value("int(11) unsigned"),
+            Value::UInt32(None)
+        ));
+        assert!(matches!(
+            sql_type_to_value("tinyint(1)"),
+            Value::Boolean(None)
+        ));
+        assert!(matches!(sql_type_to_value("UBIGINT"), Value::UInt64(None)));
+        assert!(matches!(
+            sql_type_to_value("double precision"),
+            Value::Float64(None)
+        ));
+        assert!(matches!(
+            sql_type_to_value("numeric(10,2)"),
+            Value::Decimal(None, 10, 2)
+        ));
+        assert!(matches!(
+            sql_type_to_value("character varying(64)"),
+            Value::Varchar(None)
+        ));
+        assert!(matches!(sql_type_to_value("CHAR(1)"), Value::Char(None)));
+        assert!(matches!(
+            sql_type_to_value("char(36)"),
+            Value::Varchar(None)
+        ));
+        assert!(matches!(
+            sql_type_to_value("timestamp(3) with time zone"),
+            Value::TimestampWithTimezone(None)
+        ));
+        assert!(matches!(
+            sql_type_to_value("datetime"),
+            Value::Timestamp(None)
+        ));
+        assert!(matches!(sql_type_to_value("uuid"), Value::Uuid(None)));
+        assert!(matches!(sql_type_to_value("jsonb"), Value::Json(None)));
+        assert!(matches!(sql_type_to_value("polygon"), Value::Unknown(None)));
+        assert_eq!(
+            sql_type_to_value("integer[]"),
+            Value::List(None, Value::Int32(None).into())
+        );
+        assert_eq!(
+            sql_type_to_value("INTEGER[3]"),
+            Value::Array(None, Value::Int32(None).into(), 3)
+        );
+        assert_eq!(
+            sql_type_to_value("MAP(VARCHAR, DOUBLE)"),
+            Value::Map(
+                None,
+                Value::Varchar(None).into(),
+                Value::Float64(None).into()
+            )
+        );
+    }
+
+    #[test]
+    fn test_catalog_queries() {
+        let mut query = String::new();
+        WRITER.write_select_table_columns(&mut query, &TableName::new("", "it's"));
+        assert_eq!(
+            query,
+            indoc! {r#"
+                SELECT column_name, data_type, is_nullable = 'YES', column_default, NULL
+                FROM information_schema.columns
+                WHERE table_schema = current_schema() AND table_name = 'it''s'
+                ORDER BY ordinal_position;
+            "#}
+            .trim()
+        );
+        let mut query = String::new();
+        WRITER.write_select_tables(&mut query, Some("army"));
+        assert_eq!(
+            query,
+            indoc! {r#"
+                SELECT table_schema, table_name
+                FROM information_schema.tables
+                WHERE table_type = 'BASE TABLE' AND table_schema = 'army'
+                ORDER BY table_schema, table_name;
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_decode_table_schema() {
+        let columns = rows(
+            &["name", "type", "nullable", "default", "comment"],
+            vec![
+                vec![
+                    text("id"),
+                    text("BIGINT"),
+                    Value::Boolean(Some(false)),
+                    Value::Null,
+                    Value::Null,
+                ],
+                vec![
+                    text("unit"),
+                    text("INTEGER"),
+                    Value::Int32(Some(0)),
+                    Value::Null,
+                    text(""),
+                ],
+                vec![
+                    text("callsign"),
+                    text("VARCHAR"),
+                    Value::Boolean(Some(true)),
+                    text("'none'"),
+                    text("Radio callsign"),
+                ],
+            ],
+        );
+        let constraint = |name: &str, kind: &str, column: &str, position: i32| {
+            vec![
+                text(name),
+                text(kind),
+                text(column),
+                Value::Int32(Some(position)),
+                Value::Null,
+                Value::Null,
+                Value::Null,
+                Value::Null,
+                Value::Null,
+            ]
+        };
+        let constraints = rows(
+            &[
+                "name", "type", "column", "position", "schema", "table", "ref", "upd", "del",
+            ],
+            vec![
+                constraint("pk", "PRIMARY KEY", "id", 1),
+                constraint("uq", "UNIQUE", "callsign", 2),
+                constraint("uq", "UNIQUE", "unit", 1),
+                vec![
+                    text("fk"),
+                    text("FOREIGN KEY"),
+                    text("unit"),
+                    Value::Int32(Some(1)),
+                    text("army"),
+                    text("units"),
+                    text("id"),
+                    text("NO ACTION"),
+                    text("CASCADE"),
+                ],
+            ],
+        );
+        let indexes = rows(
+            &["name", "unique", "column"],
+            vec![
+                vec![text("idx"), Value::Boolean(Some(false)), text("unit")],
+                vec![text("idx"), Value::Boolean(Some(false)), text("id")],
+            ],
+        );
+        let table = TableSchema::decode(
+            TableName::new("army", "soldiers"),
+            columns,
+            constraints,
+            indexes,
+        )
+        .expect("Failed to decode the table")
+        .expect("Table must exist");
+        assert_eq!(table.table.to_string(), "army.soldiers");
+        assert_eq!(table.columns.len(), 3);
+        assert!(!table.column("id").unwrap().nullable);
+        assert!(!table.column("unit").unwrap().nullable);
+        assert!(matches!(
+            table.column("unit").unwrap().value,
+            Value::Int32(None)
+        ));
+        assert_eq!(
+            table.column("callsign").unwrap().default.as_deref(),
+            Some("'none'")
+        );
+        assert_eq!(table.column("unit").unwrap().comment, None);
+        assert_eq!(
+            table.column("callsign").unwrap().comment.as_deref(),
+            Some("Radio callsign")
+        );
+        assert_eq!(table.primary_key, ["id"]);
+        assert_eq!(table.unique.len(), 1);
+        assert_eq!(table.unique[0].columns, ["unit", "callsign"]);
+        assert_eq!(table.foreign_keys.len(), 1);
+        assert_eq!(
+            table.foreign_keys[0].references,
+            TableName::new("army", "units")
+        );
+        assert_eq!(table.foreign_keys[0].references_columns, ["id"]);
+        assert_eq!(table.foreign_keys[0].on_update, Some(Action::NoAction));
+        assert_eq!(table.foreign_keys[0].on_delete, Some(Action::Cascade));
+        assert_eq!(table.indexes.len(), 1);
+        assert_eq!(table.indexes[0].columns, ["unit", "id"]);
+        assert!(
+            TableSchema::decode(TableName::new("", "missing"), vec![], vec![], vec![])
+                .expect("Failed to decode the table")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_write_create_table_schema() {
+        let column =
+            |name: &str, column_type: &str, nullable: bool, default: Option<&str>| ColumnSchema {
+                name: name.into(),
+                column_type: column_type.into(),
+                value: sql_type_to_value(column_type),
+                nullable,
+                default: default.map(Into::into),
+                comment: None,
+            };
+        let table = TableSchema {
+            table: TableName::new("army", "soldiers"),
+            columns: vec![
+                column("id", "bigint", false, None),
+                column("unit", "integer", false, None),
+                column("callsign", "character varying(32)", true, Some("'none'")),
+                column("position", "geography", true, None),
+            ],
+            primary_key: vec!["id".into()],
+            unique: vec![UniqueSchema {
+                name: "soldiers_unit_callsign_key".into(),
+                columns: vec!["unit".into(), "callsign".into()],
+            }],
+            foreign_keys: vec![ForeignKeySchema {
+                name: "soldiers_unit_fkey".into(),
+                columns: vec!["unit".into()],
+                references: TableName::new("army", "units"),
+                references_columns: vec!["id".into()],
+                on_delete: Some(Action::Cascade),
+                on_update: None,
+            }],
+            indexes: vec![IndexSchema {
+                name: "soldiers_callsign_idx".into(),
+                columns: vec!["callsign".into()],
+                unique: false,
+            }],
+        };
+        let mut query = String::new();
+        WRITER.write_create_table_schema(&mut query, &table, true);
+        assert_eq!(
+            query,
+            indoc! {r#"
+                CREATE TABLE IF NOT EXISTS "army"."soldiers" (
+                "id" BIGINT,
+                "unit" INTEGER NOT NULL,
+                "callsign" VARCHAR(32) DEFAULT 'none',
+                "position" geography,
+                PRIMARY KEY ("id"),
+                UNIQUE ("unit", "callsign"),
+                FOREIGN KEY ("unit") REFERENCES "army"."units"("id") ON DELETE CASCADE);
+                CREATE INDEX IF NOT EXISTS "soldiers_callsign_idx" ON "army"."soldiers" ("callsign");
+            "#}
+            .trim()
+        );
+    }
+}
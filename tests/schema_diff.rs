@@ -0,0 +1,280 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use tank::{
+        ColumnSchema, Entity, ForeignKeySchema, GenericSqlWriter, IndexSchema, SchemaChange,
+        SchemaDiff, SqlWriter, TableName, TableSchema, UniqueSchema, sql_type_to_value,
+    };
+
+    #[derive(Entity)]
+    #[tank(schema = "army", name = "units")]
+    struct Unit {
+        #[tank(primary_key)]
+        id: i32,
+    }
+
+    #[derive(Entity)]
+    #[tank(schema = "army", name = "soldiers", unique = (Self::unit, Self::callsign))]
+    struct Soldier {
+        #[tank(primary_key)]
+        id: i64,
+        #[tank(references = Unit::id)]
+        unit: i32,
+        callsign: String,
+        #[tank(default = 1)]
+        rank: i16,
+        nickname: Option<String>,
+    }
+
+    #[derive(Entity)]
+    #[tank(schema = "army", name = "squads")]
+    struct Squad {
+        #[tank(primary_key)]
+        id: i32,
+        #[tank(default = "a::b")]
+        motto: String,
+    }
+
+    const WRITER: GenericSqlWriter = GenericSqlWriter {};
+
+    fn column(
+        name: &str,
+        column_type: &str,
+        nullable: bool,
+        default: Option<&str>,
+    ) -> ColumnSchema {
+        ColumnSchema {
+            name: name.into(),
+            column_type: column_type.into(),
+            value: sql_type_to_value(column_type),
+            nullable,
+            default: default.map(Into::into),
+            comment: None,
+        }
+    }
+
+    fn unit_foreign_key() -> ForeignKeySchema {
+        ForeignKeySchema {
+            name: "soldiers_unit_fkey".into(),
+            columns: vec!["unit".into()],
+            references: TableName::new("army", "units"),
+            references_columns: vec!["id".into()],
+            on_delete: None,
+            on_update: None,
+        }
+    }
+
+    #[test]
+    fn test_schema_diff_in_sync() {
+        let live = TableSchema {
+            table: TableName::new("army", "soldiers"),
+            columns: vec![
+                column("id", "bigint", false, None),
+                column("unit", "integer", false, None),
+                column("callsign", "character varying", false, None),
+                column("rank", "smallint", false, Some("'1'::smallint")),
+                column("nickname", "character varying", true, None),
+            ],
+            primary_key: vec!["id".into()],
+            unique: vec![UniqueSchema {
+                name: "soldiers_unit_callsign_key".into(),
+                columns: vec!["callsign".into(), "unit".into()],
+            }],
+            foreign_keys: vec![unit_foreign_key()],
+            indexes: vec![],
+        };
+        let diff = SchemaDiff::compute::<Soldier>(&WRITER, &live);
+        assert!(diff.is_empty(), "Unexpected changes: {:?}", diff.changes);
+    }
+
+    #[test]
+    fn test_schema_diff_default_with_colons() {
+        // The same default as reported by MySQL, SQLite, DuckDB and Postgres
+        for default in ["a::b", "'a::b'", "('a::b')", "'a::b'::character varying"] {
+            let live = TableSchema {
+                table: TableName::new("army", "squads"),
+                columns: vec![
+                    column("id", "integer", false, None),
+                    column("motto", "character varying", false, Some(default)),
+                ],
+                primary_key: vec!["id".into()],
+                unique: vec![],
+                foreign_keys: vec![],
+                indexes: vec![],
+            };
+            let diff = SchemaDiff::compute::<Squad>(&WRITER, &live);
+            assert!(
+                diff.is_empty(),
+                "Unexpected changes for {default}: {:?}",
+                diff.changes
+            );
+        }
+    }
+
+    #[test]
+    fn test_schema_diff_changes() {
+        let live = TableSchema {
+            table: TableName::new("army", "soldiers"),
+            columns: vec![
+                column("id", "BIGINT", false, None),
+                column("unit", "INTEGER", false, None),
+                column("callsign", "VARCHAR", true, None),
+                column("rank", "INTEGER", false, None),
+                column("legacy", "TEXT", true, Some("('none')")),
+            ],
+            primary_key: vec!["id".into()],
+            unique: vec![UniqueSchema {
+                name: "soldiers_callsign_key".into(),
+                columns: vec!["callsign".into()],
+            }],
+            foreign_keys: vec![],
+            indexes: vec![],
+        };
+        let diff = SchemaDiff::compute::<Soldier>(&WRITER, &live);
+        assert!(diff.is_destructive());
+        assert!(matches!(
+            diff.changes.as_slice(),
+            [
+                SchemaChange::DropConstraint(..),
+                SchemaChange::AddColumn(..),
+                SchemaChange::SetNotNull(..),
+                SchemaChange::AlterColumnType(..),
+                SchemaChange::SetDefault(..),
+                SchemaChange::AddUnique(..),
+                SchemaChange::AddForeignKey(..),
+                SchemaChange::DropColumn(..),
+            ]
+        ));
+
+        let mut query = String::new();
+        WRITER.write_schema_diff(&mut query, &diff, false);
+        assert_eq!(
+            query,
+            indoc! {r#"
+                ALTER TABLE "army"."soldiers" ADD COLUMN "nickname" VARCHAR;
+                ALTER TABLE "army"."soldiers" ALTER COLUMN "callsign" SET NOT NULL;
+                ALTER TABLE "army"."soldiers" ALTER COLUMN "rank" SET DEFAULT 1;
+                ALTER TABLE "army"."soldiers" ADD UNIQUE ("unit", "callsign");
+                ALTER TABLE "army"."soldiers" ADD FOREIGN KEY ("unit") REFERENCES "army"."units"("id");
+            "#}
+            .trim()
+        );
+
+        let mut query = String::new();
+        WRITER.write_schema_diff(&mut query, &diff, true);
+        assert_eq!(
+            query,
+            indoc! {r#"
+                ALTER TABLE "army"."soldiers" DROP CONSTRAINT "soldiers_callsign_key";
+                ALTER TABLE "army"."soldiers" ADD COLUMN "nickname" VARCHAR;
+                ALTER TABLE "army"."soldiers" ALTER COLUMN "callsign" SET NOT NULL;
+                ALTER TABLE "army"."soldiers" ALTER COLUMN "rank" TYPE SMALLINT;
+                ALTER TABLE "army"."soldiers" ALTER COLUMN "rank" SET DEFAULT 1;
+                ALTER TABLE "army"."soldiers" ADD UNIQUE ("unit", "callsign");
+                ALTER TABLE "army"."soldiers" ADD FOREIGN KEY ("unit") REFERENCES "army"."units"("id");
+                ALTER TABLE "army"."soldiers" DROP COLUMN "legacy";
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_schema_change() {
+        let table = Soldier::table();
+        let rank = &Soldier::columns()[3];
+        let nickname = &Soldier::columns()[4];
+        let mut query = String::new();
+        WRITER.write_schema_change(&mut query, table, &SchemaChange::DropNotNull(rank));
+        WRITER.write_schema_change(&mut query, table, &SchemaChange::DropDefault(rank));
+        WRITER.write_schema_change(&mut query, table, &SchemaChange::AlterColumnType(nickname));
+        WRITER.write_schema_change(
+            &mut query,
+            table,
+            &SchemaChange::DropConstraint("soldiers_unit_fkey".into()),
+        );
+        assert_eq!(
+            query,
+            indoc! {r#"
+                ALTER TABLE "army"."soldiers" ALTER COLUMN "rank" DROP NOT NULL;
+                ALTER TABLE "army"."soldiers" ALTER COLUMN "rank" DROP DEFAULT;
+                ALTER TABLE "army"."soldiers" ALTER COLUMN "nickname" TYPE VARCHAR;
+                ALTER TABLE "army"."soldiers" DROP CONSTRAINT "soldiers_unit_fkey";
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_alter_table_primitives() {
+        let table = Soldier::table();
+        let rank = &Soldier::columns()[3];
+        let nickname = &Soldier::columns()[4];
+        let mut query = String::new();
+        WRITER.write_alter_table_drop_not_null(&mut query, table, rank);
+        WRITER.write_alter_table_drop_default(&mut query, table, rank);
+        WRITER.write_alter_table_alter_type(&mut query, table, nickname);
+        WRITER.write_alter_table_drop_constraint(&mut query, table, "soldiers_unit_fkey");
+        WRITER.write_alter_table_rename_column(&mut query, table, "nickname", "alias");
+        WRITER.write_alter_table_rename_table(&mut query, &table.into(), "troopers");
+        assert_eq!(
+            query,
+            indoc! {r#"
+                ALTER TABLE "army"."soldiers" ALTER COLUMN "rank" DROP NOT NULL;
+                ALTER TABLE "army"."soldiers" ALTER COLUMN "rank" DROP DEFAULT;
+                ALTER TABLE "army"."soldiers" ALTER COLUMN "nickname" TYPE VARCHAR;
+                ALTER TABLE "army"."soldiers" DROP CONSTRAINT "soldiers_unit_fkey";
+                ALTER TABLE "army"."soldiers" RENAME COLUMN "nickname" TO "alias";
+                ALTER TABLE "army"."soldiers" RENAME TO "troopers";
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_alter_table_rebuild() {
+        let live = TableSchema {
+            table: TableName::new("army", "soldiers"),
+            columns: vec![
+                column("id", "BIGINT", false, None),
+                column("unit", "INTEGER", false, None),
+                column("callsign", "INTEGER", true, None),
+                column("legacy", "TEXT", true, None),
+            ],
+            primary_key: vec!["id".into()],
+            indexes: vec![
+                IndexSchema {
+                    name: "soldiers_callsign_idx".into(),
+                    columns: vec!["callsign".into()],
+                    unique: false,
+                },
+                IndexSchema {
+                    name: "soldiers_legacy_idx".into(),
+                    columns: vec!["legacy".into()],
+                    unique: false,
+                },
+            ],
+            ..Default::default()
+        };
+        let mut query = String::new();
+        WRITER.write_alter_table_rebuild::<Soldier>(&mut query, &live);
+        assert_eq!(
+            query,
+            indoc! {r#"
+                CREATE TABLE "army"."soldiers_tank_rebuild" (
+                "id" BIGINT PRIMARY KEY,
+                "unit" INTEGER NOT NULL,
+                "callsign" VARCHAR NOT NULL,
+                "rank" SMALLINT NOT NULL DEFAULT 1,
+                "nickname" VARCHAR,
+                UNIQUE ("unit", "callsign"),
+                FOREIGN KEY ("unit") REFERENCES "army"."units"("id"));
+                INSERT INTO "army"."soldiers_tank_rebuild" ("id", "unit", "callsign")
+                SELECT "id", "unit", "callsign" FROM "army"."soldiers";
+                DROP TABLE "army"."soldiers";
+                ALTER TABLE "army"."soldiers_tank_rebuild" RENAME TO "soldiers";
+                CREATE INDEX "soldiers_callsign_idx" ON "army"."soldiers" ("callsign");
+            "#}
+            .trim()
+        );
+    }
+}
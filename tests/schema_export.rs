@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use tank::{Entity, GenericSqlWriter, SchemaExport, TableName};
+
+    #[derive(Entity)]
+    #[tank(schema = "army", name = "units")]
+    struct Unit {
+        #[tank(primary_key)]
+        id: i32,
+        #[tank(references = Unit::id)]
+        parent: Option<i32>,
+    }
+
+    #[derive(Entity)]
+    #[tank(schema = "army", name = "soldiers")]
+    struct Soldier {
+        #[tank(primary_key)]
+        id: i64,
+        #[tank(references = Unit::id, on_delete = cascade)]
+        unit: i32,
+        /// Radio callsign
+        callsign: String,
+    }
+
+    #[derive(Entity)]
+    #[tank(schema = "operations", name = "missions")]
+    struct Mission {
+        #[tank(primary_key)]
+        id: i64,
+        #[tank(references = Soldier::id)]
+        leader: i64,
+    }
+
+    #[derive(Entity)]
+    #[tank(name = "radio_logs")]
+    struct RadioLog {
+        #[tank(primary_key)]
+        id: i64,
+        message: String,
+    }
+
+    const WRITER: GenericSqlWriter = GenericSqlWriter {};
+
+    #[test]
+    fn test_schema_export() {
+        let export = SchemaExport::new(WRITER)
+            .entity::<Mission>()
+            .entity::<RadioLog>()
+            .entity::<Soldier>()
+            .entity::<Unit>()
+            .entity::<Soldier>();
+        assert_eq!(
+            export.tables(),
+            [
+                &TableName::new("", "radio_logs"),
+                &TableName::new("army", "units"),
+                &TableName::new("army", "soldiers"),
+                &TableName::new("operations", "missions"),
+            ]
+        );
+        assert_eq!(
+            export.to_sql(),
+            indoc! {r#"
+                CREATE TABLE "radio_logs" (
+                "id" BIGINT PRIMARY KEY,
+                "message" VARCHAR NOT NULL);
+                CREATE SCHEMA "army";
+                CREATE TABLE "army"."units" (
+                "id" INTEGER PRIMARY KEY,
+                "parent" INTEGER,
+                FOREIGN KEY ("parent") REFERENCES "army"."units"("id"));
+                CREATE TABLE "army"."soldiers" (
+                "id" BIGINT PRIMARY KEY,
+                "unit" INTEGER NOT NULL,
+                "callsign" VARCHAR NOT NULL,
+                FOREIGN KEY ("unit") REFERENCES "army"."units"("id") ON DELETE CASCADE);
+                COMMENT ON COLUMN "army"."soldiers"."callsign" IS 'Radio callsign';
+                CREATE SCHEMA "operations";
+                CREATE TABLE "operations"."missions" (
+                "id" BIGINT PRIMARY KEY,
+                "leader" BIGINT NOT NULL,
+                FOREIGN KEY ("leader") REFERENCES "army"."soldiers"("id"));
+            "#}
+            .trim_end()
+        );
+    }
+
+    #[test]
+    fn test_schema_export_if_not_exists() {
+        let sql = SchemaExport::new(WRITER)
+            .entity::<Unit>()
+            .if_not_exists(true)
+            .to_sql();
+        assert!(
+            sql.starts_with(indoc! {r#"
+                CREATE SCHEMA IF NOT EXISTS "army";
+                CREATE TABLE IF NOT EXISTS "army"."units" (
+            "#}),
+            "{}",
+            sql
+        );
+        assert!(SchemaExport::new(WRITER).to_sql().is_empty());
+    }
+}
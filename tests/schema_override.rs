@@ -0,0 +1,228 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use std::{
+        collections::BTreeSet,
+        future::{Future, poll_fn},
+        pin::pin,
+        task::{Context, Poll, Waker},
+    };
+    use tank::{
+        Entity, GenericSqlWriter, SqlWriter, expr,
+        future::{self, FutureExt},
+        join, resolve_schema, with_schema, with_schema_sync,
+    };
+
+    #[derive(Entity)]
+    #[tank(schema = "public", name = "currencies")]
+    struct Currency {
+        #[tank(primary_key)]
+        code: String,
+    }
+
+    #[derive(Entity)]
+    #[tank(name = "accounts")]
+    struct Account {
+        #[tank(primary_key)]
+        id: i64,
+        name: String,
+    }
+
+    #[derive(Entity)]
+    #[tank(name = "invoices")]
+    struct Invoice {
+        #[tank(primary_key)]
+        id: i64,
+        #[tank(references = Account::id)]
+        account: i64,
+        #[tank(references = Currency::code)]
+        currency: String,
+        amount: f64,
+    }
+
+    const WRITER: GenericSqlWriter = GenericSqlWriter {};
+
+    #[test]
+    fn test_schema_override_create_table() {
+        let mut query = String::new();
+        with_schema_sync("tenant_42", || {
+            WRITER.write_create_schema::<Account>(&mut query, true);
+            WRITER.write_create_table::<Invoice>(&mut query, true);
+        });
+        assert_eq!(
+            query,
+            indoc! {r#"
+                CREATE SCHEMA IF NOT EXISTS "tenant_42";
+                CREATE TABLE IF NOT EXISTS "tenant_42"."invoices" (
+                "id" BIGINT PRIMARY KEY,
+                "account" BIGINT NOT NULL,
+                "currency" VARCHAR NOT NULL,
+                "amount" DOUBLE NOT NULL,
+                FOREIGN KEY ("account") REFERENCES "tenant_42"."accounts"("id"),
+                FOREIGN KEY ("currency") REFERENCES "public"."currencies"("code"));
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_schema_override_insert_delete() {
+        let mut query = String::new();
+        with_schema_sync("tenant_42", || {
+            WRITER.write_insert(
+                &mut query,
+                [&Account {
+                    id: 1,
+                    name: "Acme".into(),
+                }],
+                false,
+            );
+            WRITER.write_delete::<Account>(&mut query, &expr!(Account::id == 1));
+        });
+        assert_eq!(
+            query,
+            indoc! {r#"
+                INSERT INTO "tenant_42"."accounts" ("id", "name") VALUES
+                (1, 'Acme');
+                DELETE FROM "tenant_42"."accounts"
+                WHERE "id" = 1;
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_schema_override_select() {
+        let mut query = String::new();
+        with_schema_sync("tenant_7", || {
+            WRITER.write_select(
+                &mut query,
+                [Account::name, Invoice::amount],
+                &join!(Invoice JOIN Account ON Invoice::account == Account::id),
+                &expr!(Invoice::amount > 100),
+                None,
+            );
+        });
+        assert_eq!(
+            query,
+            indoc! {r#"
+                SELECT "tenant_7"."accounts"."name", "tenant_7"."invoices"."amount"
+                FROM "tenant_7"."invoices" JOIN "tenant_7"."accounts" ON "tenant_7"."invoices"."account" = "tenant_7"."accounts"."id"
+                WHERE "tenant_7"."invoices"."amount" > 100;
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_schema_override_create_schema() {
+        let mut query = String::new();
+        WRITER.write_create_schema::<Account>(&mut query, true);
+        assert_eq!(query, "");
+        with_schema_sync("tenant_1", || {
+            WRITER.write_create_schema::<Account>(&mut query, true);
+            WRITER.write_drop_schema::<Account>(&mut query, true);
+        });
+        assert_eq!(
+            query,
+            indoc! {r#"
+                CREATE SCHEMA IF NOT EXISTS "tenant_1";
+                DROP SCHEMA IF EXISTS "tenant_1";
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_schema_override_scope() {
+        assert_eq!(resolve_schema(""), "");
+        assert_eq!(Account::table().full_name(), "accounts");
+        with_schema_sync("tenant_1", || {
+            assert_eq!(resolve_schema(""), "tenant_1");
+            assert_eq!(resolve_schema("public"), "public");
+            assert_eq!(Account::table().full_name(), "tenant_1.accounts");
+            assert_eq!(Currency::table().full_name(), "public.currencies");
+            with_schema_sync("tenant_2", || {
+                assert_eq!(Invoice::table().full_name(), "tenant_2.invoices");
+            });
+            assert_eq!(Invoice::table().full_name(), "tenant_1.invoices");
+        });
+        assert_eq!(Invoice::table().full_name(), "invoices");
+        let name = with_schema("tenant_3", async { Account::table().full_name() })
+            .now_or_never()
+            .expect("Future must be ready");
+        assert_eq!(name, "tenant_3.accounts");
+    }
+
+    #[test]
+    fn test_schema_override_interleaved_futures() {
+        let task = |schema| {
+            with_schema(schema, async {
+                let before = Account::table().full_name();
+                let mut yielded = false;
+                poll_fn(|cx| {
+                    if yielded {
+                        return Poll::Ready(());
+                    }
+                    yielded = true;
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                })
+                .await;
+                (before, Account::table().full_name())
+            })
+        };
+        let mut both = pin!(future::join(task("tenant_1"), task("tenant_2")));
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(both.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(resolve_schema(""), "");
+        let Poll::Ready((first, second)) = both.as_mut().poll(&mut cx) else {
+            panic!("Future must be ready");
+        };
+        assert_eq!(first.0, "tenant_1.accounts");
+        assert_eq!(first.1, "tenant_1.accounts");
+        assert_eq!(second.0, "tenant_2.accounts");
+        assert_eq!(second.1, "tenant_2.accounts");
+        assert_eq!(Account::table().full_name(), "accounts");
+    }
+
+    /// Names of the methods declared with `fn` in `source` between `start` and the next `end`.
+    fn method_names(source: &str, start: &str, end: &str) -> BTreeSet<String> {
+        let source = &source[source.find(start).expect("Start marker must be present")..];
+        let source = &source[..source.find(end).expect("End marker must be present")];
+        source
+            .lines()
+            .filter_map(|line| line.trim_start().strip_prefix("fn "))
+            .map(|line| {
+                line.split(|c: char| !c.is_alphanumeric() && c != '_')
+                    .next()
+                    .unwrap_or_default()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_schema_override_forwards_every_method() {
+        let declared = method_names(
+            include_str!("../tank-core/src/writer/sql_writer.rs"),
+            "pub trait SqlWriter {",
+            "\n}\n",
+        );
+        let forwarded = method_names(
+            include_str!("../tank-core/src/schema_override.rs"),
+            "impl<W: SqlWriter> SqlWriter for SchemaSqlWriter<W> {",
+            "\n}\n",
+        );
+        let missing = declared.difference(&forwarded).collect::<Vec<_>>();
+        assert!(
+            missing.is_empty(),
+            "SchemaSqlWriter must forward every SqlWriter method, missing: {missing:?}"
+        );
+        assert!(
+            declared.len() > 90,
+            "Parsed only {} methods",
+            declared.len()
+        );
+    }
+}
@@ -3,7 +3,7 @@ mod tests {
     use indoc::indoc;
     use rust_decimal::Decimal;
     use std::str::FromStr;
-    use tank::{Entity, Passive, SqlWriter, expr};
+    use tank::{Entity, IsolationLevel, Passive, SqlWriter, TransactionOptions, Value, expr};
     use time::{Date, Month, PrimitiveDateTime, Time};
     use uuid::Uuid;
 
@@ -100,6 +100,31 @@ mod tests {
                 "#}.trim()
             );
         }
+        // UPDATE
+        {
+            let mut out = String::new();
+            let columns = Table::columns();
+            WRITER.write_update::<Table>(
+                &mut out,
+                [
+                    (&columns[0], &Value::Varchar(Some("changed".into()))),
+                    (&columns[1], &Value::Float64(Some(1.5))),
+                ],
+                &expr!(Table::_third_column == 478),
+            );
+            assert_eq!(
+                out,
+                indoc! {r#"
+                    UPDATE "my_table"
+                    SET "special_column" = 'changed', "second_column" = 1.5
+                    WHERE "third_column" = 478;
+                "#}
+                .trim()
+            );
+            let mut out = String::new();
+            WRITER.write_update::<Table>(&mut out, [], &expr!(Table::_third_column == 478));
+            assert_eq!(out, "");
+        }
     }
 
     #[test]
@@ -217,4 +242,46 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_sql_savepoint() {
+        let mut out = String::new();
+        WRITER.write_transaction_savepoint(&mut out, "tank_savepoint_1");
+        assert_eq!(out, r#"SAVEPOINT "tank_savepoint_1";"#);
+        let mut out = String::new();
+        WRITER.write_transaction_rollback_to_savepoint(&mut out, "tank_savepoint_1");
+        assert_eq!(out, r#"ROLLBACK TO SAVEPOINT "tank_savepoint_1";"#);
+        let mut out = String::new();
+        WRITER.write_transaction_release_savepoint(&mut out, "tank_savepoint_1");
+        assert_eq!(out, r#"RELEASE SAVEPOINT "tank_savepoint_1";"#);
+    }
+
+    #[test]
+    fn test_sql_transaction_options() {
+        let mut out = String::new();
+        WRITER.write_transaction_begin_with(&mut out, &Default::default());
+        assert_eq!(out, "BEGIN;");
+        let mut out = String::new();
+        WRITER.write_transaction_begin_with(
+            &mut out,
+            &TransactionOptions {
+                isolation: Some(IsolationLevel::Serializable),
+                read_only: true,
+                deferrable: true,
+            },
+        );
+        assert_eq!(
+            out,
+            "BEGIN ISOLATION LEVEL SERIALIZABLE READ ONLY DEFERRABLE;"
+        );
+        let mut out = String::new();
+        WRITER.write_transaction_begin_with(
+            &mut out,
+            &TransactionOptions {
+                isolation: Some(IsolationLevel::ReadCommitted),
+                ..Default::default()
+            },
+        );
+        assert_eq!(out, "BEGIN ISOLATION LEVEL READ COMMITTED;");
+    }
 }
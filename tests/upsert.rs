@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use tank::{Entity, GenericSqlWriter, OnConflict, SqlWriter};
+
+    #[derive(Entity)]
+    #[tank(name = "operators")]
+    struct Operator {
+        #[tank(primary_key)]
+        id: i64,
+        #[tank(unique)]
+        callsign: String,
+        rank: i32,
+        active: bool,
+    }
+
+    impl Operator {
+        fn sample() -> Self {
+            Self {
+                id: 1,
+                callsign: "Viper".into(),
+                rank: 3,
+                active: true,
+            }
+        }
+    }
+
+    #[derive(Entity)]
+    #[tank(name = "assignments", unique = (Self::squadron, Self::slot))]
+    struct Assignment {
+        #[tank(primary_key)]
+        id: i64,
+        squadron: String,
+        slot: i16,
+        operator: i64,
+    }
+
+    const WRITER: GenericSqlWriter = GenericSqlWriter {};
+
+    #[test]
+    fn test_upsert_default() {
+        let mut query = String::new();
+        WRITER.write_insert_on_conflict(
+            &mut query,
+            [&Operator::sample()],
+            Some(&OnConflict::default()),
+        );
+        assert_eq!(
+            query,
+            indoc! {r#"
+                INSERT INTO "operators" ("id", "callsign", "rank", "active") VALUES
+                (1, 'Viper', 3, true)
+                ON CONFLICT ("id") DO UPDATE SET
+                "callsign" = EXCLUDED."callsign",
+                "rank" = EXCLUDED."rank",
+                "active" = EXCLUDED."active";
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_upsert_unique_target() {
+        let mut query = String::new();
+        WRITER.write_insert_on_conflict(
+            &mut query,
+            [&Assignment {
+                id: 5,
+                squadron: "Red".into(),
+                slot: 2,
+                operator: 1,
+            }],
+            Some(&OnConflict::unique::<Assignment>(0)),
+        );
+        assert_eq!(
+            query,
+            indoc! {r#"
+                INSERT INTO "assignments" ("id", "squadron", "slot", "operator") VALUES
+                (5, 'Red', 2, 1)
+                ON CONFLICT ("squadron", "slot") DO UPDATE SET
+                "operator" = EXCLUDED."operator";
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_upsert_do_nothing() {
+        let mut query = String::new();
+        WRITER.write_insert_on_conflict(
+            &mut query,
+            [&Operator::sample()],
+            Some(&OnConflict::target([Operator::callsign]).do_nothing()),
+        );
+        assert_eq!(
+            query,
+            indoc! {r#"
+                INSERT INTO "operators" ("id", "callsign", "rank", "active") VALUES
+                (1, 'Viper', 3, true)
+                ON CONFLICT ("callsign") DO NOTHING;
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_upsert_selected_columns() {
+        let mut query = String::new();
+        WRITER.write_insert_on_conflict(
+            &mut query,
+            [&Operator::sample()],
+            Some(&OnConflict::target([Operator::callsign]).update([Operator::rank])),
+        );
+        assert_eq!(
+            query,
+            indoc! {r#"
+                INSERT INTO "operators" ("id", "callsign", "rank", "active") VALUES
+                (1, 'Viper', 3, true)
+                ON CONFLICT ("callsign") DO UPDATE SET
+                "rank" = EXCLUDED."rank";
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_upsert_without_conflict() {
+        let mut query = String::new();
+        WRITER.write_insert_on_conflict(&mut query, [&Operator::sample()], None);
+        assert_eq!(
+            query,
+            indoc! {r#"
+                INSERT INTO "operators" ("id", "callsign", "rank", "active") VALUES
+                (1, 'Viper', 3, true);
+            "#}
+            .trim()
+        );
+    }
+}
@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use tank::{Entity, GenericSqlWriter, SqlWriter, Validation, ValidationError};
+
+    #[derive(Entity)]
+    #[tank(name = "pilots")]
+    struct Pilot {
+        #[tank(primary_key)]
+        id: u32,
+        #[tank(validate(length(min = 3, max = 16), regex = "^[A-Z][a-z]+$"))]
+        callsign: String,
+        #[tank(validate(range(min = 18, max = 65)))]
+        age: u8,
+        #[tank(validate(range(min = 0.5)))]
+        rating: Option<f64>,
+    }
+
+    const WRITER: GenericSqlWriter = GenericSqlWriter {};
+
+    #[test]
+    fn test_validation_columns() {
+        let columns = Pilot::columns();
+        assert!(columns[0].validate.is_empty());
+        assert_eq!(
+            columns[1].validate,
+            [
+                Validation::Length {
+                    min: Some(3),
+                    max: Some(16)
+                },
+                Validation::Regex("^[A-Z][a-z]+$".into()),
+            ]
+        );
+        assert_eq!(columns[1].max_length(), Some(16));
+        assert_eq!(
+            columns[2].validate,
+            [Validation::Range {
+                min: Some(18.0),
+                max: Some(65.0)
+            }]
+        );
+    }
+
+    #[test]
+    fn test_validation_create_table() {
+        let mut query = String::new();
+        WRITER.write_create_table::<Pilot>(&mut query, false);
+        assert_eq!(
+            query,
+            indoc! {r#"
+                CREATE TABLE "pilots" (
+                "id" UINTEGER PRIMARY KEY,
+                "callsign" VARCHAR(16) NOT NULL,
+                "age" UTINYINT NOT NULL CHECK ("age" >= 18 AND "age" <= 65),
+                "rating" DOUBLE CHECK ("rating" >= 0.5));
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_validation_entity() {
+        let pilot = Pilot {
+            id: 1,
+            callsign: "Maverick".into(),
+            age: 32,
+            rating: None,
+        };
+        assert!(pilot.validate().is_ok());
+
+        let pilot = Pilot {
+            id: 2,
+            callsign: "ice".into(),
+            age: 17,
+            rating: Some(0.2),
+        };
+        let error = pilot.validate().expect_err("Pilot must be invalid");
+        let error = error
+            .downcast_ref::<ValidationError>()
+            .expect("Must be a validation error");
+        assert_eq!(error.table, "pilots");
+        assert_eq!(
+            error
+                .violations
+                .iter()
+                .map(|v| v.column)
+                .collect::<Vec<_>>(),
+            ["callsign", "age", "rating"]
+        );
+        assert_eq!(
+            error.to_string(),
+            indoc! {r#"
+                Validation failed for `pilots`
+                - `callsign`: "ice" does not match `^[A-Z][a-z]+$`
+                - `age`: value 17 is outside [18, 65]
+                - `rating`: value 0.2 is outside [0.5, ..]
+            "#}
+            .trim()
+        );
+    }
+}
@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use tank::{Entity, GenericSqlWriter, SchemaExport, SqlWriter, TableName, cols, expr, join};
+
+    #[derive(Entity)]
+    #[tank(schema = "fleet", name = "aircraft")]
+    struct Aircraft {
+        #[tank(primary_key)]
+        id: i32,
+        model: String,
+        squadron: i32,
+        airworthy: bool,
+    }
+
+    #[derive(Entity)]
+    #[tank(schema = "fleet", name = "squadrons")]
+    struct Squadron {
+        #[tank(primary_key)]
+        id: i32,
+        name: String,
+    }
+
+    #[derive(Entity)]
+    #[tank(
+        schema = "fleet",
+        name = "squadron_readiness",
+        view = "SELECT squadron, COUNT(*) AS ready FROM fleet.aircraft WHERE airworthy GROUP BY squadron;"
+    )]
+    struct SquadronReadiness {
+        squadron: i32,
+        ready: i64,
+    }
+
+    #[derive(Entity)]
+    #[tank(
+        name = "grounded_aircraft",
+        view = (
+            cols!(Aircraft::id, Aircraft::model, Squadron::name as squadron),
+            join!(Aircraft INNER JOIN Squadron ON Aircraft::squadron == Squadron::id),
+            expr!(!Aircraft::airworthy),
+        )
+    )]
+    struct GroundedAircraft {
+        #[tank(primary_key)]
+        id: i32,
+        model: String,
+        squadron: String,
+    }
+
+    const WRITER: GenericSqlWriter = GenericSqlWriter {};
+
+    #[test]
+    fn test_view_create() {
+        assert!(!Aircraft::is_view());
+        assert!(SquadronReadiness::is_view());
+        let mut query = String::new();
+        WRITER.write_create_view::<SquadronReadiness>(&mut query, false);
+        assert_eq!(
+            query,
+            indoc! {r#"
+                CREATE VIEW "fleet"."squadron_readiness" AS
+                SELECT squadron, COUNT(*) AS ready FROM fleet.aircraft WHERE airworthy GROUP BY squadron;
+            "#}
+            .trim()
+        );
+
+        let mut query = String::new();
+        WRITER.write_create_view::<GroundedAircraft>(&mut query, true);
+        assert_eq!(
+            query,
+            indoc! {r#"
+                CREATE VIEW IF NOT EXISTS "grounded_aircraft" AS
+                SELECT "fleet"."aircraft"."id", "fleet"."aircraft"."model", "fleet"."squadrons"."name" AS squadron
+                FROM "fleet"."aircraft" INNER JOIN "fleet"."squadrons" ON "fleet"."aircraft"."squadron" = "fleet"."squadrons"."id"
+                WHERE NOT "fleet"."aircraft"."airworthy";
+            "#}
+            .trim()
+        );
+    }
+
+    #[test]
+    fn test_view_drop() {
+        let mut query = String::new();
+        WRITER.write_drop_view::<SquadronReadiness>(&mut query, true);
+        assert_eq!(
+            query,
+            r#"DROP VIEW IF EXISTS "fleet"."squadron_readiness";"#
+        );
+    }
+
+    #[test]
+    fn test_view_read_only() {
+        assert!(Aircraft::writable().is_ok());
+        let error = SquadronReadiness::writable().expect_err("A view must be read-only");
+        assert_eq!(
+            error.to_string(),
+            "Entity `fleet.squadron_readiness` is backed by a view, it cannot be written"
+        );
+        assert!(GroundedAircraft::writable().is_err());
+    }
+
+    #[test]
+    fn test_view_export() {
+        let export = SchemaExport::new(WRITER)
+            .entity::<SquadronReadiness>()
+            .entity::<Aircraft>()
+            .entity::<GroundedAircraft>()
+            .entity::<Squadron>();
+        assert_eq!(
+            export.tables(),
+            [
+                &TableName::new("fleet", "aircraft"),
+                &TableName::new("fleet", "squadrons"),
+                &TableName::new("fleet", "squadron_readiness"),
+                &TableName::new("", "grounded_aircraft"),
+            ]
+        );
+        assert!(
+            export
+                .to_sql()
+                .contains("\nCREATE VIEW \"fleet\".\"squadron_readiness\" AS\n"),
+        );
+    }
+}